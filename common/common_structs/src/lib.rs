@@ -19,7 +19,7 @@ pub struct GenericTokenAmountPair<BigUint: BigUintApi> {
     pub amount: BigUint,
 }
 
-#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi)]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi, Clone)]
 pub struct TokenPair {
     pub first_token: TokenIdentifier,
     pub second_token: TokenIdentifier,
@@ -49,6 +49,14 @@ pub struct WrappedFarmTokenAttributes<BigUint: BigUintApi> {
     pub farming_token_amount: BigUint,
 }
 
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct WrappedSwapTokenAttributes<BigUint: BigUintApi> {
+    pub token_id: TokenIdentifier,
+    pub token_total_amount: BigUint,
+    pub locked_assets_invested: BigUint,
+    pub locked_assets_nonce: Nonce,
+}
+
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
 pub struct FarmTokenAttributes<BigUint: BigUintApi> {
     pub reward_per_share: BigUint,
@@ -61,6 +69,15 @@ pub struct FarmTokenAttributes<BigUint: BigUintApi> {
     pub current_farm_amount: BigUint,
 }
 
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct ConcentratedPositionAttributes<BigUint: BigUintApi> {
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: BigUint,
+    pub fee_growth_inside_first_last: BigUint,
+    pub fee_growth_inside_second_last: BigUint,
+}
+
 impl<BigUint: BigUintApi> FftTokenAmountPair<BigUint> {
     pub fn new() -> Self {
         FftTokenAmountPair {
@@ -0,0 +1,169 @@
+#![no_std]
+
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi, Clone, Copy)]
+pub enum Role {
+    Admin,
+    Pauser,
+    FeeManager,
+    EmissionManager,
+    Upgrader,
+}
+
+const ALL_ROLES: [Role; 5] = [
+    Role::Admin,
+    Role::Pauser,
+    Role::FeeManager,
+    Role::EmissionManager,
+    Role::Upgrader,
+];
+
+#[derive(TopEncode)]
+pub struct RoleEvent {
+    caller: Address,
+    role: Role,
+    address: Address,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[elrond_wasm::module]
+pub trait PermissionsModule {
+    // Called from init. Upgrades keep the existing role members.
+    fn init_permissions(&self, admin: &Address) {
+        if self.role_members(Role::Admin).is_empty() {
+            self.role_members(Role::Admin).insert(admin.clone());
+            self.emit_role_granted_event(Role::Admin, admin.clone());
+        }
+    }
+
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, address: Address) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        require!(
+            self.role_members(role).insert(address.clone()),
+            "Address already has role"
+        );
+
+        self.emit_role_granted_event(role, address);
+        Ok(())
+    }
+
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, address: Address) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        self.remove_role_member(role, address)
+    }
+
+    #[endpoint(renounceRole)]
+    fn renounce_role(&self, role: Role) -> SCResult<()> {
+        let caller = self.blockchain().get_caller();
+        self.remove_role_member(role, caller)
+    }
+
+    fn remove_role_member(&self, role: Role, address: Address) -> SCResult<()> {
+        require!(
+            role != Role::Admin || self.role_members(role).len() > 1,
+            "Cannot remove the last admin"
+        );
+        require!(
+            self.role_members(role).remove(&address),
+            "Address does not have role"
+        );
+
+        self.emit_role_revoked_event(role, address);
+        Ok(())
+    }
+
+    // Admins implicitly hold every role.
+    fn require_role(&self, role: Role) -> SCResult<()> {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.has_role(role, caller.clone()) || self.has_role(Role::Admin, caller),
+            "Permission denied"
+        );
+        Ok(())
+    }
+
+    #[view(hasRole)]
+    fn has_role(&self, role: Role, address: Address) -> bool {
+        self.role_members(role).contains(&address)
+    }
+
+    #[view(getRoleMembers)]
+    fn get_role_members(&self, role: Role) -> MultiResultVec<Address> {
+        MultiResultVec::from_iter(self.role_members(role).iter())
+    }
+
+    #[view(getRoles)]
+    fn get_roles(&self, address: Address) -> MultiResultVec<Role> {
+        let roles: Vec<Role> = ALL_ROLES
+            .iter()
+            .filter(|role| self.role_members(**role).contains(&address))
+            .copied()
+            .collect();
+        MultiResultVec::from_iter(roles)
+    }
+
+    #[storage_mapper("permissions_role_members")]
+    fn role_members(&self, role: Role) -> SafeSetMapper<Self::Storage, Address>;
+
+    fn emit_role_granted_event(self, role: Role, address: Address) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.role_granted_event(
+            role,
+            address.clone(),
+            epoch,
+            RoleEvent {
+                caller,
+                role,
+                address,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_role_revoked_event(self, role: Role, address: Address) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.role_revoked_event(
+            role,
+            address.clone(),
+            epoch,
+            RoleEvent {
+                caller,
+                role,
+                address,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    #[event("role_granted")]
+    fn role_granted_event(
+        self,
+        #[indexed] role: Role,
+        #[indexed] address: Address,
+        #[indexed] epoch: u64,
+        role_event: RoleEvent,
+    );
+
+    #[event("role_revoked")]
+    fn role_revoked_event(
+        self,
+        #[indexed] role: Role,
+        #[indexed] address: Address,
+        #[indexed] epoch: u64,
+        role_event: RoleEvent,
+    );
+}
@@ -0,0 +1,47 @@
+#![no_std]
+
+elrond_wasm::imports!();
+
+// Stand-in for the EGLD wrapping contract, used by the router tests. Instead of minting and
+// burning, it hands out wrapped EGLD from its own balance, so it has to be funded with both.
+#[elrond_wasm::contract]
+pub trait EgldWrapperMock {
+    #[init]
+    fn init(&self, wrapped_egld_token_id: TokenIdentifier) {
+        self.wrapped_egld_token_id().set(&wrapped_egld_token_id);
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(wrapEgld)]
+    fn wrap_egld(&self, #[payment_amount] amount: Self::BigUint) -> SCResult<()> {
+        require!(amount > 0, "Payment must be more than 0");
+
+        let caller = self.blockchain().get_caller();
+        let wrapped_egld_token_id = self.wrapped_egld_token_id().get();
+        self.send()
+            .direct(&caller, &wrapped_egld_token_id, 0, &amount, &[]);
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(unwrapEgld)]
+    fn unwrap_egld(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: Self::BigUint,
+    ) -> SCResult<()> {
+        require!(amount > 0, "Payment must be more than 0");
+        require!(
+            token_id == self.wrapped_egld_token_id().get(),
+            "Wrong esdt token"
+        );
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_egld(&caller, &amount, &[]);
+        Ok(())
+    }
+
+    #[view(getWrappedEgldTokenId)]
+    #[storage_mapper("wrapped_egld_token_id")]
+    fn wrapped_egld_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+}
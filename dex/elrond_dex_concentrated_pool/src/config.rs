@@ -0,0 +1,141 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::Nonce;
+
+pub const MAX_FEE_PERCENT: u64 = 100_000;
+
+#[derive(TopEncode, TopDecode, PartialEq, TypeAbi)]
+pub enum State {
+    Inactive,
+    Active,
+    ActiveNoSwaps,
+}
+
+#[elrond_wasm::module]
+pub trait ConfigModule: token_send::TokenSendModule {
+    #[endpoint]
+    fn set_transfer_exec_gas_limit(&self, gas_limit: u64) -> SCResult<()> {
+        self.require_permissions()?;
+        self.transfer_exec_gas_limit().set(&gas_limit);
+        Ok(())
+    }
+
+    fn require_permissions(&self) -> SCResult<()> {
+        let caller = self.blockchain().get_caller();
+        let owner = self.router_owner_address().get();
+        let router = self.router_address().get();
+        require!(caller == owner || caller == router, "Permission denied");
+        Ok(())
+    }
+
+    #[endpoint]
+    fn pause(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.state().set(&State::Inactive);
+        Ok(())
+    }
+
+    #[endpoint]
+    fn resume(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.state().set(&State::Active);
+        Ok(())
+    }
+
+    #[endpoint(setStateActiveNoSwaps)]
+    fn set_state_active_no_swaps(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.state().set(&State::ActiveNoSwaps);
+        Ok(())
+    }
+
+    #[endpoint(setFeePercents)]
+    fn set_fee_percent(&self, total_fee_percent: u64, special_fee_percent: u64) -> SCResult<()> {
+        self.require_permissions()?;
+        self.try_set_fee_percents(total_fee_percent, special_fee_percent)
+    }
+
+    fn try_set_fee_percents(
+        &self,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+    ) -> SCResult<()> {
+        require!(
+            total_fee_percent >= special_fee_percent && total_fee_percent < MAX_FEE_PERCENT,
+            "Bad percents"
+        );
+        self.total_fee_percent().set(&total_fee_percent);
+        self.special_fee_percent().set(&special_fee_percent);
+        Ok(())
+    }
+
+    #[inline]
+    fn is_active(&self) -> bool {
+        let state = self.state().get();
+        state == State::Active || state == State::ActiveNoSwaps
+    }
+
+    #[inline]
+    fn can_swap(&self) -> bool {
+        self.state().get() == State::Active
+    }
+
+    fn require_pool_token(&self, token_id: &TokenIdentifier) -> SCResult<()> {
+        require!(
+            token_id == &self.first_token_id().get() || token_id == &self.second_token_id().get(),
+            "Not a pool token"
+        );
+        Ok(())
+    }
+
+    #[view(getTotalFeePercent)]
+    #[storage_mapper("total_fee_percent")]
+    fn total_fee_percent(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getSpecialFee)]
+    #[storage_mapper("special_fee_percent")]
+    fn special_fee_percent(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getRouterAddress)]
+    #[storage_mapper("router_address")]
+    fn router_address(&self) -> SingleValueMapper<Self::Storage, Address>;
+
+    #[view(getRouterOwnerAddress)]
+    #[storage_mapper("router_owner_address")]
+    fn router_owner_address(&self) -> SingleValueMapper<Self::Storage, Address>;
+
+    #[view(getState)]
+    #[storage_mapper("state")]
+    fn state(&self) -> SingleValueMapper<Self::Storage, State>;
+
+    #[view(getFirstTokenId)]
+    #[storage_mapper("first_token_id")]
+    fn first_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[view(getSecondTokenId)]
+    #[storage_mapper("second_token_id")]
+    fn second_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[view(getTickSpacing)]
+    #[storage_mapper("tick_spacing")]
+    fn tick_spacing(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getPositionTokenId)]
+    #[storage_mapper("position_token_id")]
+    fn position_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[storage_mapper("position_token_nonce")]
+    fn position_token_nonce(&self) -> SingleValueMapper<Self::Storage, Nonce>;
+
+    #[view(getLastErrorMessage)]
+    #[storage_mapper("last_error_message")]
+    fn last_error_message(&self) -> SingleValueMapper<Self::Storage, BoxedBytes>;
+
+    #[view(getReserve)]
+    #[storage_mapper("reserve")]
+    fn pool_reserve(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+}
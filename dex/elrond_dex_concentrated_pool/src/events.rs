@@ -0,0 +1,136 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::{FftTokenAmountPair, GenericTokenAmountPair};
+
+#[derive(TopEncode)]
+pub struct SwapEvent<BigUint: BigUintApi> {
+    caller: Address,
+    token_amount_in: FftTokenAmountPair<BigUint>,
+    token_amount_out: FftTokenAmountPair<BigUint>,
+    fee_amount: BigUint,
+    sqrt_price: BigUint,
+    tick: i32,
+    liquidity: BigUint,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PositionEvent<BigUint: BigUintApi> {
+    caller: Address,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: BigUint,
+    first_token_amount: FftTokenAmountPair<BigUint>,
+    second_token_amount: FftTokenAmountPair<BigUint>,
+    position_token: GenericTokenAmountPair<BigUint>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[elrond_wasm::module]
+pub trait EventsModule {
+    fn emit_swap_event(
+        &self,
+        caller: Address,
+        token_amount_in: FftTokenAmountPair<Self::BigUint>,
+        token_amount_out: FftTokenAmountPair<Self::BigUint>,
+        fee_amount: Self::BigUint,
+        sqrt_price: Self::BigUint,
+        tick: i32,
+        liquidity: Self::BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.swap_event(
+            token_amount_in.token_id.clone(),
+            token_amount_out.token_id.clone(),
+            caller.clone(),
+            epoch,
+            SwapEvent {
+                caller,
+                token_amount_in,
+                token_amount_out,
+                fee_amount,
+                sqrt_price,
+                tick,
+                liquidity,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn build_position_event(
+        &self,
+        caller: Address,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: Self::BigUint,
+        first_token_amount: FftTokenAmountPair<Self::BigUint>,
+        second_token_amount: FftTokenAmountPair<Self::BigUint>,
+        position_token: GenericTokenAmountPair<Self::BigUint>,
+    ) -> PositionEvent<Self::BigUint> {
+        PositionEvent {
+            caller,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            first_token_amount,
+            second_token_amount,
+            position_token,
+            block: self.blockchain().get_block_nonce(),
+            epoch: self.blockchain().get_block_epoch(),
+            timestamp: self.blockchain().get_block_timestamp(),
+        }
+    }
+
+    fn emit_add_liquidity_event(&self, caller: Address, event: PositionEvent<Self::BigUint>) {
+        self.add_liquidity_event(caller, self.blockchain().get_block_epoch(), event)
+    }
+
+    fn emit_remove_liquidity_event(&self, caller: Address, event: PositionEvent<Self::BigUint>) {
+        self.remove_liquidity_event(caller, self.blockchain().get_block_epoch(), event)
+    }
+
+    fn emit_collect_fees_event(&self, caller: Address, event: PositionEvent<Self::BigUint>) {
+        self.collect_fees_event(caller, self.blockchain().get_block_epoch(), event)
+    }
+
+    #[event("swap")]
+    fn swap_event(
+        &self,
+        #[indexed] token_in: TokenIdentifier,
+        #[indexed] token_out: TokenIdentifier,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        swap_event: SwapEvent<Self::BigUint>,
+    );
+
+    #[event("add_liquidity")]
+    fn add_liquidity_event(
+        &self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        add_liquidity_event: PositionEvent<Self::BigUint>,
+    );
+
+    #[event("remove_liquidity")]
+    fn remove_liquidity_event(
+        &self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        remove_liquidity_event: PositionEvent<Self::BigUint>,
+    );
+
+    #[event("collect_fees")]
+    fn collect_fees_event(
+        &self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        collect_fees_event: PositionEvent<Self::BigUint>,
+    );
+}
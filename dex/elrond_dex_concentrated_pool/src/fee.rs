@@ -0,0 +1,141 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use super::config;
+use super::tick_math;
+use super::ticks;
+use core::iter::FromIterator;
+
+mod farm_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait Farm {
+        #[payable("*")]
+        #[endpoint(acceptFee)]
+        fn accept_fee(
+            &self,
+            #[payment_token] token_in: TokenIdentifier,
+            #[payment_amount] amount: Self::BigUint,
+        );
+    }
+}
+
+#[elrond_wasm::module]
+pub trait FeeModule:
+    config::ConfigModule
+    + tick_math::TickMathModule
+    + ticks::TicksModule
+    + token_supply::TokenSupplyModule
+    + token_send::TokenSendModule
+{
+    #[proxy]
+    fn farm_proxy(&self, to: Address) -> farm_proxy::Proxy<Self::SendApi>;
+
+    #[storage_mapper("fee_destination")]
+    fn destination_map(&self) -> SafeMapMapper<Self::Storage, Address, TokenIdentifier>;
+
+    #[view(getFeeState)]
+    fn is_fee_enabled(&self) -> bool {
+        !self.destination_map().is_empty()
+    }
+
+    #[endpoint(setFeeOn)]
+    fn set_fee_on(
+        &self,
+        enabled: bool,
+        fee_to_address: Address,
+        fee_token: TokenIdentifier,
+    ) -> SCResult<()> {
+        self.require_permissions()?;
+        let dest_fee_token = self.destination_map().get(&fee_to_address);
+
+        if enabled {
+            require!(dest_fee_token.is_none(), "Is already a fee destination");
+            self.destination_map().insert(fee_to_address, fee_token);
+        } else {
+            require!(dest_fee_token.is_some(), "Is not a fee destination");
+            require!(
+                Some(fee_token) == dest_fee_token,
+                "Destination fee token differs"
+            );
+            self.destination_map().remove(&fee_to_address);
+        }
+        Ok(())
+    }
+
+    // Fee slices that cannot be delivered as requested go to the active liquidity providers.
+    fn reinject(&self, token: &TokenIdentifier, amount: &Self::BigUint) {
+        let liquidity = self.liquidity().get();
+        if liquidity > 0 {
+            let growth = &(amount * &self.precision()) / &liquidity;
+            if token == &self.first_token_id().get() {
+                self.fee_growth_global_first()
+                    .update(|global| *global = self.wrapping_add(global, &growth));
+            } else {
+                self.fee_growth_global_second()
+                    .update(|global| *global = self.wrapping_add(global, &growth));
+            }
+        }
+        self.pool_reserve(token)
+            .update(|reserve| *reserve += amount);
+    }
+
+    fn send_fee(&self, fee_token: &TokenIdentifier, fee_amount: &Self::BigUint) {
+        if fee_amount == &0 {
+            return;
+        }
+
+        let slices = self.destination_map().len() as u64;
+        if slices == 0 {
+            self.reinject(fee_token, fee_amount);
+            return;
+        }
+
+        let fee_slice = fee_amount / &slices.into();
+        if fee_slice == 0 {
+            self.reinject(fee_token, fee_amount);
+            return;
+        }
+
+        for (fee_address, fee_token_requested) in self.destination_map().iter() {
+            if fee_token == &fee_token_requested {
+                self.send_fee_or_burn_on_zero_address(fee_token, &fee_slice, &fee_address);
+            } else {
+                self.reinject(fee_token, &fee_slice);
+            }
+        }
+
+        let rounding_error = fee_amount - &(fee_slice * slices.into());
+        if rounding_error > 0 {
+            self.reinject(fee_token, &rounding_error);
+        }
+    }
+
+    fn send_fee_or_burn_on_zero_address(
+        &self,
+        token: &TokenIdentifier,
+        amount: &Self::BigUint,
+        destination: &Address,
+    ) {
+        if amount > &0 {
+            if destination == &Address::zero() {
+                self.burn_tokens(token, amount);
+            } else {
+                self.farm_proxy(destination.clone())
+                    .accept_fee(token.clone(), amount.clone())
+                    .execute_on_dest_context();
+            }
+        }
+    }
+
+    #[view(getFeeDestinations)]
+    fn get_fee_destinations(&self) -> MultiResultVec<(Address, TokenIdentifier)> {
+        MultiResultVec::from_iter(
+            self.destination_map()
+                .iter()
+                .map(|x| (x.0, x.1))
+                .collect::<Vec<(Address, TokenIdentifier)>>(),
+        )
+    }
+}
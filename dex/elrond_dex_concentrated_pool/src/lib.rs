@@ -0,0 +1,700 @@
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+const DEFAULT_TRANSFER_EXEC_GAS_LIMIT: u64 = 35000000;
+
+pub mod config;
+mod events;
+pub mod fee;
+mod pool;
+mod position_token;
+pub mod tick_math;
+mod ticks;
+
+use common_structs::{
+    ConcentratedPositionAttributes, FftTokenAmountPair, GenericTokenAmountPair, Nonce,
+};
+use config::State;
+use tick_math::{MAX_TICK, MIN_TICK};
+
+type AddLiquidityResultType<BigUint> = MultiResult3<
+    GenericTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+>;
+
+type RemoveLiquidityResultType<BigUint> = MultiResult3<
+    FftTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+    GenericTokenAmountPair<BigUint>,
+>;
+
+type CollectFeesResultType<BigUint> = MultiResult3<
+    GenericTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+>;
+
+type SwapTokensFixedInputResultType<BigUint> = FftTokenAmountPair<BigUint>;
+
+type SwapTokensFixedOutputResultType<BigUint> =
+    MultiResult2<FftTokenAmountPair<BigUint>, FftTokenAmountPair<BigUint>>;
+
+#[elrond_wasm::contract]
+pub trait ConcentratedPool:
+    config::ConfigModule
+    + tick_math::TickMathModule
+    + ticks::TicksModule
+    + pool::PoolModule
+    + fee::FeeModule
+    + position_token::PositionTokenModule
+    + token_supply::TokenSupplyModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+{
+    #[init]
+    fn init(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        router_address: Address,
+        router_owner_address: Address,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+        tick_spacing: u64,
+        initial_sqrt_price: Self::BigUint,
+    ) -> SCResult<()> {
+        require!(
+            first_token_id.is_valid_esdt_identifier(),
+            "First token ID is not a valid ESDT identifier"
+        );
+        require!(
+            second_token_id.is_valid_esdt_identifier(),
+            "Second token ID is not a valid ESDT identifier"
+        );
+        require!(
+            first_token_id != second_token_id,
+            "Exchange tokens cannot be the same"
+        );
+        require!(
+            tick_spacing > 0 && tick_spacing <= MAX_TICK as u64,
+            "Bad tick spacing"
+        );
+        self.try_set_fee_percents(total_fee_percent, special_fee_percent)?;
+
+        if self.sqrt_price().is_empty() {
+            require!(
+                initial_sqrt_price >= self.get_sqrt_price_at_tick(MIN_TICK)
+                    && initial_sqrt_price < self.get_sqrt_price_at_tick(MAX_TICK),
+                "Initial price out of bounds"
+            );
+            self.sqrt_price().set(&initial_sqrt_price);
+            self.current_tick()
+                .set(&self.get_tick_at_sqrt_price(&initial_sqrt_price));
+            self.tick_spacing().set(&tick_spacing);
+        }
+
+        self.state().set_if_empty(&State::ActiveNoSwaps);
+        self.transfer_exec_gas_limit()
+            .set_if_empty(&DEFAULT_TRANSFER_EXEC_GAS_LIMIT);
+
+        self.router_address().set(&router_address);
+        self.router_owner_address().set(&router_owner_address);
+        self.first_token_id().set(&first_token_id);
+        self.second_token_id().set(&second_token_id);
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(acceptEsdtPayment)]
+    fn accept_esdt_payment(
+        &self,
+        #[payment_token] token: TokenIdentifier,
+        #[payment_amount] payment: Self::BigUint,
+    ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
+        require!(
+            self.call_value().esdt_token_nonce() == 0,
+            "Only fungible tokens are accepted in liquidity pools"
+        );
+        require!(payment > 0, "Payment amount cannot be zero");
+        self.require_pool_token(&token)?;
+
+        let caller = self.blockchain().get_caller();
+        let mut temporary_funds = self.temporary_funds(&caller, &token).get();
+        temporary_funds += payment;
+        self.temporary_funds(&caller, &token).set(&temporary_funds);
+
+        Ok(())
+    }
+
+    #[endpoint(addLiquidity)]
+    fn add_liquidity(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        first_token_amount_desired: Self::BigUint,
+        second_token_amount_desired: Self::BigUint,
+        first_token_amount_min: Self::BigUint,
+        second_token_amount_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<AddLiquidityResultType<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        require!(
+            !self.position_token_id().is_empty(),
+            "Position token not issued"
+        );
+        self.require_valid_ticks(tick_lower, tick_upper)?;
+
+        let caller = self.blockchain().get_caller();
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let temporary_first_token_amount = self.temporary_funds(&caller, &first_token_id).get();
+        let temporary_second_token_amount = self.temporary_funds(&caller, &second_token_id).get();
+        require!(
+            first_token_amount_desired <= temporary_first_token_amount,
+            "Insufficient first token funds to add"
+        );
+        require!(
+            second_token_amount_desired <= temporary_second_token_amount,
+            "Insufficient second token funds to add"
+        );
+
+        let liquidity = self.get_liquidity_for_amounts(
+            &self.sqrt_price().get(),
+            &self.get_sqrt_price_at_tick(tick_lower),
+            &self.get_sqrt_price_at_tick(tick_upper),
+            &first_token_amount_desired,
+            &second_token_amount_desired,
+        );
+        require!(liquidity > 0, "Insufficient liquidity minted");
+
+        let (first_token_amount, second_token_amount) =
+            self.pool_add_position_liquidity(tick_lower, tick_upper, &liquidity);
+        require!(
+            first_token_amount <= first_token_amount_desired
+                && second_token_amount <= second_token_amount_desired,
+            "Insufficient funds to add"
+        );
+        require!(
+            first_token_amount >= first_token_amount_min,
+            "Insufficient first token computed amount"
+        );
+        require!(
+            second_token_amount >= second_token_amount_min,
+            "Insufficient second token computed amount"
+        );
+
+        let (fee_growth_inside_first, fee_growth_inside_second) =
+            self.get_fee_growth_inside(tick_lower, tick_upper);
+        let attributes = ConcentratedPositionAttributes {
+            tick_lower,
+            tick_upper,
+            liquidity: liquidity.clone(),
+            fee_growth_inside_first_last: fee_growth_inside_first,
+            fee_growth_inside_second_last: fee_growth_inside_second,
+        };
+        let position_token =
+            self.send_new_position(&attributes, &caller, &opt_accept_funds_func)?;
+
+        self.temporary_funds(&caller, &first_token_id).clear();
+        self.temporary_funds(&caller, &second_token_id).clear();
+        self.send_tokens(
+            &first_token_id,
+            &(&temporary_first_token_amount - &first_token_amount),
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+        self.send_tokens(
+            &second_token_id,
+            &(&temporary_second_token_amount - &second_token_amount),
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+
+        let first_token_amount = FftTokenAmountPair {
+            token_id: first_token_id,
+            amount: first_token_amount,
+        };
+        let second_token_amount = FftTokenAmountPair {
+            token_id: second_token_id,
+            amount: second_token_amount,
+        };
+        let event = self.build_position_event(
+            caller.clone(),
+            tick_lower,
+            tick_upper,
+            liquidity,
+            first_token_amount.clone(),
+            second_token_amount.clone(),
+            position_token.clone(),
+        );
+        self.emit_add_liquidity_event(caller, event);
+        Ok((position_token, first_token_amount, second_token_amount).into())
+    }
+
+    fn reclaim_temporary_token(
+        &self,
+        caller: &Address,
+        token: &TokenIdentifier,
+        opt_accept_funds_func: &OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        let amount = self.temporary_funds(caller, token).get();
+        self.temporary_funds(caller, token).clear();
+        self.send_tokens(token, &amount, caller, opt_accept_funds_func)?;
+        Ok(())
+    }
+
+    #[endpoint(reclaimTemporaryFunds)]
+    fn reclaim_temporary_funds(
+        &self,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        let caller = self.blockchain().get_caller();
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        self.reclaim_temporary_token(&caller, &first_token_id, &opt_accept_funds_func)?;
+        self.reclaim_temporary_token(&caller, &second_token_id, &opt_accept_funds_func)?;
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(removeLiquidity)]
+    fn remove_liquidity(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_nonce] token_nonce: Nonce,
+        #[payment_amount] amount: Self::BigUint,
+        liquidity: Self::BigUint,
+        first_token_amount_min: Self::BigUint,
+        second_token_amount_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<RemoveLiquidityResultType<Self::BigUint>> {
+        self.require_position_token(&token_id, &amount)?;
+        let attributes = self.get_position_attributes(token_nonce)?;
+        require!(
+            liquidity > 0 && liquidity <= attributes.liquidity,
+            "Bad liquidity amount"
+        );
+
+        let (first_token_fees, second_token_fees) = self.take_position_fees(&attributes);
+        let (mut first_token_amount, mut second_token_amount) = self
+            .pool_remove_position_liquidity(
+                attributes.tick_lower,
+                attributes.tick_upper,
+                &liquidity,
+            );
+        require!(
+            first_token_amount >= first_token_amount_min,
+            "Insufficient first token computed amount"
+        );
+        require!(
+            second_token_amount >= second_token_amount_min,
+            "Insufficient second token computed amount"
+        );
+        first_token_amount += &first_token_fees;
+        second_token_amount += &second_token_fees;
+        self.burn_position_token(token_nonce);
+
+        let caller = self.blockchain().get_caller();
+        let remaining_liquidity = &attributes.liquidity - &liquidity;
+        let position_token = if remaining_liquidity > 0 {
+            let (fee_growth_inside_first, fee_growth_inside_second) =
+                self.get_fee_growth_inside(attributes.tick_lower, attributes.tick_upper);
+            let new_attributes = ConcentratedPositionAttributes {
+                tick_lower: attributes.tick_lower,
+                tick_upper: attributes.tick_upper,
+                liquidity: remaining_liquidity,
+                fee_growth_inside_first_last: fee_growth_inside_first,
+                fee_growth_inside_second_last: fee_growth_inside_second,
+            };
+            self.send_new_position(&new_attributes, &caller, &opt_accept_funds_func)?
+        } else {
+            GenericTokenAmountPair {
+                token_id: token_id.clone(),
+                token_nonce: 0,
+                amount: Self::BigUint::zero(),
+            }
+        };
+
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        self.send_tokens(
+            &first_token_id,
+            &first_token_amount,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+        self.send_tokens(
+            &second_token_id,
+            &second_token_amount,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+
+        let first_token_amount = FftTokenAmountPair {
+            token_id: first_token_id,
+            amount: first_token_amount,
+        };
+        let second_token_amount = FftTokenAmountPair {
+            token_id: second_token_id,
+            amount: second_token_amount,
+        };
+        let event = self.build_position_event(
+            caller.clone(),
+            attributes.tick_lower,
+            attributes.tick_upper,
+            liquidity,
+            first_token_amount.clone(),
+            second_token_amount.clone(),
+            position_token.clone(),
+        );
+        self.emit_remove_liquidity_event(caller, event);
+        Ok((first_token_amount, second_token_amount, position_token).into())
+    }
+
+    #[payable("*")]
+    #[endpoint(collectFees)]
+    fn collect_fees(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_nonce] token_nonce: Nonce,
+        #[payment_amount] amount: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<CollectFeesResultType<Self::BigUint>> {
+        self.require_position_token(&token_id, &amount)?;
+        let attributes = self.get_position_attributes(token_nonce)?;
+
+        let (first_token_fees, second_token_fees) = self.take_position_fees(&attributes);
+        self.burn_position_token(token_nonce);
+
+        let caller = self.blockchain().get_caller();
+        let (fee_growth_inside_first, fee_growth_inside_second) =
+            self.get_fee_growth_inside(attributes.tick_lower, attributes.tick_upper);
+        let new_attributes = ConcentratedPositionAttributes {
+            tick_lower: attributes.tick_lower,
+            tick_upper: attributes.tick_upper,
+            liquidity: attributes.liquidity.clone(),
+            fee_growth_inside_first_last: fee_growth_inside_first,
+            fee_growth_inside_second_last: fee_growth_inside_second,
+        };
+        let position_token =
+            self.send_new_position(&new_attributes, &caller, &opt_accept_funds_func)?;
+
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        self.send_tokens(
+            &first_token_id,
+            &first_token_fees,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+        self.send_tokens(
+            &second_token_id,
+            &second_token_fees,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+
+        let first_token_amount = FftTokenAmountPair {
+            token_id: first_token_id,
+            amount: first_token_fees,
+        };
+        let second_token_amount = FftTokenAmountPair {
+            token_id: second_token_id,
+            amount: second_token_fees,
+        };
+        let event = self.build_position_event(
+            caller.clone(),
+            attributes.tick_lower,
+            attributes.tick_upper,
+            attributes.liquidity,
+            first_token_amount.clone(),
+            second_token_amount.clone(),
+            position_token.clone(),
+        );
+        self.emit_collect_fees_event(caller, event);
+        Ok((position_token, first_token_amount, second_token_amount).into())
+    }
+
+    fn take_position_fees(
+        &self,
+        attributes: &ConcentratedPositionAttributes<Self::BigUint>,
+    ) -> (Self::BigUint, Self::BigUint) {
+        let (fee_growth_inside_first, fee_growth_inside_second) =
+            self.get_fee_growth_inside(attributes.tick_lower, attributes.tick_upper);
+        let first_token_fees = self.compute_fees_owed(
+            &attributes.liquidity,
+            &attributes.fee_growth_inside_first_last,
+            &fee_growth_inside_first,
+        );
+        let second_token_fees = self.compute_fees_owed(
+            &attributes.liquidity,
+            &attributes.fee_growth_inside_second_last,
+            &fee_growth_inside_second,
+        );
+        self.decrease_reserves(&first_token_fees, &second_token_fees);
+        (first_token_fees, second_token_fees)
+    }
+
+    fn send_new_position(
+        &self,
+        attributes: &ConcentratedPositionAttributes<Self::BigUint>,
+        destination: &Address,
+        opt_accept_funds_func: &OptionalArg<BoxedBytes>,
+    ) -> SCResult<GenericTokenAmountPair<Self::BigUint>> {
+        let position_token_id = self.position_token_id().get();
+        let position_token_nonce = self.create_position_token(attributes);
+        let amount = Self::BigUint::from(1u64);
+        self.send_nft_tokens(
+            &position_token_id,
+            position_token_nonce,
+            &amount,
+            destination,
+            opt_accept_funds_func,
+        )?;
+
+        Ok(GenericTokenAmountPair {
+            token_id: position_token_id,
+            token_nonce: position_token_nonce,
+            amount,
+        })
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInput)]
+    fn swap_tokens_fixed_input(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedInputResultType<Self::BigUint>> {
+        require!(self.can_swap(), "Swap is not enabled");
+        require!(amount_in > 0, "Invalid amount_in");
+        require!(token_in != token_out, "Swap with same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+
+        let result =
+            self.compute_swap(&token_in, &amount_in, true, self.get_special_fee_percent())?;
+        require!(
+            result.amount_out >= amount_out_min,
+            "Computed amount out lesser than minimum amount out"
+        );
+        require!(result.amount_out != 0, "Optimal value is zero");
+
+        self.commit_swap(&token_in, &result);
+        self.pool_reserve(&token_in)
+            .update(|reserve| *reserve += &(&amount_in - &result.special_fee));
+        self.pool_reserve(&token_out)
+            .update(|reserve| *reserve -= &result.amount_out);
+
+        //The transaction was made. We are left with $(fee) of $(token_in) as fee.
+        if self.is_fee_enabled() {
+            self.send_fee(&token_in, &result.special_fee);
+        }
+
+        let caller = self.blockchain().get_caller();
+        self.send_tokens(
+            &token_out,
+            &result.amount_out,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+
+        let token_amount_in = FftTokenAmountPair {
+            token_id: token_in,
+            amount: amount_in,
+        };
+        let token_amount_out = FftTokenAmountPair {
+            token_id: token_out,
+            amount: result.amount_out,
+        };
+        self.emit_swap_event(
+            caller,
+            token_amount_in,
+            token_amount_out.clone(),
+            result.special_fee,
+            result.sqrt_price,
+            result.tick,
+            result.liquidity,
+        );
+        Ok(token_amount_out)
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedOutput)]
+    fn swap_tokens_fixed_output(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in_max: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedOutputResultType<Self::BigUint>> {
+        require!(self.can_swap(), "Swap is not enabled");
+        require!(amount_in_max > 0, "Invalid amount_in");
+        require!(token_in != token_out, "Invalid swap with same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+        require!(amount_out != 0, "Desired amount out cannot be zero");
+
+        let result = self.compute_swap(
+            &token_in,
+            &amount_out,
+            false,
+            self.get_special_fee_percent(),
+        )?;
+        require!(
+            result.amount_in <= amount_in_max,
+            "Computed amount in greater than maximum amount in"
+        );
+        let residuum = &amount_in_max - &result.amount_in;
+
+        self.commit_swap(&token_in, &result);
+        self.pool_reserve(&token_in)
+            .update(|reserve| *reserve += &(&result.amount_in - &result.special_fee));
+        self.pool_reserve(&token_out)
+            .update(|reserve| *reserve -= &amount_out);
+
+        //The transaction was made. We are left with $(fee) of $(token_in) as fee.
+        if self.is_fee_enabled() {
+            self.send_fee(&token_in, &result.special_fee);
+        }
+
+        let caller = self.blockchain().get_caller();
+        self.send_tokens(&token_out, &amount_out, &caller, &opt_accept_funds_func)?;
+        self.send_tokens(&token_in, &residuum, &caller, &opt_accept_funds_func)?;
+
+        let token_amount_in = FftTokenAmountPair {
+            token_id: token_in.clone(),
+            amount: result.amount_in,
+        };
+        let token_amount_out = FftTokenAmountPair {
+            token_id: token_out,
+            amount: amount_out,
+        };
+        let residuum_token_amount = FftTokenAmountPair {
+            token_id: token_in,
+            amount: residuum,
+        };
+        self.emit_swap_event(
+            caller,
+            token_amount_in,
+            token_amount_out.clone(),
+            result.special_fee,
+            result.sqrt_price,
+            result.tick,
+            result.liquidity,
+        );
+        Ok((token_amount_out, residuum_token_amount).into())
+    }
+
+    fn get_special_fee_percent(&self) -> u64 {
+        if self.is_fee_enabled() {
+            self.special_fee_percent().get()
+        } else {
+            0
+        }
+    }
+
+    fn send_tokens(
+        &self,
+        token: &TokenIdentifier,
+        amount: &Self::BigUint,
+        destination: &Address,
+        opt_accept_funds_func: &OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        if amount > &0 {
+            self.send_fft_tokens(token, amount, destination, opt_accept_funds_func)?;
+        }
+        Ok(())
+    }
+
+    fn get_other_token(&self, token_id: &TokenIdentifier) -> SCResult<TokenIdentifier> {
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        if token_id == &first_token_id {
+            Ok(second_token_id)
+        } else if token_id == &second_token_id {
+            Ok(first_token_id)
+        } else {
+            sc_error!("Not a known token")
+        }
+    }
+
+    #[view(getAmountOut)]
+    fn get_amount_out_view(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        require!(amount_in > 0, "Zero input");
+        self.require_pool_token(&token_in)?;
+
+        let result = self.compute_swap(&token_in, &amount_in, true, 0)?;
+        Ok(result.amount_out)
+    }
+
+    #[view(getAmountIn)]
+    fn get_amount_in_view(
+        &self,
+        token_wanted: TokenIdentifier,
+        amount_wanted: Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        require!(amount_wanted > 0, "Zero input");
+        let token_in = self.get_other_token(&token_wanted)?;
+
+        let result = self.compute_swap(&token_in, &amount_wanted, false, 0)?;
+        Ok(result.amount_in)
+    }
+
+    #[view(getAmountsForLiquidity)]
+    fn get_amounts_for_liquidity_view(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: Self::BigUint,
+    ) -> SCResult<MultiResult2<Self::BigUint, Self::BigUint>> {
+        self.require_valid_ticks(tick_lower, tick_upper)?;
+        Ok(self
+            .get_amounts_for_liquidity(
+                &self.sqrt_price().get(),
+                &self.get_sqrt_price_at_tick(tick_lower),
+                &self.get_sqrt_price_at_tick(tick_upper),
+                &liquidity,
+                false,
+            )
+            .into())
+    }
+
+    #[view(getFeeGrowthInside)]
+    fn get_fee_growth_inside_view(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> SCResult<MultiResult2<Self::BigUint, Self::BigUint>> {
+        self.require_valid_ticks(tick_lower, tick_upper)?;
+        Ok(self.get_fee_growth_inside(tick_lower, tick_upper).into())
+    }
+
+    #[view(getReserves)]
+    fn get_reserves(&self) -> MultiResult2<Self::BigUint, Self::BigUint> {
+        let first_token_reserve = self.pool_reserve(&self.first_token_id().get()).get();
+        let second_token_reserve = self.pool_reserve(&self.second_token_id().get()).get();
+        (first_token_reserve, second_token_reserve).into()
+    }
+
+    #[view(getTemporaryFunds)]
+    #[storage_mapper("funds")]
+    fn temporary_funds(
+        &self,
+        caller: &Address,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+}
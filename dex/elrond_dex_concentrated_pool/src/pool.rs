@@ -0,0 +1,371 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use super::config;
+use super::tick_math;
+use super::ticks;
+
+use config::MAX_FEE_PERCENT;
+use tick_math::{MAX_TICK, MIN_TICK};
+
+const MAX_SWAP_STEPS: usize = 50;
+
+pub struct SwapResult<BigUint: BigUintApi> {
+    pub amount_in: BigUint,
+    pub amount_out: BigUint,
+    pub special_fee: BigUint,
+    pub sqrt_price: BigUint,
+    pub tick: i32,
+    pub liquidity: BigUint,
+    pub fee_growth_global: BigUint,
+    pub crossed_ticks: Vec<(i32, BigUint)>,
+}
+
+pub struct SwapStep<BigUint: BigUintApi> {
+    pub sqrt_price_next: BigUint,
+    pub amount_in: BigUint,
+    pub amount_out: BigUint,
+    pub fee_amount: BigUint,
+}
+
+#[elrond_wasm::module]
+pub trait PoolModule:
+    config::ConfigModule + tick_math::TickMathModule + ticks::TicksModule + token_send::TokenSendModule
+{
+    fn pool_add_position_liquidity(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: &Self::BigUint,
+    ) -> (Self::BigUint, Self::BigUint) {
+        self.add_tick_liquidity(tick_lower, liquidity, true);
+        self.add_tick_liquidity(tick_upper, liquidity, false);
+
+        let current_tick = self.current_tick().get();
+        if current_tick >= tick_lower && current_tick < tick_upper {
+            self.liquidity().update(|active| *active += liquidity);
+        }
+
+        let (first_token_amount, second_token_amount) = self.get_amounts_for_liquidity(
+            &self.sqrt_price().get(),
+            &self.get_sqrt_price_at_tick(tick_lower),
+            &self.get_sqrt_price_at_tick(tick_upper),
+            liquidity,
+            true,
+        );
+        self.increase_reserves(&first_token_amount, &second_token_amount);
+        (first_token_amount, second_token_amount)
+    }
+
+    fn pool_remove_position_liquidity(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: &Self::BigUint,
+    ) -> (Self::BigUint, Self::BigUint) {
+        let current_tick = self.current_tick().get();
+        if current_tick >= tick_lower && current_tick < tick_upper {
+            self.liquidity().update(|active| *active -= liquidity);
+        }
+
+        self.remove_tick_liquidity(tick_lower, liquidity, true);
+        self.remove_tick_liquidity(tick_upper, liquidity, false);
+
+        let (first_token_amount, second_token_amount) = self.get_amounts_for_liquidity(
+            &self.sqrt_price().get(),
+            &self.get_sqrt_price_at_tick(tick_lower),
+            &self.get_sqrt_price_at_tick(tick_upper),
+            liquidity,
+            false,
+        );
+        self.decrease_reserves(&first_token_amount, &second_token_amount);
+        (first_token_amount, second_token_amount)
+    }
+
+    fn compute_fees_owed(
+        &self,
+        liquidity: &Self::BigUint,
+        fee_growth_inside_last: &Self::BigUint,
+        fee_growth_inside: &Self::BigUint,
+    ) -> Self::BigUint {
+        let growth = self.wrapping_sub(fee_growth_inside, fee_growth_inside_last);
+        &(liquidity * &growth) / &self.precision()
+    }
+
+    fn compute_swap(
+        &self,
+        token_in: &TokenIdentifier,
+        amount_specified: &Self::BigUint,
+        exact_in: bool,
+        special_fee_percent: u64,
+    ) -> SCResult<SwapResult<Self::BigUint>> {
+        let moving_down = token_in == &self.first_token_id().get();
+        let total_fee_percent = self.total_fee_percent().get();
+        let initialized_ticks = self.initialized_ticks().get();
+
+        let mut result = SwapResult {
+            amount_in: Self::BigUint::zero(),
+            amount_out: Self::BigUint::zero(),
+            special_fee: Self::BigUint::zero(),
+            sqrt_price: self.sqrt_price().get(),
+            tick: self.current_tick().get(),
+            liquidity: self.liquidity().get(),
+            fee_growth_global: if moving_down {
+                self.fee_growth_global_first().get()
+            } else {
+                self.fee_growth_global_second().get()
+            },
+            crossed_ticks: Vec::new(),
+        };
+        let mut remaining = amount_specified.clone();
+
+        while remaining > 0 {
+            require!(
+                result.crossed_ticks.len() < MAX_SWAP_STEPS,
+                "Too many ticks crossed"
+            );
+
+            let next_tick =
+                self.get_next_initialized_tick(&initialized_ticks, result.tick, moving_down);
+            let target_tick = match next_tick {
+                Some(tick) => tick,
+                None => {
+                    require!(result.liquidity > 0, "Not enough liquidity");
+                    if moving_down {
+                        MIN_TICK
+                    } else {
+                        MAX_TICK
+                    }
+                }
+            };
+            let sqrt_price_target = self.get_sqrt_price_at_tick(target_tick);
+
+            if result.liquidity > 0 {
+                let step = self.compute_swap_step(
+                    &result.sqrt_price,
+                    &sqrt_price_target,
+                    &result.liquidity,
+                    &remaining,
+                    exact_in,
+                    moving_down,
+                    total_fee_percent,
+                )?;
+
+                if exact_in {
+                    remaining -= &(&step.amount_in + &step.fee_amount);
+                } else {
+                    remaining -= &step.amount_out;
+                }
+                result.amount_in += &(&step.amount_in + &step.fee_amount);
+                result.amount_out += &step.amount_out;
+
+                let special_fee = if total_fee_percent > 0 {
+                    &(&step.fee_amount * &Self::BigUint::from(special_fee_percent))
+                        / &Self::BigUint::from(total_fee_percent)
+                } else {
+                    Self::BigUint::zero()
+                };
+                let lp_fee = &step.fee_amount - &special_fee;
+                result.special_fee += &special_fee;
+                result.fee_growth_global = self.wrapping_add(
+                    &result.fee_growth_global,
+                    &(&(&lp_fee * &self.precision()) / &result.liquidity),
+                );
+                result.sqrt_price = step.sqrt_price_next;
+            } else {
+                result.sqrt_price = sqrt_price_target.clone();
+            }
+
+            if result.sqrt_price == sqrt_price_target {
+                match next_tick {
+                    Some(tick) => {
+                        result.liquidity =
+                            self.get_liquidity_after_cross(tick, &result.liquidity, moving_down);
+                        result
+                            .crossed_ticks
+                            .push((tick, result.fee_growth_global.clone()));
+                        result.tick = if moving_down { tick - 1 } else { tick };
+                    }
+                    None => {
+                        require!(remaining == 0, "Not enough liquidity");
+                    }
+                }
+            } else {
+                result.tick = self.get_tick_at_sqrt_price(&result.sqrt_price);
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn commit_swap(&self, token_in: &TokenIdentifier, result: &SwapResult<Self::BigUint>) {
+        let moving_down = token_in == &self.first_token_id().get();
+        let (global_first, global_second) = if moving_down {
+            (
+                result.fee_growth_global.clone(),
+                self.fee_growth_global_second().get(),
+            )
+        } else {
+            (
+                self.fee_growth_global_first().get(),
+                result.fee_growth_global.clone(),
+            )
+        };
+
+        for (tick, fee_growth_global_at_cross) in result.crossed_ticks.iter() {
+            if moving_down {
+                self.flip_tick_fee_growth(*tick, fee_growth_global_at_cross, &global_second);
+            } else {
+                self.flip_tick_fee_growth(*tick, &global_first, fee_growth_global_at_cross);
+            }
+        }
+
+        self.fee_growth_global_first().set(&global_first);
+        self.fee_growth_global_second().set(&global_second);
+        self.sqrt_price().set(&result.sqrt_price);
+        self.current_tick().set(&result.tick);
+        self.liquidity().set(&result.liquidity);
+    }
+
+    fn compute_swap_step(
+        &self,
+        sqrt_price_current: &Self::BigUint,
+        sqrt_price_target: &Self::BigUint,
+        liquidity: &Self::BigUint,
+        amount_remaining: &Self::BigUint,
+        exact_in: bool,
+        moving_down: bool,
+        fee_percent: u64,
+    ) -> SCResult<SwapStep<Self::BigUint>> {
+        let max_fee = Self::BigUint::from(MAX_FEE_PERCENT);
+        let fee = Self::BigUint::from(fee_percent);
+        let fee_complement = &max_fee - &fee;
+
+        let sqrt_price_next = if exact_in {
+            let remaining_less_fee = &(amount_remaining * &fee_complement) / &max_fee;
+            let amount_in_to_target = self.get_amount_in_delta(
+                sqrt_price_current,
+                sqrt_price_target,
+                liquidity,
+                moving_down,
+            );
+            if remaining_less_fee >= amount_in_to_target {
+                sqrt_price_target.clone()
+            } else if moving_down {
+                self.get_next_sqrt_price_from_first_token(
+                    sqrt_price_current,
+                    liquidity,
+                    &remaining_less_fee,
+                    true,
+                )?
+            } else {
+                self.get_next_sqrt_price_from_second_token(
+                    sqrt_price_current,
+                    liquidity,
+                    &remaining_less_fee,
+                    true,
+                )?
+            }
+        } else {
+            let amount_out_to_target = self.get_amount_out_delta(
+                sqrt_price_current,
+                sqrt_price_target,
+                liquidity,
+                moving_down,
+            );
+            if amount_remaining >= &amount_out_to_target {
+                sqrt_price_target.clone()
+            } else if moving_down {
+                self.get_next_sqrt_price_from_second_token(
+                    sqrt_price_current,
+                    liquidity,
+                    amount_remaining,
+                    false,
+                )?
+            } else {
+                self.get_next_sqrt_price_from_first_token(
+                    sqrt_price_current,
+                    liquidity,
+                    amount_remaining,
+                    false,
+                )?
+            }
+        };
+
+        let reached_target = &sqrt_price_next == sqrt_price_target;
+        let amount_in =
+            self.get_amount_in_delta(sqrt_price_current, &sqrt_price_next, liquidity, moving_down);
+        let mut amount_out =
+            self.get_amount_out_delta(sqrt_price_current, &sqrt_price_next, liquidity, moving_down);
+        if !exact_in && (!reached_target || &amount_out > amount_remaining) {
+            amount_out = amount_remaining.clone();
+        }
+
+        let mut fee_amount = if exact_in && !reached_target {
+            require!(amount_remaining >= &amount_in, "Swap step overflow");
+            amount_remaining - &amount_in
+        } else {
+            self.div_round_up(&(&amount_in * &fee), &fee_complement)
+        };
+        if exact_in && &(&amount_in + &fee_amount) > amount_remaining {
+            fee_amount = amount_remaining - &amount_in;
+        }
+
+        Ok(SwapStep {
+            sqrt_price_next,
+            amount_in,
+            amount_out,
+            fee_amount,
+        })
+    }
+
+    fn get_amount_in_delta(
+        &self,
+        sqrt_price_current: &Self::BigUint,
+        sqrt_price_next: &Self::BigUint,
+        liquidity: &Self::BigUint,
+        moving_down: bool,
+    ) -> Self::BigUint {
+        if moving_down {
+            self.get_first_token_delta(sqrt_price_next, sqrt_price_current, liquidity, true)
+        } else {
+            self.get_second_token_delta(sqrt_price_current, sqrt_price_next, liquidity, true)
+        }
+    }
+
+    fn get_amount_out_delta(
+        &self,
+        sqrt_price_current: &Self::BigUint,
+        sqrt_price_next: &Self::BigUint,
+        liquidity: &Self::BigUint,
+        moving_down: bool,
+    ) -> Self::BigUint {
+        if moving_down {
+            self.get_second_token_delta(sqrt_price_next, sqrt_price_current, liquidity, false)
+        } else {
+            self.get_first_token_delta(sqrt_price_current, sqrt_price_next, liquidity, false)
+        }
+    }
+
+    fn increase_reserves(
+        &self,
+        first_token_amount: &Self::BigUint,
+        second_token_amount: &Self::BigUint,
+    ) {
+        self.pool_reserve(&self.first_token_id().get())
+            .update(|reserve| *reserve += first_token_amount);
+        self.pool_reserve(&self.second_token_id().get())
+            .update(|reserve| *reserve += second_token_amount);
+    }
+
+    fn decrease_reserves(
+        &self,
+        first_token_amount: &Self::BigUint,
+        second_token_amount: &Self::BigUint,
+    ) {
+        self.pool_reserve(&self.first_token_id().get())
+            .update(|reserve| *reserve -= first_token_amount);
+        self.pool_reserve(&self.second_token_id().get())
+            .update(|reserve| *reserve -= second_token_amount);
+    }
+}
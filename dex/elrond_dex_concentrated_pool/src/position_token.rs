@@ -0,0 +1,166 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::{ConcentratedPositionAttributes, Nonce};
+
+use super::config;
+
+const POSITION_TOKEN_AMOUNT: u64 = 1;
+
+#[elrond_wasm::module]
+pub trait PositionTokenModule:
+    config::ConfigModule + token_send::TokenSendModule + token_supply::TokenSupplyModule
+{
+    #[payable("EGLD")]
+    #[endpoint(issuePositionToken)]
+    fn issue_position_token(
+        &self,
+        #[payment_amount] issue_cost: Self::BigUint,
+        token_display_name: BoxedBytes,
+        token_ticker: BoxedBytes,
+    ) -> SCResult<AsyncCall<Self::SendApi>> {
+        self.require_permissions()?;
+        require!(self.position_token_id().is_empty(), "Already issued");
+
+        Ok(ESDTSystemSmartContractProxy::new_proxy_obj(self.send())
+            .issue_semi_fungible(
+                issue_cost,
+                &token_display_name,
+                &token_ticker,
+                SemiFungibleTokenProperties {
+                    can_freeze: true,
+                    can_wipe: true,
+                    can_pause: true,
+                    can_change_owner: true,
+                    can_upgrade: true,
+                    can_add_special_roles: true,
+                },
+            )
+            .async_call()
+            .with_callback(
+                self.callbacks()
+                    .issue_callback(&self.blockchain().get_caller()),
+            ))
+    }
+
+    #[callback]
+    fn issue_callback(
+        &self,
+        caller: &Address,
+        #[call_result] result: AsyncCallResult<TokenIdentifier>,
+    ) {
+        match result {
+            AsyncCallResult::Ok(token_id) => {
+                self.last_error_message().clear();
+
+                if self.position_token_id().is_empty() {
+                    self.position_token_id().set(&token_id);
+                }
+            }
+            AsyncCallResult::Err(message) => {
+                self.last_error_message().set(&message.err_msg);
+
+                let (returned_tokens, token_id) = self.call_value().payment_token_pair();
+                if token_id.is_egld() && returned_tokens > 0 {
+                    self.send().direct_egld(caller, &returned_tokens, &[]);
+                }
+            }
+        }
+    }
+
+    #[endpoint(setLocalRolesPositionToken)]
+    fn set_local_roles_position_token(&self) -> SCResult<AsyncCall<Self::SendApi>> {
+        self.require_permissions()?;
+        require!(
+            !self.position_token_id().is_empty(),
+            "No position token issued"
+        );
+
+        Ok(ESDTSystemSmartContractProxy::new_proxy_obj(self.send())
+            .set_special_roles(
+                &self.blockchain().get_sc_address(),
+                &self.position_token_id().get(),
+                &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn],
+            )
+            .async_call()
+            .with_callback(self.callbacks().change_roles_callback()))
+    }
+
+    #[callback]
+    fn change_roles_callback(&self, #[call_result] result: AsyncCallResult<()>) {
+        match result {
+            AsyncCallResult::Ok(()) => {
+                self.last_error_message().clear();
+            }
+            AsyncCallResult::Err(message) => {
+                self.last_error_message().set(&message.err_msg);
+            }
+        }
+    }
+
+    fn get_position_attributes(
+        &self,
+        token_nonce: Nonce,
+    ) -> SCResult<ConcentratedPositionAttributes<Self::BigUint>> {
+        let token_info = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            &self.position_token_id().get(),
+            token_nonce,
+        );
+
+        let attributes =
+            token_info.decode_attributes::<ConcentratedPositionAttributes<Self::BigUint>>();
+        match attributes {
+            Result::Ok(decoded_obj) => Ok(decoded_obj),
+            Result::Err(_) => {
+                return sc_error!("Decoding error");
+            }
+        }
+    }
+
+    fn create_position_token(
+        &self,
+        attributes: &ConcentratedPositionAttributes<Self::BigUint>,
+    ) -> Nonce {
+        self.nft_create_tokens(
+            &self.position_token_id().get(),
+            &POSITION_TOKEN_AMOUNT.into(),
+            attributes,
+        );
+        self.increase_nonce()
+    }
+
+    fn burn_position_token(&self, token_nonce: Nonce) {
+        self.nft_burn_tokens(
+            &self.position_token_id().get(),
+            token_nonce,
+            &POSITION_TOKEN_AMOUNT.into(),
+        );
+    }
+
+    fn require_position_token(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: &Self::BigUint,
+    ) -> SCResult<()> {
+        require!(
+            !self.position_token_id().is_empty(),
+            "No position token issued"
+        );
+        require!(
+            token_id == &self.position_token_id().get(),
+            "Wrong position token"
+        );
+        require!(
+            amount == &Self::BigUint::from(POSITION_TOKEN_AMOUNT),
+            "Bad position token amount"
+        );
+        Ok(())
+    }
+
+    fn increase_nonce(&self) -> Nonce {
+        let new_nonce = self.position_token_nonce().get() + 1;
+        self.position_token_nonce().set(&new_nonce);
+        new_nonce
+    }
+}
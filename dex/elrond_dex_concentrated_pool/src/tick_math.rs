@@ -0,0 +1,223 @@
+elrond_wasm::imports!();
+
+// Square root prices are fixed point numbers with 18 decimals.
+// Each tick moves the square root price by 0.005%, hence the price by ~0.01%.
+pub const PRECISION: u64 = 1_000_000_000_000_000_000;
+pub const TICK_SQRT_BASE: u64 = 1_000_050_000_000_000_000;
+pub const MIN_TICK: i32 = -400_000;
+pub const MAX_TICK: i32 = 400_000;
+
+#[elrond_wasm::module]
+pub trait TickMathModule {
+    fn precision(&self) -> Self::BigUint {
+        Self::BigUint::from(PRECISION)
+    }
+
+    fn get_sqrt_price_at_tick(&self, tick: i32) -> Self::BigUint {
+        let precision = self.precision();
+        let mut base = Self::BigUint::from(TICK_SQRT_BASE);
+        let mut result = precision.clone();
+        let mut exponent = u64::from(tick.unsigned_abs());
+
+        while exponent != 0 {
+            if exponent % 2 == 1 {
+                result = &(&result * &base) / &precision;
+            }
+            base = &(&base * &base) / &precision;
+            exponent /= 2;
+        }
+
+        if tick < 0 {
+            &(&precision * &precision) / &result
+        } else {
+            result
+        }
+    }
+
+    // Returns the greatest tick whose square root price is lower or equal to the given one.
+    fn get_tick_at_sqrt_price(&self, sqrt_price: &Self::BigUint) -> i32 {
+        let mut low = MIN_TICK;
+        let mut high = MAX_TICK;
+
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            if &self.get_sqrt_price_at_tick(mid) <= sqrt_price {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+
+    fn div_round_up(
+        &self,
+        numerator: &Self::BigUint,
+        denominator: &Self::BigUint,
+    ) -> Self::BigUint {
+        let one = Self::BigUint::from(1u64);
+        let adjusted = &(numerator + denominator) - &one;
+        &adjusted / denominator
+    }
+
+    // Amount of first token between two square root prices, lower one first.
+    fn get_first_token_delta(
+        &self,
+        sqrt_price_lower: &Self::BigUint,
+        sqrt_price_upper: &Self::BigUint,
+        liquidity: &Self::BigUint,
+        round_up: bool,
+    ) -> Self::BigUint {
+        let diff = sqrt_price_upper - sqrt_price_lower;
+        let numerator = &(liquidity * &diff) * &self.precision();
+        let denominator = sqrt_price_lower * sqrt_price_upper;
+        if round_up {
+            self.div_round_up(&numerator, &denominator)
+        } else {
+            &numerator / &denominator
+        }
+    }
+
+    // Amount of second token between two square root prices, lower one first.
+    fn get_second_token_delta(
+        &self,
+        sqrt_price_lower: &Self::BigUint,
+        sqrt_price_upper: &Self::BigUint,
+        liquidity: &Self::BigUint,
+        round_up: bool,
+    ) -> Self::BigUint {
+        let diff = sqrt_price_upper - sqrt_price_lower;
+        let numerator = liquidity * &diff;
+        if round_up {
+            self.div_round_up(&numerator, &self.precision())
+        } else {
+            &numerator / &self.precision()
+        }
+    }
+
+    fn get_next_sqrt_price_from_first_token(
+        &self,
+        sqrt_price: &Self::BigUint,
+        liquidity: &Self::BigUint,
+        amount: &Self::BigUint,
+        add: bool,
+    ) -> SCResult<Self::BigUint> {
+        let liquidity_scaled = liquidity * &self.precision();
+        let numerator = &liquidity_scaled * sqrt_price;
+        let product = amount * sqrt_price;
+        let denominator = if add {
+            &liquidity_scaled + &product
+        } else {
+            require!(liquidity_scaled > product, "Not enough liquidity");
+            &liquidity_scaled - &product
+        };
+        Ok(self.div_round_up(&numerator, &denominator))
+    }
+
+    fn get_next_sqrt_price_from_second_token(
+        &self,
+        sqrt_price: &Self::BigUint,
+        liquidity: &Self::BigUint,
+        amount: &Self::BigUint,
+        add: bool,
+    ) -> SCResult<Self::BigUint> {
+        let scaled_amount = amount * &self.precision();
+        if add {
+            Ok(sqrt_price + &(&scaled_amount / liquidity))
+        } else {
+            let quotient = self.div_round_up(&scaled_amount, liquidity);
+            require!(sqrt_price > &quotient, "Not enough liquidity");
+            Ok(sqrt_price - &quotient)
+        }
+    }
+
+    fn get_liquidity_for_amounts(
+        &self,
+        sqrt_price: &Self::BigUint,
+        sqrt_price_lower: &Self::BigUint,
+        sqrt_price_upper: &Self::BigUint,
+        first_token_amount: &Self::BigUint,
+        second_token_amount: &Self::BigUint,
+    ) -> Self::BigUint {
+        if sqrt_price <= sqrt_price_lower {
+            self.get_liquidity_for_first_token(
+                sqrt_price_lower,
+                sqrt_price_upper,
+                first_token_amount,
+            )
+        } else if sqrt_price < sqrt_price_upper {
+            let first_liquidity = self.get_liquidity_for_first_token(
+                sqrt_price,
+                sqrt_price_upper,
+                first_token_amount,
+            );
+            let second_liquidity = self.get_liquidity_for_second_token(
+                sqrt_price_lower,
+                sqrt_price,
+                second_token_amount,
+            );
+            if first_liquidity < second_liquidity {
+                first_liquidity
+            } else {
+                second_liquidity
+            }
+        } else {
+            self.get_liquidity_for_second_token(
+                sqrt_price_lower,
+                sqrt_price_upper,
+                second_token_amount,
+            )
+        }
+    }
+
+    fn get_liquidity_for_first_token(
+        &self,
+        sqrt_price_lower: &Self::BigUint,
+        sqrt_price_upper: &Self::BigUint,
+        amount: &Self::BigUint,
+    ) -> Self::BigUint {
+        let numerator = &(amount * sqrt_price_lower) * sqrt_price_upper;
+        let denominator = &(sqrt_price_upper - sqrt_price_lower) * &self.precision();
+        &numerator / &denominator
+    }
+
+    fn get_liquidity_for_second_token(
+        &self,
+        sqrt_price_lower: &Self::BigUint,
+        sqrt_price_upper: &Self::BigUint,
+        amount: &Self::BigUint,
+    ) -> Self::BigUint {
+        let numerator = amount * &self.precision();
+        let denominator = sqrt_price_upper - sqrt_price_lower;
+        &numerator / &denominator
+    }
+
+    fn get_amounts_for_liquidity(
+        &self,
+        sqrt_price: &Self::BigUint,
+        sqrt_price_lower: &Self::BigUint,
+        sqrt_price_upper: &Self::BigUint,
+        liquidity: &Self::BigUint,
+        round_up: bool,
+    ) -> (Self::BigUint, Self::BigUint) {
+        if sqrt_price <= sqrt_price_lower {
+            let first_token_amount =
+                self.get_first_token_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up);
+            (first_token_amount, Self::BigUint::zero())
+        } else if sqrt_price < sqrt_price_upper {
+            let first_token_amount =
+                self.get_first_token_delta(sqrt_price, sqrt_price_upper, liquidity, round_up);
+            let second_token_amount =
+                self.get_second_token_delta(sqrt_price_lower, sqrt_price, liquidity, round_up);
+            (first_token_amount, second_token_amount)
+        } else {
+            let second_token_amount = self.get_second_token_delta(
+                sqrt_price_lower,
+                sqrt_price_upper,
+                liquidity,
+                round_up,
+            );
+            (Self::BigUint::zero(), second_token_amount)
+        }
+    }
+}
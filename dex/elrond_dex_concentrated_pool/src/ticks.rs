@@ -0,0 +1,257 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use super::config;
+use super::tick_math;
+
+use tick_math::{MAX_TICK, MIN_TICK};
+
+// Fee growth values are accumulated modulo 2^256. Only differences between
+// two snapshots of the same accumulator are ever used, and those are exact.
+const FEE_GROWTH_MODULUS: [u8; 33] = [
+    1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0,
+];
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct TickInfo<BigUint: BigUintApi> {
+    pub liquidity_gross: BigUint,
+    pub liquidity_starting: BigUint,
+    pub liquidity_ending: BigUint,
+    pub fee_growth_outside_first: BigUint,
+    pub fee_growth_outside_second: BigUint,
+}
+
+#[elrond_wasm::module]
+pub trait TicksModule:
+    config::ConfigModule + tick_math::TickMathModule + token_send::TokenSendModule
+{
+    fn fee_growth_modulus(&self) -> Self::BigUint {
+        Self::BigUint::from_bytes_be(&FEE_GROWTH_MODULUS[..])
+    }
+
+    fn wrapping_add(&self, a: &Self::BigUint, b: &Self::BigUint) -> Self::BigUint {
+        let modulus = self.fee_growth_modulus();
+        let sum = a + b;
+        if sum >= modulus {
+            &sum - &modulus
+        } else {
+            sum
+        }
+    }
+
+    fn wrapping_sub(&self, a: &Self::BigUint, b: &Self::BigUint) -> Self::BigUint {
+        if a >= b {
+            a - b
+        } else {
+            &self.fee_growth_modulus() - &(b - a)
+        }
+    }
+
+    fn require_valid_ticks(&self, tick_lower: i32, tick_upper: i32) -> SCResult<()> {
+        require!(tick_lower < tick_upper, "Bad ticks order");
+        require!(
+            tick_lower >= MIN_TICK && tick_upper <= MAX_TICK,
+            "Ticks out of bounds"
+        );
+        let tick_spacing = self.tick_spacing().get() as i64;
+        require!(
+            (tick_lower as i64) % tick_spacing == 0 && (tick_upper as i64) % tick_spacing == 0,
+            "Ticks not aligned to tick spacing"
+        );
+        Ok(())
+    }
+
+    fn get_tick_info(&self, tick: i32) -> TickInfo<Self::BigUint> {
+        if self.tick_info(tick).is_empty() {
+            TickInfo {
+                liquidity_gross: Self::BigUint::zero(),
+                liquidity_starting: Self::BigUint::zero(),
+                liquidity_ending: Self::BigUint::zero(),
+                fee_growth_outside_first: Self::BigUint::zero(),
+                fee_growth_outside_second: Self::BigUint::zero(),
+            }
+        } else {
+            self.tick_info(tick).get()
+        }
+    }
+
+    fn add_tick_liquidity(&self, tick: i32, liquidity: &Self::BigUint, is_lower: bool) {
+        let mut info = self.get_tick_info(tick);
+
+        if info.liquidity_gross == 0 {
+            // By convention, all the growth before the tick was initialized happened below it.
+            if tick <= self.current_tick().get() {
+                info.fee_growth_outside_first = self.fee_growth_global_first().get();
+                info.fee_growth_outside_second = self.fee_growth_global_second().get();
+            }
+            self.insert_initialized_tick(tick);
+        }
+
+        info.liquidity_gross += liquidity;
+        if is_lower {
+            info.liquidity_starting += liquidity;
+        } else {
+            info.liquidity_ending += liquidity;
+        }
+        self.tick_info(tick).set(&info);
+    }
+
+    fn remove_tick_liquidity(&self, tick: i32, liquidity: &Self::BigUint, is_lower: bool) {
+        let mut info = self.get_tick_info(tick);
+
+        info.liquidity_gross -= liquidity;
+        if is_lower {
+            info.liquidity_starting -= liquidity;
+        } else {
+            info.liquidity_ending -= liquidity;
+        }
+
+        if info.liquidity_gross == 0 {
+            self.tick_info(tick).clear();
+            self.remove_initialized_tick(tick);
+        } else {
+            self.tick_info(tick).set(&info);
+        }
+    }
+
+    fn insert_initialized_tick(&self, tick: i32) {
+        self.initialized_ticks().update(|ticks| {
+            if let Result::Err(index) = ticks.binary_search(&tick) {
+                ticks.insert(index, tick);
+            }
+        });
+    }
+
+    fn remove_initialized_tick(&self, tick: i32) {
+        self.initialized_ticks().update(|ticks| {
+            if let Result::Ok(index) = ticks.binary_search(&tick) {
+                ticks.remove(index);
+            }
+        });
+    }
+
+    // Next initialized tick at or below the given tick when moving down, strictly above it otherwise.
+    fn get_next_initialized_tick(
+        &self,
+        initialized_ticks: &[i32],
+        tick: i32,
+        moving_down: bool,
+    ) -> Option<i32> {
+        if moving_down {
+            initialized_ticks
+                .iter()
+                .rev()
+                .find(|&&t| t <= tick)
+                .copied()
+        } else {
+            initialized_ticks.iter().find(|&&t| t > tick).copied()
+        }
+    }
+
+    fn flip_tick_fee_growth(
+        &self,
+        tick: i32,
+        fee_growth_global_first: &Self::BigUint,
+        fee_growth_global_second: &Self::BigUint,
+    ) {
+        let mut info = self.get_tick_info(tick);
+        info.fee_growth_outside_first =
+            self.wrapping_sub(fee_growth_global_first, &info.fee_growth_outside_first);
+        info.fee_growth_outside_second =
+            self.wrapping_sub(fee_growth_global_second, &info.fee_growth_outside_second);
+        self.tick_info(tick).set(&info);
+    }
+
+    fn get_liquidity_after_cross(
+        &self,
+        tick: i32,
+        liquidity: &Self::BigUint,
+        moving_down: bool,
+    ) -> Self::BigUint {
+        let info = self.get_tick_info(tick);
+        if moving_down {
+            &(liquidity + &info.liquidity_ending) - &info.liquidity_starting
+        } else {
+            &(liquidity + &info.liquidity_starting) - &info.liquidity_ending
+        }
+    }
+
+    fn get_fee_growth_inside(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> (Self::BigUint, Self::BigUint) {
+        let current_tick = self.current_tick().get();
+        let global_first = self.fee_growth_global_first().get();
+        let global_second = self.fee_growth_global_second().get();
+        let lower = self.get_tick_info(tick_lower);
+        let upper = self.get_tick_info(tick_upper);
+
+        let (below_first, below_second) = if current_tick >= tick_lower {
+            (
+                lower.fee_growth_outside_first,
+                lower.fee_growth_outside_second,
+            )
+        } else {
+            (
+                self.wrapping_sub(&global_first, &lower.fee_growth_outside_first),
+                self.wrapping_sub(&global_second, &lower.fee_growth_outside_second),
+            )
+        };
+
+        let (above_first, above_second) = if current_tick < tick_upper {
+            (
+                upper.fee_growth_outside_first,
+                upper.fee_growth_outside_second,
+            )
+        } else {
+            (
+                self.wrapping_sub(&global_first, &upper.fee_growth_outside_first),
+                self.wrapping_sub(&global_second, &upper.fee_growth_outside_second),
+            )
+        };
+
+        let inside_first = self.wrapping_sub(
+            &self.wrapping_sub(&global_first, &below_first),
+            &above_first,
+        );
+        let inside_second = self.wrapping_sub(
+            &self.wrapping_sub(&global_second, &below_second),
+            &above_second,
+        );
+        (inside_first, inside_second)
+    }
+
+    #[view(getTickInfo)]
+    fn get_tick_info_view(&self, tick: i32) -> TickInfo<Self::BigUint> {
+        self.get_tick_info(tick)
+    }
+
+    #[storage_mapper("tick_info")]
+    fn tick_info(&self, tick: i32) -> SingleValueMapper<Self::Storage, TickInfo<Self::BigUint>>;
+
+    #[view(getInitializedTicks)]
+    #[storage_mapper("initialized_ticks")]
+    fn initialized_ticks(&self) -> SingleValueMapper<Self::Storage, Vec<i32>>;
+
+    #[view(getCurrentTick)]
+    #[storage_mapper("current_tick")]
+    fn current_tick(&self) -> SingleValueMapper<Self::Storage, i32>;
+
+    #[view(getSqrtPrice)]
+    #[storage_mapper("sqrt_price")]
+    fn sqrt_price(&self) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+
+    #[view(getLiquidity)]
+    #[storage_mapper("liquidity")]
+    fn liquidity(&self) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+
+    #[view(getFeeGrowthGlobalFirst)]
+    #[storage_mapper("fee_growth_global_first")]
+    fn fee_growth_global_first(&self) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+
+    #[view(getFeeGrowthGlobalSecond)]
+    #[storage_mapper("fee_growth_global_second")]
+    fn fee_growth_global_second(&self) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+}
@@ -2,6 +2,7 @@ elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
 use common_structs::Nonce;
+use permissions::Role;
 
 pub const MAX_PENALTY_PERCENT: u64 = 10_000;
 pub const DEFAULT_PENALTY_PERCENT: u64 = 100;
@@ -18,7 +19,10 @@ pub enum State {
 
 #[elrond_wasm::module]
 pub trait ConfigModule:
-    token_supply::TokenSupplyModule + token_send::TokenSendModule + nft_deposit::NftDepositModule
+    token_supply::TokenSupplyModule
+    + token_send::TokenSendModule
+    + nft_deposit::NftDepositModule
+    + permissions::PermissionsModule
 {
     #[inline]
     fn is_active(&self) -> bool {
@@ -26,17 +30,16 @@ pub trait ConfigModule:
         state == State::Active
     }
 
-    fn require_permissions(&self) -> SCResult<()> {
-        let caller = self.blockchain().get_caller();
-        let owner = self.owner().get();
-        let router = self.router_address().get();
-        require!(caller == owner || caller == router, "Permission denied");
-        Ok(())
+    fn require_permissions(&self, role: Role) -> SCResult<()> {
+        if self.blockchain().get_caller() == self.router_address().get() {
+            return Ok(());
+        }
+        self.require_role(role)
     }
 
     #[endpoint]
     fn set_penalty_percent(&self, percent: u64) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::EmissionManager)?;
         require!(
             percent < MAX_PENALTY_PERCENT,
             "Percent cannot exceed max percent"
@@ -47,7 +50,7 @@ pub trait ConfigModule:
 
     #[endpoint]
     fn set_locked_rewards_apr_multiplier(&self, muliplier: u8) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::EmissionManager)?;
         require!(muliplier > 0, "Multiplier cannot be zero");
         self.locked_rewards_apr_multiplier().set(&muliplier);
         Ok(())
@@ -55,35 +58,35 @@ pub trait ConfigModule:
 
     #[endpoint]
     fn set_minimum_farming_epochs(&self, epochs: u8) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::EmissionManager)?;
         self.minimum_farming_epochs().set(&epochs);
         Ok(())
     }
 
     #[endpoint]
     fn set_transfer_exec_gas_limit(&self, gas_limit: u64) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::Admin)?;
         self.transfer_exec_gas_limit().set(&gas_limit);
         Ok(())
     }
 
     #[endpoint(setNftDepositMaxLen)]
     fn set_nft_deposit_max_len(&self, max_len: usize) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::Admin)?;
         self.nft_deposit_max_len().set(&max_len);
         Ok(())
     }
 
     #[endpoint]
     fn pause(&self) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::Pauser)?;
         self.state().set(&State::Inactive);
         Ok(())
     }
 
     #[endpoint]
     fn resume(&self) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::Pauser)?;
         self.state().set(&State::Active);
         Ok(())
     }
@@ -2,6 +2,7 @@ elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
 use common_structs::{FarmTokenAttributes, GenericTokenAmountPair, Nonce};
+use permissions::Role;
 
 use super::config;
 
@@ -17,6 +18,7 @@ pub trait FarmTokenModule:
     + token_send::TokenSendModule
     + token_supply::TokenSupplyModule
     + nft_deposit::NftDepositModule
+    + permissions::PermissionsModule
 {
     #[payable("EGLD")]
     #[endpoint(issueFarmToken)]
@@ -27,7 +29,7 @@ pub trait FarmTokenModule:
         token_ticker: BoxedBytes,
     ) -> SCResult<AsyncCall<Self::SendApi>> {
         require!(self.is_active(), "Not active");
-        self.require_permissions()?;
+        self.require_permissions(Role::Admin)?;
         require!(self.farm_token_id().is_empty(), "Already issued");
 
         Ok(self.issue_token(issue_cost, token_display_name, token_ticker))
@@ -89,7 +91,7 @@ pub trait FarmTokenModule:
     #[endpoint(setLocalRolesFarmToken)]
     fn set_local_roles_farm_token(&self) -> SCResult<AsyncCall<Self::SendApi>> {
         require!(self.is_active(), "Not active");
-        self.require_permissions()?;
+        self.require_permissions(Role::Admin)?;
         require!(!self.farm_token_id().is_empty(), "No farm token issued");
 
         let token = self.farm_token_id().get();
@@ -17,6 +17,7 @@ pub trait FarmTokenMergeModule:
     + token_supply::TokenSupplyModule
     + config::ConfigModule
     + token_merge::TokenMergeModule
+    + permissions::PermissionsModule
 {
     #[endpoint(mergeFarmTokens)]
     fn merge_farm_tokens(
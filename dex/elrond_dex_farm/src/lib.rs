@@ -40,6 +40,7 @@ pub trait Farm:
     + farm_token::FarmTokenModule
     + farm_token_merge::FarmTokenMergeModule
     + events::EventsModule
+    + permissions::PermissionsModule
 {
     #[proxy]
     fn locked_asset_factory(&self, to: Address) -> sc_locked_asset_factory::Proxy<Self::SendApi>;
@@ -94,6 +95,7 @@ pub trait Farm:
             .set_if_empty(&DEFAULT_NFT_DEPOSIT_MAX_LEN);
 
         self.owner().set(&self.blockchain().get_caller());
+        self.init_permissions(&self.blockchain().get_caller());
         self.router_address().set(&router_address);
         self.reward_token_id().set(&reward_token_id);
         self.farming_token_id().set(&farming_token_id);
@@ -6,6 +6,7 @@ use crate::farm_token;
 use super::config;
 
 use common_structs::Nonce;
+use permissions::Role;
 
 #[elrond_wasm::module]
 pub trait RewardsModule:
@@ -14,6 +15,7 @@ pub trait RewardsModule:
     + token_send::TokenSendModule
     + nft_deposit::NftDepositModule
     + farm_token::FarmTokenModule
+    + permissions::PermissionsModule
 {
     fn calculate_per_block_rewards(
         &self,
@@ -143,7 +145,7 @@ pub trait RewardsModule:
 
     #[endpoint]
     fn start_produce_rewards(&self) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::EmissionManager)?;
         require!(
             self.per_block_reward_amount().get() != 0,
             "Cannot produce zero reward amount"
@@ -160,7 +162,7 @@ pub trait RewardsModule:
 
     #[endpoint]
     fn end_produce_rewards(&self) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::EmissionManager)?;
         let reward_token_id = self.reward_token_id().get();
         self.generate_aggregated_rewards(&reward_token_id);
         self.produce_rewards_enabled().set(&false);
@@ -169,7 +171,7 @@ pub trait RewardsModule:
 
     #[endpoint(setPerBlockRewardAmount)]
     fn set_per_block_rewards(&self, per_block_amount: Self::BigUint) -> SCResult<()> {
-        self.require_permissions()?;
+        self.require_permissions(Role::EmissionManager)?;
         require!(per_block_amount != 0, "Amount cannot be zero");
         let reward_token_id = self.reward_token_id().get();
         self.generate_aggregated_rewards(&reward_token_id);
@@ -0,0 +1,88 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+#[derive(TopEncode, TopDecode, PartialEq, TypeAbi)]
+pub enum State {
+    Inactive,
+    Active,
+    Migrated,
+}
+
+#[elrond_wasm::module]
+pub trait ConfigModule: token_send::TokenSendModule {
+    #[endpoint]
+    fn pause(&self) -> SCResult<()> {
+        only_owner!(self, "Permission denied");
+        require!(
+            self.state().get() != State::Migrated,
+            "Liquidity already migrated"
+        );
+        self.state().set(&State::Inactive);
+        Ok(())
+    }
+
+    #[endpoint]
+    fn resume(&self) -> SCResult<()> {
+        only_owner!(self, "Permission denied");
+        require!(
+            self.state().get() != State::Migrated,
+            "Liquidity already migrated"
+        );
+        self.state().set(&State::Active);
+        Ok(())
+    }
+
+    #[endpoint]
+    fn set_transfer_exec_gas_limit(&self, gas_limit: u64) -> SCResult<()> {
+        only_owner!(self, "Permission denied");
+        self.transfer_exec_gas_limit().set(&gas_limit);
+        Ok(())
+    }
+
+    #[view(getState)]
+    #[storage_mapper("state")]
+    fn state(&self) -> SingleValueMapper<Self::Storage, State>;
+
+    #[view(getRouterAddress)]
+    #[storage_mapper("router_address")]
+    fn router_address(&self) -> SingleValueMapper<Self::Storage, Address>;
+
+    #[view(getFirstTokenId)]
+    #[storage_mapper("first_token_id")]
+    fn first_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[view(getSecondTokenId)]
+    #[storage_mapper("second_token_id")]
+    fn second_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[view(getTotalFeePercent)]
+    #[storage_mapper("total_fee_percent")]
+    fn total_fee_percent(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getStartWeight)]
+    #[storage_mapper("start_weight")]
+    fn start_weight(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getEndWeight)]
+    #[storage_mapper("end_weight")]
+    fn end_weight(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getStartBlock)]
+    #[storage_mapper("start_block")]
+    fn start_block(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getEndBlock)]
+    #[storage_mapper("end_block")]
+    fn end_block(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getMigratedPairAddress)]
+    #[storage_mapper("migrated_pair_address")]
+    fn migrated_pair_address(&self) -> SingleValueMapper<Self::Storage, Address>;
+
+    #[view(getReserve)]
+    #[storage_mapper("reserve")]
+    fn pool_reserve(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+}
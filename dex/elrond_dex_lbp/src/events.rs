@@ -0,0 +1,102 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::FftTokenAmountPair;
+
+#[derive(TopEncode)]
+pub struct SwapEvent<BigUint: BigUintApi> {
+    caller: Address,
+    token_amount_in: FftTokenAmountPair<BigUint>,
+    token_amount_out: FftTokenAmountPair<BigUint>,
+    first_token_weight: u64,
+    second_token_weight: u64,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct MigrateEvent<BigUint: BigUintApi> {
+    caller: Address,
+    pair_address: Address,
+    first_token_amount: FftTokenAmountPair<BigUint>,
+    second_token_amount: FftTokenAmountPair<BigUint>,
+    lp_token_amount: FftTokenAmountPair<BigUint>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[elrond_wasm::module]
+pub trait EventsModule {
+    fn emit_swap_event(
+        &self,
+        caller: Address,
+        token_amount_in: FftTokenAmountPair<Self::BigUint>,
+        token_amount_out: FftTokenAmountPair<Self::BigUint>,
+        weights: (u64, u64),
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.swap_event(
+            token_amount_in.token_id.clone(),
+            token_amount_out.token_id.clone(),
+            caller.clone(),
+            epoch,
+            SwapEvent {
+                caller,
+                token_amount_in,
+                token_amount_out,
+                first_token_weight: weights.0,
+                second_token_weight: weights.1,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_migrate_event(
+        &self,
+        caller: Address,
+        pair_address: Address,
+        first_token_amount: FftTokenAmountPair<Self::BigUint>,
+        second_token_amount: FftTokenAmountPair<Self::BigUint>,
+        lp_token_amount: FftTokenAmountPair<Self::BigUint>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.migrate_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            MigrateEvent {
+                caller,
+                pair_address,
+                first_token_amount,
+                second_token_amount,
+                lp_token_amount,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    #[event("swap")]
+    fn swap_event(
+        &self,
+        #[indexed] token_in: TokenIdentifier,
+        #[indexed] token_out: TokenIdentifier,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        swap_event: SwapEvent<Self::BigUint>,
+    );
+
+    #[event("migrate")]
+    fn migrate_event(
+        &self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        migrate_event: MigrateEvent<Self::BigUint>,
+    );
+}
@@ -0,0 +1,539 @@
+#![no_std]
+#![allow(clippy::too_many_arguments)]
+
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+const DEFAULT_TRANSFER_EXEC_GAS_LIMIT: u64 = 35000000;
+const ACCEPT_PAY_FUNC_NAME: &[u8] = b"acceptPay";
+
+pub mod config;
+mod events;
+
+use common_structs::FftTokenAmountPair;
+use config::State;
+use elrond_dex_weighted_pool::config::{MIN_TOKEN_WEIGHT, TOTAL_WEIGHT};
+use elrond_dex_weighted_pool::math::{MAX_IN_RATIO, MAX_OUT_RATIO};
+
+type SwapTokensFixedInputResultType<BigUint> = FftTokenAmountPair<BigUint>;
+
+type SwapTokensFixedOutputResultType<BigUint> =
+    MultiResult2<FftTokenAmountPair<BigUint>, FftTokenAmountPair<BigUint>>;
+
+mod router_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait Router {
+        #[view(getPair)]
+        fn get_pair(
+            &self,
+            first_token_id: TokenIdentifier,
+            second_token_id: TokenIdentifier,
+        ) -> Option<Address>;
+    }
+}
+
+mod pair_tokens_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait Pair {
+        #[view(getFirstTokenId)]
+        fn get_first_token_id(&self) -> TokenIdentifier;
+
+        #[view(getSecondTokenId)]
+        fn get_second_token_id(&self) -> TokenIdentifier;
+    }
+}
+
+#[elrond_wasm::contract]
+pub trait LiquidityBootstrappingPool:
+    config::ConfigModule
+    + elrond_dex_weighted_pool::math::WeightedMathModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+{
+    #[proxy]
+    fn router_proxy(&self, to: Address) -> router_proxy::Proxy<Self::SendApi>;
+
+    #[proxy]
+    fn pair_proxy(&self, to: Address) -> elrond_dex_pair::Proxy<Self::SendApi>;
+
+    #[proxy]
+    fn pair_tokens_proxy(&self, to: Address) -> pair_tokens_proxy::Proxy<Self::SendApi>;
+
+    #[init]
+    fn init(
+        &self,
+        router_address: Address,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        start_weight: u64,
+        end_weight: u64,
+        start_block: u64,
+        end_block: u64,
+        total_fee_percent: u64,
+    ) -> SCResult<()> {
+        require!(
+            first_token_id.is_valid_esdt_identifier(),
+            "First token ID is not a valid ESDT identifier"
+        );
+        require!(
+            second_token_id.is_valid_esdt_identifier(),
+            "Second token ID is not a valid ESDT identifier"
+        );
+        require!(
+            first_token_id != second_token_id,
+            "Exchange tokens cannot be the same"
+        );
+        self.require_valid_weight(start_weight)?;
+        self.require_valid_weight(end_weight)?;
+        require!(
+            start_block >= self.blockchain().get_block_nonce(),
+            "Start block in the past"
+        );
+        require!(start_block < end_block, "Bad blocks interval");
+        require!(total_fee_percent < 100_000, "Bad percents");
+
+        self.state().set_if_empty(&State::Inactive);
+        self.transfer_exec_gas_limit()
+            .set_if_empty(&DEFAULT_TRANSFER_EXEC_GAS_LIMIT);
+
+        self.router_address().set(&router_address);
+        self.first_token_id().set(&first_token_id);
+        self.second_token_id().set(&second_token_id);
+        self.start_weight().set(&start_weight);
+        self.end_weight().set(&end_weight);
+        self.start_block().set(&start_block);
+        self.end_block().set(&end_block);
+        self.total_fee_percent().set(&total_fee_percent);
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(depositLiquidity)]
+    fn deposit_liquidity(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: Self::BigUint,
+    ) -> SCResult<()> {
+        only_owner!(self, "Permission denied");
+        require!(
+            self.blockchain().get_block_nonce() < self.start_block().get(),
+            "Launch already started"
+        );
+        require!(amount > 0, "Payment amount cannot be zero");
+        require!(
+            token_id == self.first_token_id().get() || token_id == self.second_token_id().get(),
+            "Invalid token"
+        );
+
+        self.pool_reserve(&token_id)
+            .update(|reserve| *reserve += amount);
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(acceptPay)]
+    fn accept_pay(&self) {}
+
+    #[endpoint(migrateToPair)]
+    fn migrate_to_pair(
+        &self,
+        first_token_amount_min: Self::BigUint,
+        second_token_amount_min: Self::BigUint,
+    ) -> SCResult<()> {
+        only_owner!(self, "Permission denied");
+        require!(
+            self.state().get() != State::Migrated,
+            "Liquidity already migrated"
+        );
+        require!(
+            self.blockchain().get_block_nonce() > self.end_block().get(),
+            "Launch not ended"
+        );
+
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let pair_address = self
+            .router_proxy(self.router_address().get())
+            .get_pair(first_token_id.clone(), second_token_id.clone())
+            .execute_on_dest_context();
+        require!(pair_address.is_some(), "Pair not created through router");
+        let pair_address = pair_address.unwrap();
+
+        // The router finds the pair for either token order, the amounts follow the pair's order.
+        let pair_first_token_id = self
+            .pair_tokens_proxy(pair_address.clone())
+            .get_first_token_id()
+            .execute_on_dest_context();
+        let pair_second_token_id = self
+            .pair_tokens_proxy(pair_address.clone())
+            .get_second_token_id()
+            .execute_on_dest_context();
+        let same_order =
+            pair_first_token_id == first_token_id && pair_second_token_id == second_token_id;
+        let reversed_order =
+            pair_first_token_id == second_token_id && pair_second_token_id == first_token_id;
+        require!(same_order || reversed_order, "Pair tokens mismatch");
+
+        let first_token_reserve = self.pool_reserve(&first_token_id).get();
+        let second_token_reserve = self.pool_reserve(&second_token_id).get();
+        require!(
+            first_token_reserve > 0 && second_token_reserve > 0,
+            "No liquidity to migrate"
+        );
+        self.pool_reserve(&first_token_id).clear();
+        self.pool_reserve(&second_token_id).clear();
+        self.state().set(&State::Migrated);
+        self.migrated_pair_address().set(&pair_address);
+
+        self.pair_proxy(pair_address.clone())
+            .accept_esdt_payment(first_token_id.clone(), first_token_reserve.clone())
+            .execute_on_dest_context();
+        self.pair_proxy(pair_address.clone())
+            .accept_esdt_payment(second_token_id.clone(), second_token_reserve.clone())
+            .execute_on_dest_context();
+        let (pair_first_token_args, pair_second_token_args) = if same_order {
+            (
+                (first_token_reserve.clone(), first_token_amount_min),
+                (second_token_reserve.clone(), second_token_amount_min),
+            )
+        } else {
+            (
+                (second_token_reserve.clone(), second_token_amount_min),
+                (first_token_reserve.clone(), first_token_amount_min),
+            )
+        };
+        let (lp_token_amount, pair_first_token_amount, pair_second_token_amount) = self
+            .pair_proxy(pair_address.clone())
+            .add_liquidity(
+                pair_first_token_args.0,
+                pair_second_token_args.0,
+                pair_first_token_args.1,
+                pair_second_token_args.1,
+                OptionalArg::Some(BoxedBytes::from(ACCEPT_PAY_FUNC_NAME)),
+            )
+            .execute_on_dest_context_custom_range(|_, after| (after - 3, after))
+            .into_tuple();
+        let (first_token_amount, second_token_amount) = if same_order {
+            (pair_first_token_amount, pair_second_token_amount)
+        } else {
+            (pair_second_token_amount, pair_first_token_amount)
+        };
+        require!(
+            first_token_amount.token_id == first_token_id
+                && second_token_amount.token_id == second_token_id,
+            "Bad tokens received from pair"
+        );
+
+        // The pair returns whatever it did not use, all of it belongs to the owner.
+        let owner = self.blockchain().get_owner_address();
+        let first_token_unused = &first_token_reserve - &first_token_amount.amount;
+        let second_token_unused = &second_token_reserve - &second_token_amount.amount;
+        self.send_tokens(&lp_token_amount.token_id, &lp_token_amount.amount, &owner)?;
+        self.send_tokens(&first_token_id, &first_token_unused, &owner)?;
+        self.send_tokens(&second_token_id, &second_token_unused, &owner)?;
+
+        self.emit_migrate_event(
+            owner,
+            pair_address,
+            first_token_amount,
+            second_token_amount,
+            lp_token_amount,
+        );
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInput)]
+    fn swap_tokens_fixed_input(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedInputResultType<Self::BigUint>> {
+        require!(self.can_swap(), "Swap is not enabled");
+        require!(amount_in > 0, "Invalid amount_in");
+        require!(token_in != token_out, "Swap with same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+
+        let amount_out = self.compute_amount_out(&token_in, &token_out, &amount_in)?;
+        require!(
+            amount_out >= amount_out_min,
+            "Computed amount out lesser than minimum amount out"
+        );
+        require!(amount_out != 0, "Optimal value is zero");
+
+        self.pool_reserve(&token_in)
+            .update(|reserve| *reserve += &amount_in);
+        self.pool_reserve(&token_out)
+            .update(|reserve| *reserve -= &amount_out);
+
+        let caller = self.blockchain().get_caller();
+        self.send_tokens_with_func(&token_out, &amount_out, &caller, &opt_accept_funds_func)?;
+
+        let token_amount_in = FftTokenAmountPair {
+            token_id: token_in,
+            amount: amount_in,
+        };
+        let token_amount_out = FftTokenAmountPair {
+            token_id: token_out,
+            amount: amount_out,
+        };
+        self.emit_swap_event(
+            caller,
+            token_amount_in,
+            token_amount_out.clone(),
+            self.get_current_weights().into_tuple(),
+        );
+        Ok(token_amount_out)
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedOutput)]
+    fn swap_tokens_fixed_output(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in_max: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedOutputResultType<Self::BigUint>> {
+        require!(self.can_swap(), "Swap is not enabled");
+        require!(amount_in_max > 0, "Invalid amount_in");
+        require!(token_in != token_out, "Invalid swap with same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+        require!(amount_out != 0, "Desired amount out cannot be zero");
+
+        let amount_in_optimal = self.compute_amount_in(&token_in, &token_out, &amount_out)?;
+        require!(
+            amount_in_optimal <= amount_in_max,
+            "Computed amount in greater than maximum amount in"
+        );
+        let residuum = &amount_in_max - &amount_in_optimal;
+
+        self.pool_reserve(&token_in)
+            .update(|reserve| *reserve += &amount_in_optimal);
+        self.pool_reserve(&token_out)
+            .update(|reserve| *reserve -= &amount_out);
+
+        let caller = self.blockchain().get_caller();
+        self.send_tokens_with_func(&token_out, &amount_out, &caller, &opt_accept_funds_func)?;
+        self.send_tokens_with_func(&token_in, &residuum, &caller, &opt_accept_funds_func)?;
+
+        let token_amount_in = FftTokenAmountPair {
+            token_id: token_in.clone(),
+            amount: amount_in_optimal,
+        };
+        let token_amount_out = FftTokenAmountPair {
+            token_id: token_out,
+            amount: amount_out,
+        };
+        let residuum_token_amount = FftTokenAmountPair {
+            token_id: token_in,
+            amount: residuum,
+        };
+        self.emit_swap_event(
+            caller,
+            token_amount_in,
+            token_amount_out.clone(),
+            self.get_current_weights().into_tuple(),
+        );
+        Ok((token_amount_out, residuum_token_amount).into())
+    }
+
+    fn compute_amount_out(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_in: &Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        let reserve_in = self.pool_reserve(token_in).get();
+        let reserve_out = self.pool_reserve(token_out).get();
+        require!(
+            reserve_in > 0 && reserve_out > 0,
+            "Zero reserves for swap tokens"
+        );
+        require!(
+            amount_in <= &self.bmul(&reserve_in, &MAX_IN_RATIO.into()),
+            "Amount in exceeds maximum ratio"
+        );
+
+        let amount_out = self.calc_out_given_in(
+            &reserve_in,
+            &self.get_current_normalized_weight(token_in),
+            &reserve_out,
+            &self.get_current_normalized_weight(token_out),
+            amount_in,
+            &self.fee_percent_to_bone(self.total_fee_percent().get()),
+        )?;
+        require!(reserve_out > amount_out, "Insufficient amount out reserve");
+        Ok(amount_out)
+    }
+
+    fn compute_amount_in(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_out: &Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        let reserve_in = self.pool_reserve(token_in).get();
+        let reserve_out = self.pool_reserve(token_out).get();
+        require!(
+            reserve_in > 0 && reserve_out > 0,
+            "Zero reserves for swap tokens"
+        );
+        require!(
+            amount_out <= &self.bmul(&reserve_out, &MAX_OUT_RATIO.into()),
+            "Amount out exceeds maximum ratio"
+        );
+
+        let amount_in = self.calc_in_given_out(
+            &reserve_in,
+            &self.get_current_normalized_weight(token_in),
+            &reserve_out,
+            &self.get_current_normalized_weight(token_out),
+            amount_out,
+            &self.fee_percent_to_bone(self.total_fee_percent().get()),
+        )?;
+        Ok(amount_in + 1u64.into())
+    }
+
+    fn get_current_first_token_weight(&self) -> u64 {
+        let start_weight = self.start_weight().get();
+        let end_weight = self.end_weight().get();
+        let start_block = self.start_block().get();
+        let end_block = self.end_block().get();
+        let block = self.blockchain().get_block_nonce();
+
+        if block <= start_block {
+            return start_weight;
+        }
+        if block >= end_block {
+            return end_weight;
+        }
+
+        let elapsed = block - start_block;
+        let duration = end_block - start_block;
+        if end_weight >= start_weight {
+            start_weight + (end_weight - start_weight) * elapsed / duration
+        } else {
+            start_weight - (start_weight - end_weight) * elapsed / duration
+        }
+    }
+
+    fn get_current_normalized_weight(&self, token_id: &TokenIdentifier) -> Self::BigUint {
+        let first_token_weight = self.get_current_first_token_weight();
+        let weight = if token_id == &self.first_token_id().get() {
+            first_token_weight
+        } else {
+            TOTAL_WEIGHT - first_token_weight
+        };
+        self.normalized_weight(weight, TOTAL_WEIGHT)
+    }
+
+    fn require_valid_weight(&self, first_token_weight: u64) -> SCResult<()> {
+        require!(
+            (MIN_TOKEN_WEIGHT..=TOTAL_WEIGHT - MIN_TOKEN_WEIGHT).contains(&first_token_weight),
+            "Bad weight"
+        );
+        Ok(())
+    }
+
+    fn require_pool_token(&self, token_id: &TokenIdentifier) -> SCResult<()> {
+        require!(
+            token_id == &self.first_token_id().get() || token_id == &self.second_token_id().get(),
+            "Not a pool token"
+        );
+        Ok(())
+    }
+
+    fn send_tokens(
+        &self,
+        token: &TokenIdentifier,
+        amount: &Self::BigUint,
+        destination: &Address,
+    ) -> SCResult<()> {
+        self.send_tokens_with_func(token, amount, destination, &OptionalArg::None)
+    }
+
+    fn send_tokens_with_func(
+        &self,
+        token: &TokenIdentifier,
+        amount: &Self::BigUint,
+        destination: &Address,
+        opt_accept_funds_func: &OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        if amount > &0 {
+            self.send_fft_tokens(token, amount, destination, opt_accept_funds_func)?;
+        }
+        Ok(())
+    }
+
+    #[view(getCurrentWeights)]
+    fn get_current_weights(&self) -> MultiResult2<u64, u64> {
+        let first_token_weight = self.get_current_first_token_weight();
+        (first_token_weight, TOTAL_WEIGHT - first_token_weight).into()
+    }
+
+    #[view(getSpotPrice)]
+    fn get_spot_price(&self, token_in: TokenIdentifier) -> SCResult<Self::BigUint> {
+        self.require_pool_token(&token_in)?;
+        let token_out = if token_in == self.first_token_id().get() {
+            self.second_token_id().get()
+        } else {
+            self.first_token_id().get()
+        };
+
+        let reserve_in = self.pool_reserve(&token_in).get();
+        let reserve_out = self.pool_reserve(&token_out).get();
+        require!(
+            reserve_in > 0 && reserve_out > 0,
+            "Zero reserves for swap tokens"
+        );
+        Ok(self.calc_spot_price(
+            &reserve_in,
+            &self.get_current_normalized_weight(&token_in),
+            &reserve_out,
+            &self.get_current_normalized_weight(&token_out),
+            &Self::BigUint::zero(),
+        ))
+    }
+
+    #[view(getAmountOut)]
+    fn get_amount_out_view(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        require!(amount_in > 0, "Zero input");
+        self.require_pool_token(&token_in)?;
+        let token_out = if token_in == self.first_token_id().get() {
+            self.second_token_id().get()
+        } else {
+            self.first_token_id().get()
+        };
+        self.compute_amount_out(&token_in, &token_out, &amount_in)
+    }
+
+    #[view(getReserves)]
+    fn get_reserves(&self) -> MultiResult2<Self::BigUint, Self::BigUint> {
+        let first_token_reserve = self.pool_reserve(&self.first_token_id().get()).get();
+        let second_token_reserve = self.pool_reserve(&self.second_token_id().get()).get();
+        (first_token_reserve, second_token_reserve).into()
+    }
+
+    #[inline]
+    fn can_swap(&self) -> bool {
+        let block = self.blockchain().get_block_nonce();
+        self.state().get() == State::Active
+            && block >= self.start_block().get()
+            && block <= self.end_block().get()
+    }
+}
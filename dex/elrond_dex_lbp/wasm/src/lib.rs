@@ -0,0 +1,4 @@
+#![no_std]
+
+pub use elrond_dex_lbp::*;
+pub use elrond_wasm_output::*;
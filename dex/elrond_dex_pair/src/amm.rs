@@ -2,9 +2,12 @@ elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
 use super::config;
+use super::events;
 
 #[elrond_wasm::module]
-pub trait AmmModule: config::ConfigModule + token_send::TokenSendModule {
+pub trait AmmModule:
+    config::ConfigModule + token_send::TokenSendModule + events::EventsModule
+{
     fn calculate_k_constant(
         &self,
         first_token_amount: &Self::BigUint,
@@ -1,55 +1,96 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
-#[derive(TopEncode, TopDecode, PartialEq, TypeAbi)]
+use super::events;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi, Clone, Copy)]
 pub enum State {
     Inactive,
     Active,
     ActiveNoSwaps,
+    Sunset,
+    ExitOnly,
 }
 
 #[elrond_wasm::module]
-pub trait ConfigModule: token_send::TokenSendModule {
+pub trait ConfigModule: token_send::TokenSendModule + events::EventsModule {
     #[endpoint]
     fn set_transfer_exec_gas_limit(&self, gas_limit: u64) -> SCResult<()> {
         self.require_permissions()?;
+        let old_gas_limit = self.transfer_exec_gas_limit().get();
         self.transfer_exec_gas_limit().set(&gas_limit);
+        self.emit_gas_limit_change_event(b"transfer_exec_gas_limit", old_gas_limit, gas_limit);
         Ok(())
     }
 
     #[endpoint]
     fn set_extern_swap_gas_limit(&self, gas_limit: u64) -> SCResult<()> {
         self.require_permissions()?;
+        let old_gas_limit = self.extern_swap_gas_limit().get();
         self.extern_swap_gas_limit().set(&gas_limit);
+        self.emit_gas_limit_change_event(b"extern_swap_gas_limit", old_gas_limit, gas_limit);
         Ok(())
     }
 
+    // Only the router may configure the pair. It checks the roles of its own callers and
+    // queues the sensitive changes behind its timelock.
     fn require_permissions(&self) -> SCResult<()> {
-        let caller = self.blockchain().get_caller();
-        let owner = self.router_owner_address().get();
-        let router = self.router_address().get();
-        require!(caller == owner || caller == router, "Permission denied");
+        require!(
+            self.blockchain().get_caller() == self.router_address().get(),
+            "Permission denied"
+        );
         Ok(())
     }
 
     #[endpoint]
     fn pause(&self) -> SCResult<()> {
         self.require_permissions()?;
-        self.state().set(&State::Inactive);
+        self.require_not_sunset()?;
+        self.set_state(State::Inactive);
         Ok(())
     }
 
     #[endpoint]
     fn resume(&self) -> SCResult<()> {
         self.require_permissions()?;
-        self.state().set(&State::Active);
+        self.require_not_sunset()?;
+        self.set_state(State::Active);
         Ok(())
     }
 
     #[endpoint(setStateActiveNoSwaps)]
     fn set_state_active_no_swaps(&self) -> SCResult<()> {
         self.require_permissions()?;
-        self.state().set(&State::ActiveNoSwaps);
+        self.require_not_sunset()?;
+        self.set_state(State::ActiveNoSwaps);
+        Ok(())
+    }
+
+    // Used for delisted pairs: swaps and adding liquidity are disabled, LPs can still exit.
+    #[endpoint(setStateExitOnly)]
+    fn set_state_exit_only(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.require_not_sunset()?;
+        self.set_state(State::ExitOnly);
+        Ok(())
+    }
+
+    #[endpoint(startSunset)]
+    fn start_sunset(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.require_not_sunset()?;
+        self.set_state(State::Sunset);
+        Ok(())
+    }
+
+    fn set_state(&self, new_state: State) {
+        let old_state = self.state().get();
+        self.state().set(&new_state);
+        self.emit_state_change_event(old_state, new_state);
+    }
+
+    fn require_not_sunset(&self) -> SCResult<()> {
+        require!(self.state().get() != State::Sunset, "Pair is in sunset");
         Ok(())
     }
 
@@ -61,7 +102,17 @@ pub trait ConfigModule: token_send::TokenSendModule {
     #[endpoint(setFeePercents)]
     fn set_fee_percent(&self, total_fee_percent: u64, special_fee_percent: u64) -> SCResult<()> {
         self.require_permissions()?;
-        self.try_set_fee_percents(total_fee_percent, special_fee_percent)
+        let old_total_fee_percent = self.total_fee_percent().get();
+        let old_special_fee_percent = self.special_fee_percent().get();
+        self.try_set_fee_percents(total_fee_percent, special_fee_percent)?;
+
+        self.emit_fee_percents_change_event(
+            old_total_fee_percent,
+            old_special_fee_percent,
+            total_fee_percent,
+            special_fee_percent,
+        );
+        Ok(())
     }
 
     fn try_set_fee_percents(
@@ -98,6 +149,10 @@ pub trait ConfigModule: token_send::TokenSendModule {
     #[storage_mapper("state")]
     fn state(&self) -> SingleValueMapper<Self::Storage, State>;
 
+    #[view(isSunsetFinalized)]
+    #[storage_mapper("sunset_finalized")]
+    fn sunset_finalized(&self) -> SingleValueMapper<Self::Storage, bool>;
+
     #[view(getExternSwapGasLimit)]
     #[storage_mapper("extern_swap_gas_limit")]
     fn extern_swap_gas_limit(&self) -> SingleValueMapper<Self::Storage, u64>;
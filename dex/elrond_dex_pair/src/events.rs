@@ -1,6 +1,7 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+use super::config::State;
 use common_structs::FftTokenAmountPair;
 
 #[derive(TopEncode)]
@@ -51,6 +52,96 @@ pub struct RemoveLiquidityEvent<BigUint: BigUintApi> {
     timestamp: u64,
 }
 
+#[derive(TopEncode)]
+pub struct MigrateLiquidityEvent<BigUint: BigUintApi> {
+    caller: Address,
+    destination: Address,
+    lp_token_amount: FftTokenAmountPair<BigUint>,
+    first_token_amount: FftTokenAmountPair<BigUint>,
+    second_token_amount: FftTokenAmountPair<BigUint>,
+    new_lp_token_amount: FftTokenAmountPair<BigUint>,
+    pair_reserves: Vec<FftTokenAmountPair<BigUint>>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct StateChangeEvent {
+    caller: Address,
+    old_state: State,
+    new_state: State,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct FeePercentsChangeEvent {
+    caller: Address,
+    old_total_fee_percent: u64,
+    old_special_fee_percent: u64,
+    new_total_fee_percent: u64,
+    new_special_fee_percent: u64,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct GasLimitChangeEvent {
+    caller: Address,
+    gas_limit_name: BoxedBytes,
+    old_gas_limit: u64,
+    new_gas_limit: u64,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct LpTokenChangeEvent {
+    caller: Address,
+    old_lp_token_id: TokenIdentifier,
+    new_lp_token_id: TokenIdentifier,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct WhitelistChangeEvent {
+    caller: Address,
+    address: Address,
+    whitelisted: bool,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct TrustedSwapPairChangeEvent {
+    caller: Address,
+    first_token_id: TokenIdentifier,
+    second_token_id: TokenIdentifier,
+    swap_pair_address: Address,
+    trusted: bool,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct FeeDestinationChangeEvent {
+    caller: Address,
+    fee_to_address: Address,
+    fee_token: TokenIdentifier,
+    enabled: bool,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[elrond_wasm::module]
 pub trait EventsModule {
     fn emit_swap_event(
@@ -160,6 +251,193 @@ pub trait EventsModule {
         )
     }
 
+    fn emit_migrate_liquidity_event(
+        &self,
+        caller: Address,
+        destination: Address,
+        lp_token_amount: FftTokenAmountPair<Self::BigUint>,
+        first_token_amount: FftTokenAmountPair<Self::BigUint>,
+        second_token_amount: FftTokenAmountPair<Self::BigUint>,
+        new_lp_token_amount: FftTokenAmountPair<Self::BigUint>,
+        pair_reserves: Vec<FftTokenAmountPair<Self::BigUint>>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.migrate_liquidity_event(
+            destination.clone(),
+            caller.clone(),
+            epoch,
+            MigrateLiquidityEvent {
+                caller,
+                destination,
+                lp_token_amount,
+                first_token_amount,
+                second_token_amount,
+                new_lp_token_amount,
+                pair_reserves,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_state_change_event(&self, old_state: State, new_state: State) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.state_change_event(
+            caller.clone(),
+            epoch,
+            StateChangeEvent {
+                caller,
+                old_state,
+                new_state,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_fee_percents_change_event(
+        &self,
+        old_total_fee_percent: u64,
+        old_special_fee_percent: u64,
+        new_total_fee_percent: u64,
+        new_special_fee_percent: u64,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.fee_percents_change_event(
+            caller.clone(),
+            epoch,
+            FeePercentsChangeEvent {
+                caller,
+                old_total_fee_percent,
+                old_special_fee_percent,
+                new_total_fee_percent,
+                new_special_fee_percent,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_gas_limit_change_event(
+        &self,
+        gas_limit_name: &[u8],
+        old_gas_limit: u64,
+        new_gas_limit: u64,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        let gas_limit_name = BoxedBytes::from(gas_limit_name);
+        self.gas_limit_change_event(
+            gas_limit_name.clone(),
+            caller.clone(),
+            epoch,
+            GasLimitChangeEvent {
+                caller,
+                gas_limit_name,
+                old_gas_limit,
+                new_gas_limit,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_lp_token_change_event(
+        &self,
+        old_lp_token_id: TokenIdentifier,
+        new_lp_token_id: TokenIdentifier,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.lp_token_change_event(
+            new_lp_token_id.clone(),
+            caller.clone(),
+            epoch,
+            LpTokenChangeEvent {
+                caller,
+                old_lp_token_id,
+                new_lp_token_id,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_whitelist_change_event(&self, address: Address, whitelisted: bool) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.whitelist_change_event(
+            address.clone(),
+            caller.clone(),
+            epoch,
+            WhitelistChangeEvent {
+                caller,
+                address,
+                whitelisted,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_trusted_swap_pair_change_event(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        swap_pair_address: Address,
+        trusted: bool,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.trusted_swap_pair_change_event(
+            swap_pair_address.clone(),
+            caller.clone(),
+            epoch,
+            TrustedSwapPairChangeEvent {
+                caller,
+                first_token_id,
+                second_token_id,
+                swap_pair_address,
+                trusted,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_fee_destination_change_event(
+        &self,
+        fee_to_address: Address,
+        fee_token: TokenIdentifier,
+        enabled: bool,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.fee_destination_change_event(
+            fee_to_address.clone(),
+            caller.clone(),
+            epoch,
+            FeeDestinationChangeEvent {
+                caller,
+                fee_to_address,
+                fee_token,
+                enabled,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
     #[event("swap")]
     fn swap_event(
         &self,
@@ -198,4 +476,74 @@ pub trait EventsModule {
         #[indexed] epoch: u64,
         remove_liquidity_event: RemoveLiquidityEvent<Self::BigUint>,
     );
+
+    #[event("migrate_liquidity")]
+    fn migrate_liquidity_event(
+        &self,
+        #[indexed] destination: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        migrate_liquidity_event: MigrateLiquidityEvent<Self::BigUint>,
+    );
+
+    #[event("state_change")]
+    fn state_change_event(
+        &self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        state_change_event: StateChangeEvent,
+    );
+
+    #[event("fee_percents_change")]
+    fn fee_percents_change_event(
+        &self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        fee_percents_change_event: FeePercentsChangeEvent,
+    );
+
+    #[event("gas_limit_change")]
+    fn gas_limit_change_event(
+        &self,
+        #[indexed] gas_limit_name: BoxedBytes,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        gas_limit_change_event: GasLimitChangeEvent,
+    );
+
+    #[event("lp_token_change")]
+    fn lp_token_change_event(
+        &self,
+        #[indexed] lp_token_id: TokenIdentifier,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        lp_token_change_event: LpTokenChangeEvent,
+    );
+
+    #[event("whitelist_change")]
+    fn whitelist_change_event(
+        &self,
+        #[indexed] address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        whitelist_change_event: WhitelistChangeEvent,
+    );
+
+    #[event("trusted_swap_pair_change")]
+    fn trusted_swap_pair_change_event(
+        &self,
+        #[indexed] swap_pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        trusted_swap_pair_change_event: TrustedSwapPairChangeEvent,
+    );
+
+    #[event("fee_destination_change")]
+    fn fee_destination_change_event(
+        &self,
+        #[indexed] fee_to_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        fee_destination_change_event: FeeDestinationChangeEvent,
+    );
 }
@@ -3,11 +3,19 @@ elrond_wasm::derive_imports!();
 
 use super::amm;
 use super::config;
+use super::events;
 use super::liquidity_pool;
 use common_structs::TokenPair;
 use core::iter::FromIterator;
 
 const SWAP_NO_FEE_AND_FORWARD_FUNC_NAME: &[u8] = b"swapNoFeeAndForward";
+pub const REFERRAL_FEE_PERCENT_BASE: u64 = 100_000;
+
+pub struct ReferralFee<BigUint: BigUintApi> {
+    pub referrer: Address,
+    pub fee_percent: u64,
+    pub fee_cap: BigUint,
+}
 
 mod farm_proxy {
     elrond_wasm::imports!();
@@ -24,6 +32,22 @@ mod farm_proxy {
     }
 }
 
+mod router_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait Router {
+        #[payable("*")]
+        #[endpoint(acceptReferralFee)]
+        fn accept_referral_fee(
+            &self,
+            #[payment_token] token_id: TokenIdentifier,
+            #[payment_amount] amount: Self::BigUint,
+            referrer: Address,
+        );
+    }
+}
+
 #[elrond_wasm::module]
 pub trait FeeModule:
     config::ConfigModule
@@ -31,16 +55,26 @@ pub trait FeeModule:
     + amm::AmmModule
     + token_supply::TokenSupplyModule
     + token_send::TokenSendModule
+    + events::EventsModule
 {
     #[proxy]
     fn farm_proxy(&self, to: Address) -> farm_proxy::Proxy<Self::SendApi>;
 
+    #[proxy]
+    fn router_proxy(&self, to: Address) -> router_proxy::Proxy<Self::SendApi>;
+
     #[storage_mapper("fee_destination")]
     fn destination_map(&self) -> SafeMapMapper<Self::Storage, Address, TokenIdentifier>;
 
     #[storage_mapper("trusted_swap_pair")]
     fn trusted_swap_pair(&self) -> SafeMapMapper<Self::Storage, TokenPair, Address>;
 
+    #[storage_mapper("routed_fee_destinations")]
+    fn routed_fee_destinations(&self) -> SafeSetMapper<Self::Storage, Address>;
+
+    #[storage_mapper("routed_trusted_swap_pairs")]
+    fn routed_trusted_swap_pairs(&self) -> SafeSetMapper<Self::Storage, TokenPair>;
+
     #[view(getWhitelistedAddresses)]
     #[storage_mapper("whitelist")]
     fn whitelist(&self) -> SafeSetMapper<Self::Storage, Address>;
@@ -53,8 +87,9 @@ pub trait FeeModule:
     #[endpoint(whitelist)]
     fn whitelist_endpoint(&self, address: Address) -> SCResult<()> {
         self.require_permissions()?;
-        let is_new = self.whitelist().insert(address);
+        let is_new = self.whitelist().insert(address.clone());
         require!(is_new, "Address already whitelisted");
+        self.emit_whitelist_change_event(address, true);
         Ok(())
     }
 
@@ -63,6 +98,7 @@ pub trait FeeModule:
         self.require_permissions()?;
         let is_removed = self.whitelist().remove(&address);
         require!(is_removed, "Addresss not whitelisted");
+        self.emit_whitelist_change_event(address, false);
         Ok(())
     }
 
@@ -75,12 +111,14 @@ pub trait FeeModule:
     ) -> SCResult<()> {
         self.require_permissions()?;
         require!(first_token != second_token, "Tokens should differ");
-        let token_pair = TokenPair {
-            first_token,
-            second_token,
-        };
-        let is_new = self.trusted_swap_pair().insert(token_pair, pair_address) == None;
-        require!(is_new, "Pair already trusted");
+        require!(
+            self.get_extern_swap_pair_address(&first_token, &second_token) == Address::zero(),
+            "Pair already trusted"
+        );
+        let token_pair = self.get_normalized_token_pair(&first_token, &second_token);
+        self.trusted_swap_pair()
+            .insert(token_pair, pair_address.clone());
+        self.emit_trusted_swap_pair_change_event(first_token, second_token, pair_address, true);
         Ok(())
     }
 
@@ -91,19 +129,85 @@ pub trait FeeModule:
         second_token: TokenIdentifier,
     ) -> SCResult<()> {
         self.require_permissions()?;
-        let token_pair = TokenPair {
-            first_token: first_token.clone(),
-            second_token: second_token.clone(),
-        };
+        let token_pair = self.get_normalized_token_pair(&first_token, &second_token);
 
-        let mut is_removed = self.trusted_swap_pair().remove(&token_pair) != None;
-        if !is_removed {
+        let mut removed_address = self.trusted_swap_pair().remove(&token_pair);
+        self.routed_trusted_swap_pairs().remove(&token_pair);
+        if removed_address.is_none() {
             let token_pair_reversed = TokenPair {
-                first_token: second_token,
-                second_token: first_token,
+                first_token: token_pair.second_token,
+                second_token: token_pair.first_token,
             };
-            is_removed = self.trusted_swap_pair().remove(&token_pair_reversed) != None;
-            require!(is_removed, "Pair does not exist in trusted pair map");
+            removed_address = self.trusted_swap_pair().remove(&token_pair_reversed);
+            self.routed_trusted_swap_pairs()
+                .remove(&token_pair_reversed);
+        }
+        require!(
+            removed_address.is_some(),
+            "Pair does not exist in trusted pair map"
+        );
+        self.emit_trusted_swap_pair_change_event(
+            first_token,
+            second_token,
+            removed_address.unwrap(),
+            false,
+        );
+        Ok(())
+    }
+
+    // Replaces the fee destinations and trusted swap pairs set by a previous call. Used by the
+    // router to push the fee routing it computed from its pair map. Entries configured with
+    // setFeeOn or addTrustedSwapPair are kept, and win over a routed entry with the same key.
+    #[endpoint(setFeeRouting)]
+    fn set_fee_routing(
+        &self,
+        fee_to_address: Address,
+        fee_token: TokenIdentifier,
+        #[var_args] trusted_swap_pairs: VarArgs<
+            MultiArg3<TokenIdentifier, TokenIdentifier, Address>,
+        >,
+    ) -> SCResult<()> {
+        self.require_permissions()?;
+        require!(fee_token.is_valid_esdt_identifier(), "Invalid fee token");
+
+        let destinations: Vec<Address> = self.routed_fee_destinations().iter().collect();
+        for destination in destinations.into_iter() {
+            self.routed_fee_destinations().remove(&destination);
+            if let Some(old_fee_token) = self.destination_map().remove(&destination) {
+                self.emit_fee_destination_change_event(destination, old_fee_token, false);
+            }
+        }
+        if self.destination_map().get(&fee_to_address).is_none() {
+            self.destination_map()
+                .insert(fee_to_address.clone(), fee_token.clone());
+            self.routed_fee_destinations()
+                .insert(fee_to_address.clone());
+            self.emit_fee_destination_change_event(fee_to_address, fee_token, true);
+        }
+
+        let token_pairs: Vec<TokenPair> = self.routed_trusted_swap_pairs().iter().collect();
+        for token_pair in token_pairs.into_iter() {
+            self.routed_trusted_swap_pairs().remove(&token_pair);
+            if let Some(old_pair_address) = self.trusted_swap_pair().remove(&token_pair) {
+                self.emit_trusted_swap_pair_change_event(
+                    token_pair.first_token,
+                    token_pair.second_token,
+                    old_pair_address,
+                    false,
+                );
+            }
+        }
+        for trusted_swap_pair in trusted_swap_pairs.into_vec() {
+            let (first_token, second_token, pair_address) = trusted_swap_pair.into_tuple();
+            require!(first_token != second_token, "Tokens should differ");
+            if self.get_extern_swap_pair_address(&first_token, &second_token) != Address::zero() {
+                continue;
+            }
+            let token_pair = self.get_normalized_token_pair(&first_token, &second_token);
+            self.trusted_swap_pair()
+                .insert(token_pair.clone(), pair_address.clone());
+            self.routed_trusted_swap_pairs().insert(token_pair);
+            self.emit_trusted_swap_pair_change_event(first_token, second_token, pair_address, true);
         }
         Ok(())
     }
@@ -115,6 +219,54 @@ pub trait FeeModule:
             .update(|reserve| *reserve += amount);
     }
 
+    // Referral rates and caps are owned by the router, which is the only one allowed to pass them.
+    fn build_referral_fee(
+        &self,
+        referrer: Address,
+        fee_percent: u64,
+        fee_cap: Self::BigUint,
+    ) -> SCResult<ReferralFee<Self::BigUint>> {
+        require!(
+            self.blockchain().get_caller() == self.router_address().get(),
+            "Only the router can set a referrer"
+        );
+        require!(
+            fee_percent <= REFERRAL_FEE_PERCENT_BASE,
+            "Bad referral fee percent"
+        );
+        Ok(ReferralFee {
+            referrer,
+            fee_percent,
+            fee_cap,
+        })
+    }
+
+    // Sends the referrer's share of the fee to the router and returns what is left of it.
+    fn send_referral_fee(
+        &self,
+        fee_token: &TokenIdentifier,
+        fee_amount: &Self::BigUint,
+        referral_fee: &ReferralFee<Self::BigUint>,
+    ) -> Self::BigUint {
+        let mut referral_amount = &(fee_amount * &Self::BigUint::from(referral_fee.fee_percent))
+            / &Self::BigUint::from(REFERRAL_FEE_PERCENT_BASE);
+        if referral_fee.fee_cap > 0 && referral_amount > referral_fee.fee_cap {
+            referral_amount = referral_fee.fee_cap.clone();
+        }
+        if referral_amount == 0 {
+            return fee_amount.clone();
+        }
+
+        self.router_proxy(self.router_address().get())
+            .accept_referral_fee(
+                fee_token.clone(),
+                referral_amount.clone(),
+                referral_fee.referrer.clone(),
+            )
+            .execute_on_dest_context();
+        fee_amount - &referral_amount
+    }
+
     fn send_fee(&self, fee_token: &TokenIdentifier, fee_amount: &Self::BigUint) {
         if fee_amount == &0 {
             return;
@@ -333,29 +485,35 @@ pub trait FeeModule:
         first_token: &TokenIdentifier,
         second_token: &TokenIdentifier,
     ) -> Address {
-        let token_pair = TokenPair {
-            first_token: first_token.clone(),
-            second_token: second_token.clone(),
+        let token_pair = self.get_normalized_token_pair(first_token, second_token);
+        if let Some(pair_address) = self.trusted_swap_pair().get(&token_pair) {
+            return pair_address;
+        }
+
+        // Pairs trusted before keys were normalized may still be stored in the other order.
+        let token_pair_reversed = TokenPair {
+            first_token: token_pair.second_token,
+            second_token: token_pair.first_token,
         };
-        let is_cached = self.trusted_swap_pair().keys().any(|key| key == token_pair);
+        self.trusted_swap_pair()
+            .get(&token_pair_reversed)
+            .unwrap_or_else(Address::zero)
+    }
 
-        if is_cached {
-            self.trusted_swap_pair().get(&token_pair).unwrap()
+    fn get_normalized_token_pair(
+        &self,
+        first_token: &TokenIdentifier,
+        second_token: &TokenIdentifier,
+    ) -> TokenPair {
+        if first_token.as_esdt_identifier() <= second_token.as_esdt_identifier() {
+            TokenPair {
+                first_token: first_token.clone(),
+                second_token: second_token.clone(),
+            }
         } else {
-            let token_pair_reversed = TokenPair {
+            TokenPair {
                 first_token: second_token.clone(),
                 second_token: first_token.clone(),
-            };
-
-            let is_cached_reversed = self
-                .trusted_swap_pair()
-                .keys()
-                .any(|key| key == token_pair_reversed);
-
-            if is_cached_reversed {
-                self.trusted_swap_pair().get(&token_pair_reversed).unwrap()
-            } else {
-                Address::zero()
             }
         }
     }
@@ -375,13 +533,16 @@ pub trait FeeModule:
 
         if enabled {
             require!(!is_dest, "Is already a fee destination");
-            self.destination_map().insert(fee_to_address, fee_token);
+            self.destination_map()
+                .insert(fee_to_address.clone(), fee_token.clone());
         } else {
             require!(is_dest, "Is not a fee destination");
             let dest_fee_token = self.destination_map().get(&fee_to_address).unwrap();
             require!(fee_token == dest_fee_token, "Destination fee token differs");
             self.destination_map().remove(&fee_to_address);
+            self.routed_fee_destinations().remove(&fee_to_address);
         }
+        self.emit_fee_destination_change_event(fee_to_address, fee_token, enabled);
         Ok(())
     }
 
@@ -400,6 +561,25 @@ pub trait FeeModule:
         )
     }
 
+    #[view(getFeeDestinationToken)]
+    fn get_fee_destination_token(&self, fee_to_address: Address) -> Option<TokenIdentifier> {
+        self.destination_map().get(&fee_to_address)
+    }
+
+    #[view(getTrustedSwapPair)]
+    fn get_trusted_swap_pair(
+        &self,
+        first_token: TokenIdentifier,
+        second_token: TokenIdentifier,
+    ) -> Address {
+        self.get_extern_swap_pair_address(&first_token, &second_token)
+    }
+
+    #[view(isWhitelisted)]
+    fn is_whitelisted(&self, address: Address) -> bool {
+        self.whitelist().contains(&address)
+    }
+
     #[view(getTrustedSwapPairs)]
     fn get_trusted_swap_pairs(&self) -> MultiResultVec<(TokenPair, Address)> {
         MultiResultVec::from_iter(
@@ -1,20 +1,25 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
 const DEFAULT_TRANSFER_EXEC_GAS_LIMIT: u64 = 35000000;
 const DEFAULT_EXTERN_SWAP_GAS_LIMIT: u64 = 50000000;
+const ACCEPT_PAY_FUNC_NAME: &[u8] = b"acceptPay";
 
 mod amm;
 pub mod config;
 mod events;
 pub mod fee;
 mod liquidity_pool;
-mod oracle;
+pub mod migration;
+pub mod oracle;
 
 use common_structs::FftTokenAmountPair;
 use config::State;
+use fee::ReferralFee;
+use migration::destination_pair_proxy::ProxyTrait as _;
 
 type AddLiquidityResultType<BigUint> = MultiResult3<
     FftTokenAmountPair<BigUint>,
@@ -25,6 +30,12 @@ type AddLiquidityResultType<BigUint> = MultiResult3<
 type RemoveLiquidityResultType<BigUint> =
     MultiResult2<FftTokenAmountPair<BigUint>, FftTokenAmountPair<BigUint>>;
 
+type MigrateLiquidityResultType<BigUint> = MultiResult3<
+    FftTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+>;
+
 type SwapTokensFixedInputResultType<BigUint> = FftTokenAmountPair<BigUint>;
 
 type SwapTokensFixedOutputResultType<BigUint> =
@@ -36,6 +47,7 @@ pub trait Pair:
     + fee::FeeModule
     + liquidity_pool::LiquidityPoolModule
     + config::ConfigModule
+    + migration::MigrationModule
     + oracle::OracleModule
     + token_supply::TokenSupplyModule
     + token_send::TokenSendModule
@@ -355,6 +367,159 @@ pub trait Pair:
         Ok((first_token_amount, second_token_amount).into())
     }
 
+    #[payable("*")]
+    #[endpoint(migrateLiquidity)]
+    fn migrate_liquidity(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] liquidity: Self::BigUint,
+        first_token_amount_min: Self::BigUint,
+        second_token_amount_min: Self::BigUint,
+        new_lp_token_amount_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<MigrateLiquidityResultType<Self::BigUint>> {
+        require!(self.is_migration_open(), "Migration is not open");
+        require!(
+            !self.lp_token_identifier().is_empty(),
+            "LP token not issued"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let lp_token_id = self.lp_token_identifier().get();
+        require!(token_id == lp_token_id, "Wrong liquidity token");
+        self.broadcast_pair_reserves();
+
+        let old_k = self.calculate_k_for_reserves();
+        let (first_token_amount, second_token_amount) = self.pool_remove_liquidity(
+            liquidity.clone(),
+            first_token_amount_min,
+            second_token_amount_min,
+        )?;
+
+        // Once liquidity has been removed, the new K should always be lesser than the old K.
+        let new_k = self.calculate_k_for_reserves();
+        self.validate_k_invariant_strict(&new_k, &old_k)?;
+        self.burn_tokens(&token_id, &liquidity);
+
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let destination_address = self.migration_destination().get();
+        let same_order = self.get_destination_token_order(&destination_address)?;
+        self.destination_pair_proxy(destination_address.clone())
+            .accept_esdt_payment(first_token_id.clone(), first_token_amount.clone())
+            .execute_on_dest_context();
+        self.destination_pair_proxy(destination_address.clone())
+            .accept_esdt_payment(second_token_id.clone(), second_token_amount.clone())
+            .execute_on_dest_context();
+
+        // Amounts go in the destination's token order and come back mapped by token ID.
+        let (destination_first_amount, destination_second_amount) = if same_order {
+            (first_token_amount.clone(), second_token_amount.clone())
+        } else {
+            (second_token_amount.clone(), first_token_amount.clone())
+        };
+        let (new_lp_token_amount, destination_first_added, destination_second_added) = self
+            .destination_pair_proxy(destination_address.clone())
+            .add_liquidity(
+                destination_first_amount,
+                destination_second_amount,
+                Self::BigUint::zero(),
+                Self::BigUint::zero(),
+                OptionalArg::Some(BoxedBytes::from(ACCEPT_PAY_FUNC_NAME)),
+            )
+            .execute_on_dest_context_custom_range(|_, after| (after - 3, after))
+            .into_tuple();
+        let (first_token_added, second_token_added) = if same_order {
+            (destination_first_added, destination_second_added)
+        } else {
+            (destination_second_added, destination_first_added)
+        };
+        require!(
+            first_token_added.token_id == first_token_id
+                && second_token_added.token_id == second_token_id,
+            "Bad tokens received from destination"
+        );
+        require!(
+            new_lp_token_amount.amount >= new_lp_token_amount_min,
+            "Insufficient new LP tokens received"
+        );
+
+        // The destination sends back what it did not use, all of it belongs to the caller.
+        self.send_tokens(
+            &new_lp_token_amount.token_id,
+            &new_lp_token_amount.amount,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+        let first_token_unused = FftTokenAmountPair {
+            token_id: first_token_id.clone(),
+            amount: &first_token_amount - &first_token_added.amount,
+        };
+        let second_token_unused = FftTokenAmountPair {
+            token_id: second_token_id.clone(),
+            amount: &second_token_amount - &second_token_added.amount,
+        };
+        self.send_tokens(
+            &first_token_unused.token_id,
+            &first_token_unused.amount,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+        self.send_tokens(
+            &second_token_unused.token_id,
+            &second_token_unused.amount,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+
+        let lp_token_amount = FftTokenAmountPair {
+            token_id: lp_token_id,
+            amount: liquidity,
+        };
+        let first_token_reserve = FftTokenAmountPair {
+            token_id: first_token_id.clone(),
+            amount: self.pair_reserve(&first_token_id).get(),
+        };
+        let second_token_reserve = FftTokenAmountPair {
+            token_id: second_token_id.clone(),
+            amount: self.pair_reserve(&second_token_id).get(),
+        };
+        self.emit_migrate_liquidity_event(
+            caller,
+            destination_address,
+            lp_token_amount,
+            first_token_added,
+            second_token_added,
+            new_lp_token_amount.clone(),
+            [first_token_reserve, second_token_reserve].to_vec(),
+        );
+        Ok((new_lp_token_amount, first_token_unused, second_token_unused).into())
+    }
+
+    #[endpoint(finalizeSunset)]
+    fn finalize_sunset(&self, treasury_address: Address) -> SCResult<()> {
+        self.require_permissions()?;
+        require!(self.state().get() == State::Sunset, "Pair is not in sunset");
+        require!(!self.sunset_finalized().get(), "Sunset already finalized");
+
+        let (first_token_amount, second_token_amount) = self.pool_remove_locked_liquidity();
+        self.sunset_finalized().set(&true);
+
+        self.send_tokens(
+            &self.first_token_id().get(),
+            &first_token_amount,
+            &treasury_address,
+            &OptionalArg::None,
+        )?;
+        self.send_tokens(
+            &self.second_token_id().get(),
+            &second_token_amount,
+            &treasury_address,
+            &OptionalArg::None,
+        )?;
+        Ok(())
+    }
+
     #[payable("*")]
     #[endpoint(removeLiquidityAndBuyBackAndBurnToken)]
     fn remove_liquidity_and_burn_token(
@@ -465,6 +630,50 @@ pub trait Pair:
         token_out: TokenIdentifier,
         amount_out_min: Self::BigUint,
         #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedInputResultType<Self::BigUint>> {
+        self.perform_swap_tokens_fixed_input(
+            token_in,
+            amount_in,
+            token_out,
+            amount_out_min,
+            opt_accept_funds_func,
+            None,
+        )
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInputWithReferrer)]
+    fn swap_tokens_fixed_input_with_referrer(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        referrer: Address,
+        referral_fee_percent: u64,
+        referral_fee_cap: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedInputResultType<Self::BigUint>> {
+        let referral_fee =
+            self.build_referral_fee(referrer, referral_fee_percent, referral_fee_cap)?;
+        self.perform_swap_tokens_fixed_input(
+            token_in,
+            amount_in,
+            token_out,
+            amount_out_min,
+            opt_accept_funds_func,
+            Some(referral_fee),
+        )
+    }
+
+    fn perform_swap_tokens_fixed_input(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        opt_accept_funds_func: OptionalArg<BoxedBytes>,
+        opt_referral_fee: Option<ReferralFee<Self::BigUint>>,
     ) -> SCResult<SwapTokensFixedInputResultType<Self::BigUint>> {
         require!(self.can_swap(), "Swap is not enabled");
         require!(amount_in > 0, "Invalid amount_in");
@@ -529,7 +738,11 @@ pub trait Pair:
 
         //The transaction was made. We are left with $(fee) of $(token_in) as fee.
         if self.is_fee_enabled() {
-            self.send_fee(&token_in, &fee_amount);
+            let fee_amount_left = match opt_referral_fee {
+                Some(referral_fee) => self.send_referral_fee(&token_in, &fee_amount, &referral_fee),
+                None => fee_amount.clone(),
+            };
+            self.send_fee(&token_in, &fee_amount_left);
         }
         self.send_tokens(
             &token_out,
@@ -573,6 +786,50 @@ pub trait Pair:
         token_out: TokenIdentifier,
         amount_out: Self::BigUint,
         #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedOutputResultType<Self::BigUint>> {
+        self.perform_swap_tokens_fixed_output(
+            token_in,
+            amount_in_max,
+            token_out,
+            amount_out,
+            opt_accept_funds_func,
+            None,
+        )
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedOutputWithReferrer)]
+    fn swap_tokens_fixed_output_with_referrer(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in_max: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out: Self::BigUint,
+        referrer: Address,
+        referral_fee_percent: u64,
+        referral_fee_cap: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedOutputResultType<Self::BigUint>> {
+        let referral_fee =
+            self.build_referral_fee(referrer, referral_fee_percent, referral_fee_cap)?;
+        self.perform_swap_tokens_fixed_output(
+            token_in,
+            amount_in_max,
+            token_out,
+            amount_out,
+            opt_accept_funds_func,
+            Some(referral_fee),
+        )
+    }
+
+    fn perform_swap_tokens_fixed_output(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in_max: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out: Self::BigUint,
+        opt_accept_funds_func: OptionalArg<BoxedBytes>,
+        opt_referral_fee: Option<ReferralFee<Self::BigUint>>,
     ) -> SCResult<SwapTokensFixedOutputResultType<Self::BigUint>> {
         require!(self.can_swap(), "Swap is not enabled");
         require!(amount_in_max > 0, "Invalid amount_in");
@@ -634,7 +891,11 @@ pub trait Pair:
 
         //The transaction was made. We are left with $(fee) of $(token_in) as fee.
         if self.is_fee_enabled() {
-            self.send_fee(&token_in, &fee_amount);
+            let fee_amount_left = match opt_referral_fee {
+                Some(referral_fee) => self.send_referral_fee(&token_in, &fee_amount, &referral_fee),
+                None => fee_amount.clone(),
+            };
+            self.send_fee(&token_in, &fee_amount_left);
         }
 
         self.send_tokens(&token_out, &amount_out, &caller, &opt_accept_funds_func)?;
@@ -705,6 +966,7 @@ pub trait Pair:
         );
 
         self.lp_token_identifier().set(&token_identifier);
+        self.emit_lp_token_change_event(TokenIdentifier::egld(), token_identifier);
 
         Ok(())
     }
@@ -3,6 +3,7 @@ elrond_wasm::derive_imports!();
 
 use super::amm;
 use super::config;
+use super::events;
 use common_structs::{FftTokenAmountPair, Nonce};
 
 const MINIMUM_LIQUIDITY: u64 = 1_000;
@@ -13,6 +14,7 @@ pub trait LiquidityPoolModule:
     + config::ConfigModule
     + token_supply::TokenSupplyModule
     + token_send::TokenSendModule
+    + events::EventsModule
 {
     fn pool_add_liquidity(
         &self,
@@ -66,7 +68,10 @@ pub trait LiquidityPoolModule:
         let amount = (liquidity * &reserve) / total_supply.clone();
         require!(amount > 0, "Insufficient liquidity burned");
         require!(&amount >= amount_min, "Insufficient liquidity burned");
-        require!(reserve > amount, "Not enough reserve");
+        require!(
+            reserve > amount || (self.sunset_finalized().get() && reserve == amount),
+            "Not enough reserve"
+        );
 
         reserve -= &amount;
         self.pair_reserve(token).set(&reserve);
@@ -81,8 +86,14 @@ pub trait LiquidityPoolModule:
         second_token_amount_min: Self::BigUint,
     ) -> SCResult<(Self::BigUint, Self::BigUint)> {
         let total_supply = self.get_total_lp_token_supply();
+        // Once the sunset is finalized nothing is locked anymore, so the pool can be fully drained.
+        let locked_liquidity = if self.sunset_finalized().get() {
+            Self::BigUint::zero()
+        } else {
+            MINIMUM_LIQUIDITY.into()
+        };
         require!(
-            total_supply >= &liquidity + &MINIMUM_LIQUIDITY.into(),
+            total_supply >= &liquidity + &locked_liquidity,
             "Not enough LP token supply"
         );
 
@@ -102,6 +113,34 @@ pub trait LiquidityPoolModule:
         Ok((first_token_amount, second_token_amount))
     }
 
+    fn pool_remove_locked_liquidity(&self) -> (Self::BigUint, Self::BigUint) {
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let first_token_reserve = self.pair_reserve(&first_token_id).get();
+        let second_token_reserve = self.pair_reserve(&second_token_id).get();
+        let total_supply = self.get_total_lp_token_supply();
+        let minimum_liquidity = MINIMUM_LIQUIDITY.into();
+
+        // With no other LPs left, whatever remains in the reserves is dust and goes along.
+        let (first_token_amount, second_token_amount, locked_liquidity) =
+            if total_supply <= minimum_liquidity {
+                (first_token_reserve, second_token_reserve, total_supply)
+            } else {
+                (
+                    &(&minimum_liquidity * &first_token_reserve) / &total_supply,
+                    &(&minimum_liquidity * &second_token_reserve) / &total_supply,
+                    minimum_liquidity,
+                )
+            };
+
+        if locked_liquidity > 0 {
+            self.burn_tokens(&self.lp_token_identifier().get(), &locked_liquidity);
+        }
+        self.decrease_token_reserve(&first_token_id, &first_token_amount);
+        self.decrease_token_reserve(&second_token_id, &second_token_amount);
+        (first_token_amount, second_token_amount)
+    }
+
     fn calculate_optimal_amounts(
         &self,
         first_token_amount_desired: Self::BigUint,
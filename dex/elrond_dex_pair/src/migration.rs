@@ -0,0 +1,117 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use super::amm;
+use super::config;
+use super::events;
+use super::liquidity_pool;
+
+pub mod destination_pair_proxy {
+    elrond_wasm::imports!();
+
+    use common_structs::FftTokenAmountPair;
+
+    #[elrond_wasm::proxy]
+    pub trait DestinationPair {
+        #[payable("*")]
+        #[endpoint(acceptEsdtPayment)]
+        fn accept_esdt_payment(
+            &self,
+            #[payment_token] token: TokenIdentifier,
+            #[payment_amount] payment: Self::BigUint,
+        );
+
+        #[endpoint(addLiquidity)]
+        fn add_liquidity(
+            &self,
+            first_token_amount_desired: Self::BigUint,
+            second_token_amount_desired: Self::BigUint,
+            first_token_amount_min: Self::BigUint,
+            second_token_amount_min: Self::BigUint,
+            #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+        ) -> MultiResult3<
+            FftTokenAmountPair<Self::BigUint>,
+            FftTokenAmountPair<Self::BigUint>,
+            FftTokenAmountPair<Self::BigUint>,
+        >;
+
+        #[view(getFirstTokenId)]
+        fn get_first_token_id(&self) -> TokenIdentifier;
+
+        #[view(getSecondTokenId)]
+        fn get_second_token_id(&self) -> TokenIdentifier;
+    }
+}
+
+#[elrond_wasm::module]
+pub trait MigrationModule:
+    amm::AmmModule
+    + config::ConfigModule
+    + liquidity_pool::LiquidityPoolModule
+    + token_supply::TokenSupplyModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+{
+    #[proxy]
+    fn destination_pair_proxy(&self, to: Address) -> destination_pair_proxy::Proxy<Self::SendApi>;
+
+    #[endpoint(openMigration)]
+    fn open_migration(&self, destination_address: Address) -> SCResult<()> {
+        self.require_permissions()?;
+        require!(
+            destination_address != self.blockchain().get_sc_address(),
+            "Cannot migrate to self"
+        );
+        self.get_destination_token_order(&destination_address)?;
+
+        self.migration_destination().set(&destination_address);
+        Ok(())
+    }
+
+    #[endpoint(closeMigration)]
+    fn close_migration(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.migration_destination().clear();
+        Ok(())
+    }
+
+    // The destination must hold the same two tokens. Returns true if it also keeps them in the
+    // same order, false if they are reversed.
+    fn get_destination_token_order(&self, destination_address: &Address) -> SCResult<bool> {
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let destination_first_token_id = self
+            .destination_pair_proxy(destination_address.clone())
+            .get_first_token_id()
+            .execute_on_dest_context();
+        let destination_second_token_id = self
+            .destination_pair_proxy(destination_address.clone())
+            .get_second_token_id()
+            .execute_on_dest_context();
+
+        if destination_first_token_id == first_token_id
+            && destination_second_token_id == second_token_id
+        {
+            Ok(true)
+        } else if destination_first_token_id == second_token_id
+            && destination_second_token_id == first_token_id
+        {
+            Ok(false)
+        } else {
+            sc_error!("Destination tokens mismatch")
+        }
+    }
+
+    #[payable("*")]
+    #[endpoint(acceptPay)]
+    fn accept_pay(&self) {}
+
+    #[view(isMigrationOpen)]
+    fn is_migration_open(&self) -> bool {
+        !self.migration_destination().is_empty()
+    }
+
+    #[view(getMigrationDestination)]
+    #[storage_mapper("migration_destination")]
+    fn migration_destination(&self) -> SingleValueMapper<Self::Storage, Address>;
+}
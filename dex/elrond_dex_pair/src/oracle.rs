@@ -9,10 +9,10 @@ type Nonce = u64;
 
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
 pub struct PriceRecord<BigUint: BigUintApi> {
-    first_token_price: BigUint,
-    second_token_price: BigUint,
-    start_block: Nonce,
-    end_block: Nonce,
+    pub first_token_price: BigUint,
+    pub second_token_price: BigUint,
+    pub start_block: Nonce,
+    pub end_block: Nonce,
 }
 
 impl<BigUint: BigUintApi> Default for PriceRecord<BigUint> {
@@ -248,6 +248,59 @@ pub trait OracleModule {
         RECORD_BLOCKS_FREQUENCY
     }
 
+    // Averages the committed records over the last window_blocks blocks, each weighted by the
+    // number of its blocks inside the window. The record being built is left out, since it
+    // can still be moved by the current block. Returns None if no committed record is in it.
+    #[view(getTwapPriceRecord)]
+    fn get_twap_price_record(&self, window_blocks: Nonce) -> Option<PriceRecord<Self::BigUint>> {
+        let len = self.price_records().len();
+        if len == 0 || window_blocks == 0 {
+            return None;
+        }
+
+        let current_block = self.blockchain().get_block_nonce();
+        let window_start = current_block.saturating_sub(window_blocks);
+        let mut first_token_price_sum = Self::BigUint::zero();
+        let mut second_token_price_sum = Self::BigUint::zero();
+        let mut total_blocks = 0u64;
+        let mut start_block = current_block;
+        let mut end_block = 0;
+
+        // Records are committed in block order, so walking back from the head stops at the
+        // first one that ended before the window.
+        let mut index = self.price_records_head().get();
+        if index == 0 {
+            index = len;
+        }
+        for _ in 0..len {
+            let record = self.price_records().get(index);
+            if record.end_block <= window_start {
+                break;
+            }
+
+            let record_start = core::cmp::max(record.start_block, window_start);
+            let blocks = Self::BigUint::from(record.end_block - record_start);
+            first_token_price_sum += &(&record.first_token_price * &blocks);
+            second_token_price_sum += &(&record.second_token_price * &blocks);
+            total_blocks += record.end_block - record_start;
+            start_block = core::cmp::min(start_block, record_start);
+            end_block = core::cmp::max(end_block, record.end_block);
+
+            index = if index == 1 { len } else { index - 1 };
+        }
+
+        if total_blocks == 0 {
+            return None;
+        }
+        let total_blocks = Self::BigUint::from(total_blocks);
+        Some(PriceRecord {
+            first_token_price: &first_token_price_sum / &total_blocks,
+            second_token_price: &second_token_price_sum / &total_blocks,
+            start_block,
+            end_block,
+        })
+    }
+
     #[view(getCurrentPriceRecord)]
     #[storage_mapper("current_price_record")]
     fn current_price_record(&self) -> SingleValueMapper<Self::Storage, PriceRecord<Self::BigUint>>;
@@ -0,0 +1,202 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+use permissions::Role;
+
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::factory::PairTokens;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pair_registry::MAX_PAGE_SIZE;
+use super::pool_factory;
+use super::referral;
+use super::state;
+
+const DEFAULT_DELIST_GRACE_PERIOD_EPOCHS: u64 = 30;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct ArchivedPair {
+    pub pair_id: u64,
+    pub address: Address,
+    pub first_token_id: TokenIdentifier,
+    pub second_token_id: TokenIdentifier,
+    pub delist_epoch: u64,
+    pub archive_epoch: u64,
+}
+
+#[elrond_wasm::module]
+pub trait DelistingModule:
+    state::StateModule
+    + factory::FactoryModule
+    + pair_manager::PairManagerModule
+    + pair_registry::PairRegistryModule
+    + events::EventsModule
+    + pool_factory::PoolFactoryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + token_send::TokenSendModule
+    + permissions::PermissionsModule
+{
+    fn init_delisting(&self) {
+        self.delist_grace_period_epochs()
+            .set_if_empty(&DEFAULT_DELIST_GRACE_PERIOD_EPOCHS);
+    }
+
+    // Disables swaps and adding liquidity on the pair and flags it as delisted. LPs can still
+    // remove their liquidity.
+    // Returns the epoch from which the pair can be archived.
+    #[endpoint(delistPair)]
+    fn delist_pair(&self, pair_address: Address) -> SCResult<u64> {
+        self.require_role(Role::Admin)?;
+        require!(self.is_active(), "Not active");
+        require!(
+            self.pair_registry_backfilled().get(),
+            "Pair registry not backfilled"
+        );
+        let pair_id = self.pair_id_by_address(&pair_address).get();
+        require!(pair_id != 0, "Pair not registered");
+        require!(
+            !self.is_delisted_pair(&pair_address),
+            "Pair already delisted"
+        );
+        require!(
+            self.pair_info(pair_id).get().state != PairState::Sunset,
+            "Pair is in sunset"
+        );
+
+        self.change_pair_state(pair_address.clone(), PairState::ExitOnly);
+
+        let delist_epoch = self.blockchain().get_block_epoch();
+        let archivable_epoch = delist_epoch + self.delist_grace_period_epochs().get();
+        self.delisted_pairs()
+            .insert(pair_address.clone(), (delist_epoch, archivable_epoch));
+
+        self.emit_delist_pair_event(pair_address, archivable_epoch);
+        Ok(archivable_epoch)
+    }
+
+    // Once the grace period is over, anyone can remove the pair from getPair,
+    // getAllPairsAddresses and the token index. A new pair can then be created for the same
+    // tokens. The pair keeps its ID and can still be looked up by address.
+    #[endpoint(archivePair)]
+    fn archive_pair(&self, pair_address: Address) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
+        let delisting = self.delisted_pairs().get(&pair_address);
+        require!(delisting.is_some(), "Pair not delisted");
+        let (delist_epoch, archivable_epoch) = delisting.unwrap();
+        let archive_epoch = self.blockchain().get_block_epoch();
+        require!(archive_epoch >= archivable_epoch, "Grace period not over");
+
+        let pair_id = self.pair_id_by_address(&pair_address).get();
+        let pair_info = self.pair_info(pair_id).get();
+
+        // A pair finalized in sunset may already have been replaced for the same tokens.
+        let pair_tokens = PairTokens {
+            first_token_id: pair_info.first_token_id.clone(),
+            second_token_id: pair_info.second_token_id.clone(),
+        };
+        if self.pair_map().get(&pair_tokens) == Some(pair_address.clone()) {
+            self.pair_map().remove(&pair_tokens);
+        }
+        let sorted_tokens =
+            self.get_sorted_token_pair(&pair_info.first_token_id, &pair_info.second_token_id);
+        if self.pair_id_by_tokens(&sorted_tokens).get() == pair_id {
+            self.pair_id_by_tokens(&sorted_tokens).clear();
+        }
+        self.remove_token_pair_id(&pair_info.first_token_id, pair_id);
+        self.remove_token_pair_id(&pair_info.second_token_id, pair_id);
+        self.delisted_pairs().remove(&pair_address);
+
+        let archived_pair = ArchivedPair {
+            pair_id,
+            address: pair_address.clone(),
+            first_token_id: pair_info.first_token_id,
+            second_token_id: pair_info.second_token_id,
+            delist_epoch,
+            archive_epoch,
+        };
+        self.archived_pairs().push(&archived_pair);
+        self.archived_pair_index(&pair_address)
+            .set(&self.archived_pairs().len());
+
+        self.emit_archive_pair_event(archived_pair);
+        Ok(())
+    }
+
+    #[endpoint(setDelistGracePeriod)]
+    fn set_delist_grace_period(&self, grace_period_epochs: u64) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        let old_grace_period_epochs = self.delist_grace_period_epochs().get();
+        self.delist_grace_period_epochs().set(&grace_period_epochs);
+
+        self.emit_config_change_event(
+            b"delist_grace_period_epochs",
+            old_grace_period_epochs,
+            grace_period_epochs,
+        );
+        Ok(())
+    }
+
+    // Delisted pairs stay flagged after they are archived.
+    fn is_delisted_pair(&self, pair_address: &Address) -> bool {
+        self.delisted_pairs().get(pair_address).is_some() || self.is_archived_pair(pair_address)
+    }
+
+    #[view(getDelistedPairs)]
+    fn get_delisted_pairs(&self) -> MultiResultVec<MultiResult3<Address, u64, u64>> {
+        let pairs: Vec<MultiResult3<Address, u64, u64>> = self
+            .delisted_pairs()
+            .iter()
+            .map(|(address, (delist_epoch, archivable_epoch))| {
+                (address, delist_epoch, archivable_epoch).into()
+            })
+            .collect();
+        MultiResultVec::from_iter(pairs)
+    }
+
+    #[view(getArchivedPairs)]
+    fn get_archived_pairs(
+        &self,
+        offset: usize,
+        max_count: usize,
+    ) -> SCResult<MultiResultVec<ArchivedPair>> {
+        require!(max_count <= MAX_PAGE_SIZE, "Page too large");
+
+        let pairs: Vec<ArchivedPair> = (offset + 1..=self.archived_pairs().len())
+            .take(max_count)
+            .map(|index| self.archived_pairs().get(index))
+            .collect();
+        Ok(MultiResultVec::from_iter(pairs))
+    }
+
+    #[view(getArchivedPair)]
+    fn get_archived_pair(&self, pair_address: Address) -> Option<ArchivedPair> {
+        let index = self.archived_pair_index(&pair_address).get();
+        if index == 0 {
+            None
+        } else {
+            Some(self.archived_pairs().get(index))
+        }
+    }
+
+    #[view(getArchivedPairCount)]
+    fn get_archived_pair_count(&self) -> usize {
+        self.archived_pairs().len()
+    }
+
+    #[view(getDelistGracePeriod)]
+    #[storage_mapper("delist_grace_period_epochs")]
+    fn delist_grace_period_epochs(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[storage_mapper("delisted_pairs")]
+    fn delisted_pairs(&self) -> SafeMapMapper<Self::Storage, Address, (u64, u64)>;
+
+    #[storage_mapper("archived_pairs")]
+    fn archived_pairs(&self) -> VecMapper<Self::Storage, ArchivedPair>;
+}
@@ -0,0 +1,117 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use super::events;
+use super::state;
+
+mod egld_wrapper_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait EgldWrapper {
+        #[payable("EGLD")]
+        #[endpoint(wrapEgld)]
+        fn wrap_egld(&self, #[payment_amount] amount: Self::BigUint);
+
+        #[payable("*")]
+        #[endpoint(unwrapEgld)]
+        fn unwrap_egld(
+            &self,
+            #[payment_token] token_id: TokenIdentifier,
+            #[payment_amount] amount: Self::BigUint,
+        );
+    }
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct EgldWrapperConfig {
+    pub wrapper_address: Address,
+    pub wrapped_egld_token_id: TokenIdentifier,
+}
+
+#[elrond_wasm::module]
+pub trait EgldWrapperModule:
+    state::StateModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + permissions::PermissionsModule
+{
+    #[proxy]
+    fn egld_wrapper_proxy(&self, to: Address) -> egld_wrapper_proxy::Proxy<Self::SendApi>;
+
+    // EGLD payments are wrapped 1:1 and the wrapped token is returned in their place.
+    // Any other payment is returned as it is.
+    fn wrap_egld_payment(
+        &self,
+        token_id: TokenIdentifier,
+        amount: &Self::BigUint,
+    ) -> SCResult<TokenIdentifier> {
+        if !token_id.is_egld() {
+            return Ok(token_id);
+        }
+        let config = self.get_egld_wrapper_config()?;
+
+        self.egld_wrapper_proxy(config.wrapper_address)
+            .wrap_egld(amount.clone())
+            .execute_on_dest_context();
+        Ok(config.wrapped_egld_token_id)
+    }
+
+    fn is_wrapped_egld(&self, token_id: &TokenIdentifier) -> bool {
+        !self.egld_wrapper_config().is_empty()
+            && token_id == &self.egld_wrapper_config().get().wrapped_egld_token_id
+    }
+
+    // Sends the tokens, first unwrapping them to EGLD if asked to and they are wrapped EGLD.
+    fn send_tokens_or_unwrapped_egld(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: &Self::BigUint,
+        destination: &Address,
+        opt_accept_funds_func: &OptionalArg<BoxedBytes>,
+        unwrap: bool,
+    ) -> SCResult<()> {
+        if !unwrap || !self.is_wrapped_egld(token_id) {
+            return self.send_fft_tokens(token_id, amount, destination, opt_accept_funds_func);
+        }
+
+        if *amount == 0 {
+            return Ok(());
+        }
+
+        let config = self.egld_wrapper_config().get();
+        self.egld_wrapper_proxy(config.wrapper_address)
+            .unwrap_egld(token_id.clone(), amount.clone())
+            .execute_on_dest_context();
+
+        let (function, gas_limit) = match opt_accept_funds_func {
+            OptionalArg::Some(accept_funds_func) => (
+                accept_funds_func.as_slice(),
+                self.transfer_exec_gas_limit().get(),
+            ),
+            OptionalArg::None => {
+                let no_func: &[u8] = &[];
+                (no_func, 0u64)
+            }
+        };
+        SCResult::from_result(self.send().direct_egld_execute(
+            destination,
+            amount,
+            gas_limit,
+            function,
+            &ArgBuffer::new(),
+        ))
+    }
+
+    fn get_egld_wrapper_config(&self) -> SCResult<EgldWrapperConfig> {
+        require!(
+            !self.egld_wrapper_config().is_empty(),
+            "EGLD wrapper not configured"
+        );
+        Ok(self.egld_wrapper_config().get())
+    }
+
+    #[view(getEgldWrapperConfig)]
+    #[storage_mapper("egld_wrapper_config")]
+    fn egld_wrapper_config(&self) -> SingleValueMapper<Self::Storage, EgldWrapperConfig>;
+}
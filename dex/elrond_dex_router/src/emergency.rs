@@ -0,0 +1,132 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use elrond_dex_pair::config::State as PairState;
+use permissions::Role;
+
+use super::delisting;
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
+use super::state;
+
+const EMERGENCY_GAS_THRESHOLD: u64 = 20_000_000;
+
+#[elrond_wasm::module]
+pub trait EmergencyModule:
+    state::StateModule
+    + delisting::DelistingModule
+    + pair_manager::PairManagerModule
+    + pair_registry::PairRegistryModule
+    + events::EventsModule
+    + factory::FactoryModule
+    + egld_wrapper::EgldWrapperModule
+    + pool_factory::PoolFactoryModule
+    + referral::ReferralModule
+    + token_send::TokenSendModule
+    + pair_code_versions::PairCodeVersionsModule
+    + permissions::PermissionsModule
+{
+    // Pauses the router, then every registered pair that is not already paused, in sunset or
+    // delisted.
+    // Returns the pair ID to continue from when gas runs low, or 0 once all pairs are paused.
+    // The router state is only recorded by the call that starts the emergency.
+    #[endpoint(emergencyPause)]
+    fn emergency_pause(&self, from_pair_id: u64) -> SCResult<MultiResult2<u64, usize>> {
+        self.require_role(Role::Pauser)?;
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        require!(
+            self.pair_registry_backfilled().get(),
+            "Pair registry not backfilled"
+        );
+        if !self.emergency_active().get() {
+            let router_was_active = self.is_active();
+            self.emergency_active().set(&true);
+            self.emergency_router_was_active().set(&router_was_active);
+            if router_was_active {
+                self.set_router_state(false);
+            }
+        }
+
+        let pair_count = self.pair_count().get();
+        let mut pair_id = from_pair_id;
+        let mut paused = 0;
+
+        while pair_id <= pair_count {
+            if self.blockchain().get_gas_left() < EMERGENCY_GAS_THRESHOLD {
+                return Ok((pair_id, paused).into());
+            }
+
+            let pair_info = self.pair_info(pair_id).get();
+            if pair_info.state != PairState::Inactive
+                && pair_info.state != PairState::Sunset
+                && !self.is_delisted_pair(&pair_info.address)
+            {
+                self.change_pair_state(pair_info.address.clone(), PairState::Inactive);
+                self.emergency_paused_pairs()
+                    .insert(pair_id, pair_info.state);
+                paused += 1;
+            }
+            pair_id += 1;
+        }
+
+        Ok((0, paused).into())
+    }
+
+    // Puts every pair paused by emergencyPause back in the state it had before. Pairs delisted
+    // in the meantime are left as they are.
+    // The router itself is resumed last, once all pairs are done, and only if it was
+    // active when the emergency started.
+    #[endpoint(emergencyResume)]
+    fn emergency_resume(&self, from_pair_id: u64) -> SCResult<MultiResult2<u64, usize>> {
+        self.require_role(Role::Pauser)?;
+        require!(self.emergency_active().get(), "No active emergency");
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+
+        let pair_count = self.pair_count().get();
+        let mut pair_id = from_pair_id;
+        let mut resumed = 0;
+
+        while pair_id <= pair_count {
+            if self.blockchain().get_gas_left() < EMERGENCY_GAS_THRESHOLD {
+                return Ok((pair_id, resumed).into());
+            }
+
+            if let Some(previous_state) = self.emergency_paused_pairs().remove(&pair_id) {
+                let address = self.pair_info(pair_id).get().address;
+                if !self.is_delisted_pair(&address) {
+                    self.change_pair_state(address, previous_state);
+                    resumed += 1;
+                }
+            }
+            pair_id += 1;
+        }
+
+        if self.emergency_router_was_active().get() && !self.is_active() {
+            self.set_router_state(true);
+        }
+        self.emergency_active().clear();
+        self.emergency_router_was_active().clear();
+        Ok((0, resumed).into())
+    }
+
+    #[view(getEmergencyPausedPairCount)]
+    fn get_emergency_paused_pair_count(&self) -> usize {
+        self.emergency_paused_pairs().len()
+    }
+
+    #[view(isEmergencyActive)]
+    #[storage_mapper("emergency_active")]
+    fn emergency_active(&self) -> SingleValueMapper<Self::Storage, bool>;
+
+    #[storage_mapper("emergency_router_was_active")]
+    fn emergency_router_was_active(&self) -> SingleValueMapper<Self::Storage, bool>;
+
+    #[storage_mapper("emergency_paused_pairs")]
+    fn emergency_paused_pairs(&self) -> SafeMapMapper<Self::Storage, u64, PairState>;
+}
@@ -1,6 +1,23 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+use common_structs::FftTokenAmountPair;
+use elrond_dex_pair::config::State as PairState;
+
+use super::delisting::ArchivedPair;
+use super::egld_wrapper::EgldWrapperConfig;
+use super::fee_policy::FeePercents;
+use super::fee_routing::FeeRoutingConfig;
+use super::pair_bonds::{PairBond, PairBondConfig};
+use super::pool_factory::PoolType;
+use super::timelock::TimelockedAction;
+
+#[derive(TopEncode, NestedEncode, TypeAbi)]
+pub enum CodeConstructionStage {
+    Start,
+    End,
+}
+
 #[derive(TopEncode)]
 pub struct CreatePairEvent {
     caller: Address,
@@ -14,6 +31,289 @@ pub struct CreatePairEvent {
     timestamp: u64,
 }
 
+#[derive(TopEncode)]
+pub struct CreatePoolEvent {
+    caller: Address,
+    tokens: Vec<TokenIdentifier>,
+    weights: Vec<u64>,
+    total_fee_percent: u64,
+    special_fee_percent: u64,
+    pool_address: Address,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct ReferralFeeEvent<BigUint: BigUintApi> {
+    referrer: Address,
+    pair_address: Address,
+    fee_amount: FftTokenAmountPair<BigUint>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct ClaimReferralRewardsEvent<BigUint: BigUintApi> {
+    referrer: Address,
+    rewards: Vec<FftTokenAmountPair<BigUint>>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct TimelockEvent {
+    caller: Address,
+    action_id: u64,
+    action: TimelockedAction,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct ConfigChangeEvent {
+    caller: Address,
+    name: BoxedBytes,
+    old_value: u64,
+    new_value: u64,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct TokenConfigChangeEvent<BigUint: BigUintApi> {
+    caller: Address,
+    token_id: TokenIdentifier,
+    name: BoxedBytes,
+    old_value: BigUint,
+    new_value: BigUint,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct FeePolicyChangeEvent {
+    caller: Address,
+    token_class: u64,
+    old_fee_percents: Option<FeePercents>,
+    new_fee_percents: Option<FeePercents>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct FeeRoutingConfigChangeEvent {
+    caller: Address,
+    old_config: Option<FeeRoutingConfig>,
+    new_config: FeeRoutingConfig,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct EgldWrapperConfigChangeEvent {
+    caller: Address,
+    old_config: Option<EgldWrapperConfig>,
+    new_config: EgldWrapperConfig,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairStateChangeEvent {
+    caller: Address,
+    pair_address: Address,
+    old_state: PairState,
+    new_state: PairState,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairFeeChangeEvent {
+    caller: Address,
+    pair_address: Address,
+    fee_to_address: Address,
+    old_fee_token: Option<TokenIdentifier>,
+    new_fee_token: Option<TokenIdentifier>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairWhitelistChangeEvent {
+    caller: Address,
+    pair_address: Address,
+    address: Address,
+    old_whitelisted: bool,
+    new_whitelisted: bool,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairFeePercentsChangeEvent {
+    caller: Address,
+    pair_address: Address,
+    old_fee_percents: FeePercents,
+    new_fee_percents: FeePercents,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairTemporaryOwnerExpiredEvent {
+    caller: Address,
+    pair_address: Address,
+    temporary_owner: Address,
+    creation_block: u64,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairFeeOverrideChangeEvent {
+    caller: Address,
+    pair_address: Address,
+    old_value: bool,
+    new_value: bool,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairFeeRoutingEvent {
+    caller: Address,
+    pair_address: Address,
+    fee_to_address: Address,
+    fee_token: TokenIdentifier,
+    swap_pair_addresses: Vec<Address>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct UpgradePairEvent {
+    caller: Address,
+    pair_address: Address,
+    old_code_version: u64,
+    new_code_version: u64,
+    old_fee_percents: FeePercents,
+    new_fee_percents: FeePercents,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairMigrationEvent {
+    caller: Address,
+    pair_address: Address,
+    destination_address: Address,
+    open: bool,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct FinalizePairSunsetEvent {
+    caller: Address,
+    pair_address: Address,
+    treasury_address: Address,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct IssueLpTokenEvent {
+    caller: Address,
+    pair_address: Address,
+    lp_token_id: TokenIdentifier,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct SetLocalRolesEvent {
+    caller: Address,
+    address: Address,
+    token_id: TokenIdentifier,
+    roles: Vec<EsdtLocalRole>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct CodeConstructionEvent {
+    caller: Address,
+    pool_type: Option<PoolType>,
+    stage: CodeConstructionStage,
+    code_version: u64,
+    code_hash: H256,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairBondConfigChangeEvent<BigUint: BigUintApi> {
+    caller: Address,
+    old_config: PairBondConfig<BigUint>,
+    new_config: PairBondConfig<BigUint>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct PairBondEvent<BigUint: BigUintApi> {
+    caller: Address,
+    pair_address: Address,
+    bond: PairBond<BigUint>,
+    destination: Address,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct DelistPairEvent {
+    caller: Address,
+    pair_address: Address,
+    archivable_epoch: u64,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct ArchivePairEvent {
+    caller: Address,
+    archived_pair: ArchivedPair,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[elrond_wasm::module]
 pub trait EventsModule {
     fn emit_create_pair_event(
@@ -45,13 +345,930 @@ pub trait EventsModule {
         )
     }
 
-    #[event("create_pair")]
-    fn create_pair_event(
+    fn emit_create_pool_event(
         self,
-        #[indexed] first_token_id: TokenIdentifier,
-        #[indexed] second_token_id: TokenIdentifier,
-        #[indexed] caller: Address,
-        #[indexed] epoch: u64,
-        swap_event: CreatePairEvent,
+        caller: Address,
+        tokens: Vec<TokenIdentifier>,
+        weights: Vec<u64>,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+        pool_address: Address,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.create_pool_event(
+            pool_address.clone(),
+            caller.clone(),
+            epoch,
+            CreatePoolEvent {
+                caller,
+                tokens,
+                weights,
+                total_fee_percent,
+                special_fee_percent,
+                pool_address,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_referral_fee_event(
+        self,
+        referrer: Address,
+        pair_address: Address,
+        fee_amount: FftTokenAmountPair<Self::BigUint>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.referral_fee_event(
+            referrer.clone(),
+            fee_amount.token_id.clone(),
+            epoch,
+            ReferralFeeEvent {
+                referrer,
+                pair_address,
+                fee_amount,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_claim_referral_rewards_event(
+        self,
+        referrer: Address,
+        rewards: Vec<FftTokenAmountPair<Self::BigUint>>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.claim_referral_rewards_event(
+            referrer.clone(),
+            epoch,
+            ClaimReferralRewardsEvent {
+                referrer,
+                rewards,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_timelock_queued_event(self, action_id: u64, action: TimelockedAction) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.timelock_queued_event(
+            action_id,
+            caller.clone(),
+            epoch,
+            TimelockEvent {
+                caller,
+                action_id,
+                action,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_timelock_executed_event(self, action_id: u64, action: TimelockedAction) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.timelock_executed_event(
+            action_id,
+            caller.clone(),
+            epoch,
+            TimelockEvent {
+                caller,
+                action_id,
+                action,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_timelock_cancelled_event(self, action_id: u64, action: TimelockedAction) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.timelock_cancelled_event(
+            action_id,
+            caller.clone(),
+            epoch,
+            TimelockEvent {
+                caller,
+                action_id,
+                action,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_config_change_event(self, name: &[u8], old_value: u64, new_value: u64) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        let name = BoxedBytes::from(name);
+        self.config_change_event(
+            name.clone(),
+            caller.clone(),
+            epoch,
+            ConfigChangeEvent {
+                caller,
+                name,
+                old_value,
+                new_value,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_token_config_change_event(
+        self,
+        token_id: TokenIdentifier,
+        name: &[u8],
+        old_value: Self::BigUint,
+        new_value: Self::BigUint,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        let name = BoxedBytes::from(name);
+        self.token_config_change_event(
+            token_id.clone(),
+            name.clone(),
+            caller.clone(),
+            epoch,
+            TokenConfigChangeEvent {
+                caller,
+                token_id,
+                name,
+                old_value,
+                new_value,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_fee_policy_change_event(
+        self,
+        token_class: u64,
+        old_fee_percents: Option<FeePercents>,
+        new_fee_percents: Option<FeePercents>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.fee_policy_change_event(
+            token_class,
+            caller.clone(),
+            epoch,
+            FeePolicyChangeEvent {
+                caller,
+                token_class,
+                old_fee_percents,
+                new_fee_percents,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_fee_routing_config_change_event(
+        self,
+        old_config: Option<FeeRoutingConfig>,
+        new_config: FeeRoutingConfig,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.fee_routing_config_change_event(
+            caller.clone(),
+            epoch,
+            FeeRoutingConfigChangeEvent {
+                caller,
+                old_config,
+                new_config,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_egld_wrapper_config_change_event(
+        self,
+        old_config: Option<EgldWrapperConfig>,
+        new_config: EgldWrapperConfig,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.egld_wrapper_config_change_event(
+            caller.clone(),
+            epoch,
+            EgldWrapperConfigChangeEvent {
+                caller,
+                old_config,
+                new_config,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_state_change_event(
+        self,
+        pair_address: Address,
+        old_state: PairState,
+        new_state: PairState,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_state_change_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairStateChangeEvent {
+                caller,
+                pair_address,
+                old_state,
+                new_state,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_fee_change_event(
+        self,
+        pair_address: Address,
+        fee_to_address: Address,
+        old_fee_token: Option<TokenIdentifier>,
+        new_fee_token: Option<TokenIdentifier>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_fee_change_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairFeeChangeEvent {
+                caller,
+                pair_address,
+                fee_to_address,
+                old_fee_token,
+                new_fee_token,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_whitelist_change_event(
+        self,
+        pair_address: Address,
+        address: Address,
+        old_whitelisted: bool,
+        new_whitelisted: bool,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_whitelist_change_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairWhitelistChangeEvent {
+                caller,
+                pair_address,
+                address,
+                old_whitelisted,
+                new_whitelisted,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_fee_percents_change_event(
+        self,
+        pair_address: Address,
+        old_fee_percents: FeePercents,
+        new_fee_percents: FeePercents,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_fee_percents_change_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairFeePercentsChangeEvent {
+                caller,
+                pair_address,
+                old_fee_percents,
+                new_fee_percents,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_temporary_owner_expired_event(
+        self,
+        pair_address: Address,
+        temporary_owner: Address,
+        creation_block: u64,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_temporary_owner_expired_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairTemporaryOwnerExpiredEvent {
+                caller,
+                pair_address,
+                temporary_owner,
+                creation_block,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_fee_override_change_event(
+        self,
+        pair_address: Address,
+        old_value: bool,
+        new_value: bool,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_fee_override_change_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairFeeOverrideChangeEvent {
+                caller,
+                pair_address,
+                old_value,
+                new_value,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_fee_routing_event(
+        self,
+        pair_address: Address,
+        fee_to_address: Address,
+        fee_token: TokenIdentifier,
+        swap_pair_addresses: Vec<Address>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_fee_routing_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairFeeRoutingEvent {
+                caller,
+                pair_address,
+                fee_to_address,
+                fee_token,
+                swap_pair_addresses,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_upgrade_pair_event(
+        self,
+        pair_address: Address,
+        old_code_version: u64,
+        new_code_version: u64,
+        old_fee_percents: FeePercents,
+        new_fee_percents: FeePercents,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.upgrade_pair_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            UpgradePairEvent {
+                caller,
+                pair_address,
+                old_code_version,
+                new_code_version,
+                old_fee_percents,
+                new_fee_percents,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_migration_event(
+        self,
+        pair_address: Address,
+        destination_address: Address,
+        open: bool,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_migration_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairMigrationEvent {
+                caller,
+                pair_address,
+                destination_address,
+                open,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_finalize_pair_sunset_event(self, pair_address: Address, treasury_address: Address) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.finalize_pair_sunset_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            FinalizePairSunsetEvent {
+                caller,
+                pair_address,
+                treasury_address,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_issue_lp_token_event(
+        self,
+        caller: Address,
+        pair_address: Address,
+        lp_token_id: TokenIdentifier,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.issue_lp_token_event(
+            pair_address.clone(),
+            lp_token_id.clone(),
+            caller.clone(),
+            epoch,
+            IssueLpTokenEvent {
+                caller,
+                pair_address,
+                lp_token_id,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_set_local_roles_event(
+        self,
+        caller: Address,
+        address: Address,
+        token_id: TokenIdentifier,
+        roles: Vec<EsdtLocalRole>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.set_local_roles_event(
+            address.clone(),
+            token_id.clone(),
+            caller.clone(),
+            epoch,
+            SetLocalRolesEvent {
+                caller,
+                address,
+                token_id,
+                roles,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_code_construction_event(
+        self,
+        pool_type: Option<PoolType>,
+        stage: CodeConstructionStage,
+        code_version: u64,
+        code_hash: H256,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.code_construction_event(
+            caller.clone(),
+            epoch,
+            CodeConstructionEvent {
+                caller,
+                pool_type,
+                stage,
+                code_version,
+                code_hash,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_pair_bond_config_change_event(
+        self,
+        old_config: PairBondConfig<Self::BigUint>,
+        new_config: PairBondConfig<Self::BigUint>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.pair_bond_config_change_event(
+            caller.clone(),
+            epoch,
+            PairBondConfigChangeEvent {
+                caller,
+                old_config,
+                new_config,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_release_pair_bond_event(
+        self,
+        pair_address: Address,
+        bond: PairBond<Self::BigUint>,
+        destination: Address,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.release_pair_bond_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairBondEvent {
+                caller,
+                pair_address,
+                bond,
+                destination,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_slash_pair_bond_event(
+        self,
+        pair_address: Address,
+        bond: PairBond<Self::BigUint>,
+        destination: Address,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.slash_pair_bond_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            PairBondEvent {
+                caller,
+                pair_address,
+                bond,
+                destination,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_delist_pair_event(self, pair_address: Address, archivable_epoch: u64) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.delist_pair_event(
+            pair_address.clone(),
+            caller.clone(),
+            epoch,
+            DelistPairEvent {
+                caller,
+                pair_address,
+                archivable_epoch,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_archive_pair_event(self, archived_pair: ArchivedPair) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.archive_pair_event(
+            archived_pair.address.clone(),
+            caller.clone(),
+            epoch,
+            ArchivePairEvent {
+                caller,
+                archived_pair,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    #[event("create_pair")]
+    fn create_pair_event(
+        self,
+        #[indexed] first_token_id: TokenIdentifier,
+        #[indexed] second_token_id: TokenIdentifier,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        swap_event: CreatePairEvent,
+    );
+
+    #[event("create_pool")]
+    fn create_pool_event(
+        self,
+        #[indexed] pool_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        create_pool_event: CreatePoolEvent,
+    );
+
+    #[event("referral_fee")]
+    fn referral_fee_event(
+        self,
+        #[indexed] referrer: Address,
+        #[indexed] token_id: TokenIdentifier,
+        #[indexed] epoch: u64,
+        referral_fee_event: ReferralFeeEvent<Self::BigUint>,
+    );
+
+    #[event("claim_referral_rewards")]
+    fn claim_referral_rewards_event(
+        self,
+        #[indexed] referrer: Address,
+        #[indexed] epoch: u64,
+        claim_referral_rewards_event: ClaimReferralRewardsEvent<Self::BigUint>,
+    );
+
+    #[event("timelock_queued")]
+    fn timelock_queued_event(
+        self,
+        #[indexed] action_id: u64,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        timelock_event: TimelockEvent,
+    );
+
+    #[event("timelock_executed")]
+    fn timelock_executed_event(
+        self,
+        #[indexed] action_id: u64,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        timelock_event: TimelockEvent,
+    );
+
+    #[event("timelock_cancelled")]
+    fn timelock_cancelled_event(
+        self,
+        #[indexed] action_id: u64,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        timelock_event: TimelockEvent,
+    );
+
+    #[event("config_change")]
+    fn config_change_event(
+        self,
+        #[indexed] name: BoxedBytes,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        config_change_event: ConfigChangeEvent,
+    );
+
+    #[event("token_config_change")]
+    fn token_config_change_event(
+        self,
+        #[indexed] token_id: TokenIdentifier,
+        #[indexed] name: BoxedBytes,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        token_config_change_event: TokenConfigChangeEvent<Self::BigUint>,
+    );
+
+    #[event("fee_policy_change")]
+    fn fee_policy_change_event(
+        self,
+        #[indexed] token_class: u64,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        fee_policy_change_event: FeePolicyChangeEvent,
+    );
+
+    #[event("fee_routing_config_change")]
+    fn fee_routing_config_change_event(
+        self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        fee_routing_config_change_event: FeeRoutingConfigChangeEvent,
+    );
+
+    #[event("egld_wrapper_config_change")]
+    fn egld_wrapper_config_change_event(
+        self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        egld_wrapper_config_change_event: EgldWrapperConfigChangeEvent,
+    );
+
+    #[event("pair_state_change")]
+    fn pair_state_change_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_state_change_event: PairStateChangeEvent,
+    );
+
+    #[event("pair_fee_change")]
+    fn pair_fee_change_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_fee_change_event: PairFeeChangeEvent,
+    );
+
+    #[event("pair_whitelist_change")]
+    fn pair_whitelist_change_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_whitelist_change_event: PairWhitelistChangeEvent,
+    );
+
+    #[event("pair_fee_percents_change")]
+    fn pair_fee_percents_change_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_fee_percents_change_event: PairFeePercentsChangeEvent,
+    );
+
+    #[event("pair_temporary_owner_expired")]
+    fn pair_temporary_owner_expired_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_temporary_owner_expired_event: PairTemporaryOwnerExpiredEvent,
+    );
+
+    #[event("pair_fee_override_change")]
+    fn pair_fee_override_change_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_fee_override_change_event: PairFeeOverrideChangeEvent,
+    );
+
+    #[event("pair_fee_routing")]
+    fn pair_fee_routing_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_fee_routing_event: PairFeeRoutingEvent,
+    );
+
+    #[event("upgrade_pair")]
+    fn upgrade_pair_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        upgrade_pair_event: UpgradePairEvent,
+    );
+
+    #[event("pair_migration")]
+    fn pair_migration_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_migration_event: PairMigrationEvent,
+    );
+
+    #[event("finalize_pair_sunset")]
+    fn finalize_pair_sunset_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        finalize_pair_sunset_event: FinalizePairSunsetEvent,
+    );
+
+    #[event("issue_lp_token")]
+    fn issue_lp_token_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] lp_token_id: TokenIdentifier,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        issue_lp_token_event: IssueLpTokenEvent,
+    );
+
+    #[event("set_local_roles")]
+    fn set_local_roles_event(
+        self,
+        #[indexed] address: Address,
+        #[indexed] token_id: TokenIdentifier,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        set_local_roles_event: SetLocalRolesEvent,
+    );
+
+    #[event("code_construction")]
+    fn code_construction_event(
+        self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        code_construction_event: CodeConstructionEvent,
+    );
+
+    #[event("pair_bond_config_change")]
+    fn pair_bond_config_change_event(
+        self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        pair_bond_config_change_event: PairBondConfigChangeEvent<Self::BigUint>,
+    );
+
+    #[event("release_pair_bond")]
+    fn release_pair_bond_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        release_pair_bond_event: PairBondEvent<Self::BigUint>,
+    );
+
+    #[event("slash_pair_bond")]
+    fn slash_pair_bond_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        slash_pair_bond_event: PairBondEvent<Self::BigUint>,
+    );
+
+    #[event("delist_pair")]
+    fn delist_pair_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        delist_pair_event: DelistPairEvent,
+    );
+
+    #[event("archive_pair")]
+    fn archive_pair_event(
+        self,
+        #[indexed] pair_address: Address,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        archive_pair_event: ArchivePairEvent,
     );
 }
@@ -1,8 +1,19 @@
+#![allow(clippy::too_many_arguments)]
+
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+use super::events;
+use super::events::CodeConstructionStage;
+use super::fee_policy::FeePercents;
+use super::pair_code_versions;
+use super::pair_registry;
+use super::pair_registry::pair_info_proxy::ProxyTrait as _;
+use super::pool_factory;
 use super::state;
 use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+use permissions::Role;
 
 const TEMPORARY_OWNER_PERIOD_BLOCKS: u64 = 50;
 
@@ -12,6 +23,22 @@ pub struct PairTokens {
     pub second_token_id: TokenIdentifier,
 }
 
+// The keys of `pair_map` are kept in a linked list stored under the same key.
+// These mirror its list info and node links, so the backfill can walk it node by node.
+#[derive(TopEncode, TopDecode)]
+pub struct PairMapListInfo {
+    pub len: u32,
+    pub front: u32,
+    pub back: u32,
+    pub new: u32,
+}
+
+#[derive(TopEncode, TopDecode)]
+pub struct PairMapNodeLinks {
+    pub previous: u32,
+    pub next: u32,
+}
+
 #[derive(TopEncode, TopDecode, PartialEq, TypeAbi)]
 pub struct PairContractMetadata {
     first_token_id: TokenIdentifier,
@@ -20,12 +47,36 @@ pub struct PairContractMetadata {
 }
 
 #[elrond_wasm::module]
-pub trait FactoryModule: state::StateModule {
+pub trait FactoryModule:
+    state::StateModule
+    + pool_factory::PoolFactoryModule
+    + pair_registry::PairRegistryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + events::EventsModule
+    + permissions::PermissionsModule
+{
     fn init_factory(&self) {
         self.pair_code_ready().set_if_empty(&false);
-        self.pair_code().set_if_empty(&BoxedBytes::empty());
         self.temporary_owner_period()
             .set_if_empty(&TEMPORARY_OWNER_PERIOD_BLOCKS);
+        self.pair_registry_backfilled()
+            .set_if_empty(&self.pair_map().is_empty());
+
+        // Code uploaded before versioning becomes the first version.
+        let legacy_code = self.legacy_pair_code().get();
+        if !legacy_code.is_empty() {
+            if self.pair_code_ready().get() {
+                self.clear_pending_pair_code();
+                self.append_pending_pair_code(&legacy_code);
+                let code_hash = self.crypto().keccak256(legacy_code.as_slice());
+                let version = self
+                    .save_pending_pair_code_version(&code_hash, BoxedBytes::empty())
+                    .ok()
+                    .unwrap_or_default();
+                self.current_pair_code_version().set(&version);
+            }
+            self.legacy_pair_code().clear();
+        }
     }
 
     fn create_pair(
@@ -41,7 +92,8 @@ pub trait FactoryModule: state::StateModule {
         let gas_left = self.blockchain().get_gas_left();
         let amount = 0u64.into();
         let mut arg_buffer = ArgBuffer::new();
-        let code = self.pair_code().get();
+        let version = self.current_pair_code_version().get();
+        let code = self.get_pair_code_version_code(version);
         arg_buffer.push_argument_bytes(first_token_id.as_esdt_identifier());
         arg_buffer.push_argument_bytes(second_token_id.as_esdt_identifier());
         arg_buffer.push_argument_bytes(self.blockchain().get_sc_address().as_bytes());
@@ -52,6 +104,19 @@ pub trait FactoryModule: state::StateModule {
             self.send()
                 .deploy_contract(gas_left, &amount, &code, code_metadata, &arg_buffer);
         require!(new_address.is_some(), "deploy failed");
+        self.register_pair(
+            new_address.as_ref().unwrap(),
+            first_token_id,
+            second_token_id,
+            &self.blockchain().get_caller(),
+            self.blockchain().get_block_nonce(),
+            TokenIdentifier::egld(),
+            total_fee_percent,
+            special_fee_percent,
+            PairState::ActiveNoSwaps,
+        );
+        self.pair_code_version_of(new_address.as_ref().unwrap())
+            .set(&version);
         self.pair_map().insert(
             PairTokens {
                 first_token_id: first_token_id.clone(),
@@ -77,8 +142,12 @@ pub trait FactoryModule: state::StateModule {
         owner: &Address,
         total_fee_percent: u64,
         special_fee_percent: u64,
+        version: u64,
     ) -> SCResult<()> {
         require!(self.pair_code_ready().get(), "Pair code not ready");
+        self.require_pair_code_version_exists(version)?;
+        let old_code_version = self.pair_code_version_of(pair_address).get();
+        let old_fee_percents = self.get_pair_fee_percents(pair_address);
 
         let mut arg_buffer = ArgBuffer::new();
         arg_buffer.push_argument_bytes(first_token_id.as_esdt_identifier());
@@ -92,20 +161,54 @@ pub trait FactoryModule: state::StateModule {
             pair_address,
             self.blockchain().get_gas_left(),
             &0u64.into(),
-            &self.pair_code().get(),
+            &self.get_pair_code_version_code(version),
             CodeMetadata::UPGRADEABLE,
             &arg_buffer,
         );
+        self.pair_code_version_of(pair_address).set(&version);
+        self.update_pair_info(pair_address, |info| {
+            info.total_fee_percent = total_fee_percent;
+            info.special_fee_percent = special_fee_percent;
+        });
+
+        self.emit_upgrade_pair_event(
+            pair_address.clone(),
+            old_code_version,
+            version,
+            old_fee_percents,
+            FeePercents {
+                total_fee_percent,
+                special_fee_percent,
+            },
+        );
         Ok(())
     }
 
     fn start_pair_construct(&self) {
         self.pair_code_ready().set(&false);
-        self.pair_code().set(&BoxedBytes::empty());
+        self.clear_pending_pair_code();
+        self.emit_code_construction_event(
+            None,
+            CodeConstructionStage::Start,
+            self.pending_pair_code_version(),
+            H256::zero(),
+        );
     }
 
-    fn end_pair_construct(&self) {
+    fn end_pair_construct(&self, expected_hash: &H256, label: BoxedBytes) -> SCResult<u64> {
+        let version = self.save_pending_pair_code_version(expected_hash, label)?;
+        // Later versions only become current through the timelock.
+        if self.current_pair_code_version().get() == 0 {
+            self.update_current_pair_code_version(version);
+        }
         self.pair_code_ready().set(&true);
+        self.emit_code_construction_event(
+            None,
+            CodeConstructionStage::End,
+            version,
+            expected_hash.clone(),
+        );
+        Ok(version)
     }
 
     fn append_pair_code(&self, part: &BoxedBytes) -> SCResult<()> {
@@ -113,15 +216,25 @@ pub trait FactoryModule: state::StateModule {
             !self.pair_code_ready().get(),
             "Pair construction not started"
         );
-        let existent = self.pair_code().get();
-        let new_code = BoxedBytes::from_concat(&[existent.as_slice(), part.as_slice()]);
-        self.pair_code().set(&new_code);
+        self.append_pending_pair_code(part);
         Ok(())
     }
 
     #[storage_mapper("pair_map")]
     fn pair_map(&self) -> SafeMapMapper<Self::Storage, PairTokens, Address>;
 
+    #[storage_mapper("pair_map.info")]
+    fn pair_map_list_info(&self) -> SingleValueMapper<Self::Storage, PairMapListInfo>;
+
+    #[storage_mapper("pair_map.node_links")]
+    fn pair_map_node_links(
+        &self,
+        node_id: u32,
+    ) -> SingleValueMapper<Self::Storage, PairMapNodeLinks>;
+
+    #[storage_mapper("pair_map.value")]
+    fn pair_map_node_value(&self, node_id: u32) -> SingleValueMapper<Self::Storage, PairTokens>;
+
     #[view(getAllPairsAddresses)]
     fn get_all_pairs_addresses(&self) -> MultiResultVec<Address> {
         self.pair_map().values().collect()
@@ -146,6 +259,8 @@ pub trait FactoryModule: state::StateModule {
         MultiResultVec::from_iter(map)
     }
 
+    // The creator stays the temporary owner until the period has passed, after which the
+    // entry is removed and anyone allowed to may issue the LP token.
     fn get_pair_temporary_owner(&self, pair_address: &Address) -> Option<Address> {
         let result = self.pair_temporary_owner().get(pair_address);
 
@@ -153,8 +268,13 @@ pub trait FactoryModule: state::StateModule {
             Some((temporary_owner, creation_block)) => {
                 let expire_block = creation_block + self.temporary_owner_period().get();
 
-                if expire_block >= self.blockchain().get_block_nonce() {
+                if self.blockchain().get_block_nonce() > expire_block {
                     self.pair_temporary_owner().remove(pair_address);
+                    self.emit_pair_temporary_owner_expired_event(
+                        pair_address.clone(),
+                        temporary_owner,
+                        creation_block,
+                    );
                     None
                 } else {
                     Some(temporary_owner)
@@ -170,75 +290,184 @@ pub trait FactoryModule: state::StateModule {
         first_token_id: TokenIdentifier,
         second_token_id: TokenIdentifier,
     ) -> Option<Address> {
+        let address = self.get_registered_pair(&first_token_id, &second_token_id);
+        if address.is_some() || self.pair_registry_backfilled().get() {
+            return address.filter(|pair_address| !self.sunset_pairs().contains(pair_address));
+        }
+
         let address = self.pair_map().get(&PairTokens {
             first_token_id: first_token_id.clone(),
             second_token_id: second_token_id.clone(),
         });
 
-        if address.is_none() {
+        let address = if address.is_none() {
             self.pair_map().get(&PairTokens {
                 first_token_id: second_token_id,
                 second_token_id: first_token_id,
             })
         } else {
             address
-        }
+        };
+
+        address.filter(|pair_address| !self.sunset_pairs().contains(pair_address))
     }
 
-    #[only_owner]
     #[endpoint(startPairCodeConstruction)]
     fn start_pair_code_construction(&self) -> SCResult<()> {
+        self.require_role(Role::Upgrader)?;
         require!(self.is_active(), "Not active");
 
         self.start_pair_construct();
         Ok(())
     }
 
-    #[only_owner]
     #[endpoint(endPairCodeConstruction)]
-    fn end_pair_code_construction(&self) -> SCResult<()> {
+    fn end_pair_code_construction(
+        &self,
+        expected_hash: H256,
+        #[var_args] opt_label: OptionalArg<BoxedBytes>,
+    ) -> SCResult<u64> {
+        self.require_role(Role::Upgrader)?;
         require!(self.is_active(), "Not active");
+        require!(
+            !self.pair_code_ready().get(),
+            "Pair construction not started"
+        );
 
-        self.end_pair_construct();
-        Ok(())
+        let label = match opt_label {
+            OptionalArg::Some(label) => label,
+            OptionalArg::None => BoxedBytes::empty(),
+        };
+        self.end_pair_construct(&expected_hash, label)
     }
 
-    #[only_owner]
     #[endpoint(appendPairCode)]
     fn apppend_pair_code(&self, part: BoxedBytes) -> SCResult<()> {
+        self.require_role(Role::Upgrader)?;
         require!(self.is_active(), "Not active");
 
         self.append_pair_code(&part)
     }
 
-    #[only_owner]
     #[endpoint(clearPairTemporaryOwnerStorage)]
     fn clear_pair_temporary_owner_storage(&self) -> SCResult<usize> {
+        self.require_role(Role::Admin)?;
         let size = self.pair_temporary_owner().len();
         self.pair_temporary_owner().clear();
+
+        self.emit_config_change_event(b"pair_temporary_owner_count", size as u64, 0);
         Ok(size)
     }
 
-    #[only_owner]
     #[endpoint(setTemporaryOwnerPeriod)]
     fn set_temporary_owner_period(&self, period_blocks: u64) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        let old_period_blocks = self.temporary_owner_period().get();
         self.temporary_owner_period().set(&period_blocks);
+
+        self.emit_config_change_event(b"temporary_owner_period", old_period_blocks, period_blocks);
         Ok(())
     }
 
     fn check_is_pair_sc(&self, pair_address: &Address) -> SCResult<()> {
         require!(
-            self.pair_map()
-                .values()
-                .any(|address| &address == pair_address),
+            self.is_registered_pair(pair_address)
+                || self.is_pool_sc(pair_address)
+                || (!self.pair_registry_backfilled().get()
+                    && self
+                        .pair_map()
+                        .values()
+                        .any(|address| &address == pair_address)),
             "Not a pair SC"
         );
         Ok(())
     }
 
+    #[endpoint(backfillPairRegistry)]
+    fn backfill_pair_registry(&self, max_pairs: usize) -> SCResult<usize> {
+        self.require_role(Role::Admin)?;
+        require!(
+            !self.pair_registry_backfilled().get(),
+            "Pair registry already backfilled"
+        );
+
+        // The cursor is the node of the next pair to check, 0 before the first call.
+        // Starting from it, only the pairs handled by this call are read.
+        let mut node_id = self.pair_registry_backfill_cursor().get();
+        if node_id == 0 && !self.pair_map().is_empty() {
+            node_id = self.pair_map_list_info().get().front;
+        }
+        let mut registered = 0;
+        while node_id != 0 && registered < max_pairs {
+            let pair_tokens = self.pair_map_node_value(node_id).get();
+            node_id = self.pair_map_node_links(node_id).get().next;
+            let address = match self.pair_map().get(&pair_tokens) {
+                Some(address) => address,
+                None => continue,
+            };
+            if self.is_registered_pair(&address) {
+                continue;
+            }
+
+            let state = self
+                .pair_info_proxy(address.clone())
+                .get_state()
+                .execute_on_dest_context();
+            let lp_token_id = self
+                .pair_info_proxy(address.clone())
+                .get_lp_token_identifier()
+                .execute_on_dest_context();
+            let total_fee_percent = self
+                .pair_info_proxy(address.clone())
+                .get_total_fee_percent()
+                .execute_on_dest_context();
+            let special_fee_percent = self
+                .pair_info_proxy(address.clone())
+                .get_special_fee_percent()
+                .execute_on_dest_context();
+
+            // Creator and creation block were never stored for pairs created before the registry.
+            self.register_pair(
+                &address,
+                &pair_tokens.first_token_id,
+                &pair_tokens.second_token_id,
+                &Address::zero(),
+                0,
+                lp_token_id,
+                total_fee_percent,
+                special_fee_percent,
+                state,
+            );
+            registered += 1;
+        }
+        self.pair_registry_backfill_cursor().set(&node_id);
+
+        if node_id == 0 {
+            self.pair_registry_backfilled().set(&true);
+            self.emit_config_change_event(b"pair_registry_backfilled", 0, 1);
+        }
+        Ok(registered)
+    }
+
+    #[view(getPairRegistryBackfillCursor)]
+    #[storage_mapper("pair_registry_backfill_cursor")]
+    fn pair_registry_backfill_cursor(&self) -> SingleValueMapper<Self::Storage, u32>;
+
+    #[view(getSunsetPairs)]
+    fn get_sunset_pairs(&self) -> MultiResultVec<Address> {
+        self.sunset_pairs().iter().collect()
+    }
+
+    #[storage_mapper("sunset_pairs")]
+    fn sunset_pairs(&self) -> SafeSetMapper<Self::Storage, Address>;
+
     #[view(getPairCode)]
+    fn get_pair_code(&self) -> BoxedBytes {
+        self.get_pair_code_version_code(self.current_pair_code_version().get())
+    }
+
     #[storage_mapper("pair_code")]
-    fn pair_code(&self) -> SingleValueMapper<Self::Storage, BoxedBytes>;
+    fn legacy_pair_code(&self) -> SingleValueMapper<Self::Storage, BoxedBytes>;
 
     #[view(getPairCodeReady)]
     #[storage_mapper("pair_code_ready")]
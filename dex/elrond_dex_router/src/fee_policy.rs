@@ -0,0 +1,258 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+use permissions::Role;
+
+use super::delisting;
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pair_registry::MAX_PAGE_SIZE;
+use super::pool_factory;
+use super::referral;
+use super::state;
+
+use elrond_dex_pair::config::ProxyTrait as _;
+
+pub const DEFAULT_TOTAL_FEE_PERCENT: u64 = 300;
+pub const DEFAULT_SPECIAL_FEE_PERCENT: u64 = 50;
+pub const MAX_TOTAL_FEE_PERCENT: u64 = 100_000;
+const PUSH_FEE_POLICY_GAS_THRESHOLD: u64 = 20_000_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi, Clone, Copy)]
+pub struct FeePercents {
+    pub total_fee_percent: u64,
+    pub special_fee_percent: u64,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct FeePolicyDivergence {
+    pub pair_id: u64,
+    pub pair_address: Address,
+    pub current: FeePercents,
+    pub policy: FeePercents,
+}
+
+#[elrond_wasm::module]
+pub trait FeePolicyModule:
+    state::StateModule
+    + delisting::DelistingModule
+    + factory::FactoryModule
+    + pair_manager::PairManagerModule
+    + pair_registry::PairRegistryModule
+    + events::EventsModule
+    + pool_factory::PoolFactoryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + token_send::TokenSendModule
+    + permissions::PermissionsModule
+{
+    fn init_fee_policy(&self) {
+        self.default_fee_percents().set_if_empty(&FeePercents {
+            total_fee_percent: DEFAULT_TOTAL_FEE_PERCENT,
+            special_fee_percent: DEFAULT_SPECIAL_FEE_PERCENT,
+        });
+    }
+
+    fn require_valid_fee_percents(&self, fee_percents: &FeePercents) -> SCResult<()> {
+        require!(
+            fee_percents.total_fee_percent >= fee_percents.special_fee_percent
+                && fee_percents.total_fee_percent < MAX_TOTAL_FEE_PERCENT,
+            "Bad percents"
+        );
+        Ok(())
+    }
+
+    // A class override only applies to pairs whose two tokens are both in that class.
+    // Every other pair follows the default.
+    fn get_fee_policy_for_tokens(
+        &self,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+    ) -> FeePercents {
+        let token_class = self.token_class(first_token_id).get();
+        if token_class != 0 && token_class == self.token_class(second_token_id).get() {
+            if let Some(fee_percents) = self.class_fee_percents().get(&token_class) {
+                return fee_percents;
+            }
+        }
+        self.default_fee_percents().get()
+    }
+
+    // A pair created or upgraded with fees other than its policy keeps them when the policy
+    // is pushed, until the override is cleared.
+    fn update_pair_fee_override(
+        &self,
+        pair_address: &Address,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+        fee_percents: &FeePercents,
+    ) {
+        let old_value = self.pair_fee_override(pair_address).get();
+        let new_value =
+            fee_percents != &self.get_fee_policy_for_tokens(first_token_id, second_token_id);
+        if old_value != new_value {
+            self.pair_fee_override(pair_address).set(&new_value);
+            self.emit_pair_fee_override_change_event(pair_address.clone(), old_value, new_value);
+        }
+    }
+
+    #[endpoint(clearPairFeeOverride)]
+    fn clear_pair_fee_override(&self, pair_address: Address) -> SCResult<()> {
+        self.require_role(Role::FeeManager)?;
+        require!(
+            self.pair_fee_override(&pair_address).get(),
+            "Pair has no fee override"
+        );
+        self.pair_fee_override(&pair_address).clear();
+        self.emit_pair_fee_override_change_event(pair_address, true, false);
+        Ok(())
+    }
+
+    // Sets the policy fees on every pair from the given ID, optionally only on the pairs of a
+    // token class. Pairs in sunset, delisted pairs, pairs with a fee override and pairs already
+    // matching the policy are skipped.
+    // Returns the pair ID to continue from when gas runs low, or 0 once all pairs are done.
+    #[endpoint(pushFeePolicy)]
+    fn push_fee_policy(
+        &self,
+        from_pair_id: u64,
+        #[var_args] opt_token_class: OptionalArg<u64>,
+    ) -> SCResult<MultiResult2<u64, usize>> {
+        self.require_role(Role::FeeManager)?;
+        require!(self.is_active(), "Not active");
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        require!(
+            self.pair_registry_backfilled().get(),
+            "Pair registry not backfilled"
+        );
+        let token_class = opt_token_class.into_option();
+
+        let pair_count = self.pair_count().get();
+        let mut pair_id = from_pair_id;
+        let mut pushed = 0;
+
+        while pair_id <= pair_count {
+            if self.blockchain().get_gas_left() < PUSH_FEE_POLICY_GAS_THRESHOLD {
+                return Ok((pair_id, pushed).into());
+            }
+
+            let pair_info = self.pair_info(pair_id).get();
+            pair_id += 1;
+            if pair_info.state == PairState::Sunset
+                || self.is_delisted_pair(&pair_info.address)
+                || self.pair_fee_override(&pair_info.address).get()
+            {
+                continue;
+            }
+            if let Some(token_class) = token_class {
+                if self.token_class(&pair_info.first_token_id).get() != token_class
+                    || self.token_class(&pair_info.second_token_id).get() != token_class
+                {
+                    continue;
+                }
+            }
+
+            let policy = self
+                .get_fee_policy_for_tokens(&pair_info.first_token_id, &pair_info.second_token_id);
+            let current = self.get_pair_fee_percents(&pair_info.address);
+            if current == policy {
+                continue;
+            }
+
+            self.pair_contract_proxy(pair_info.address.clone())
+                .set_fee_percent(policy.total_fee_percent, policy.special_fee_percent)
+                .execute_on_dest_context();
+            self.update_pair_info(&pair_info.address, |info| {
+                info.total_fee_percent = policy.total_fee_percent;
+                info.special_fee_percent = policy.special_fee_percent;
+            });
+            self.emit_pair_fee_percents_change_event(pair_info.address, current, policy);
+            pushed += 1;
+        }
+
+        Ok((0, pushed).into())
+    }
+
+    // Checks up to max_count pairs starting from the given ID and lists those whose fees,
+    // as read from the pair, differ from the policy. Pairs in sunset, delisted pairs and pairs
+    // with a fee override are not checked.
+    #[view(getFeePolicyDivergences)]
+    fn get_fee_policy_divergences(
+        &self,
+        from_pair_id: u64,
+        max_count: usize,
+    ) -> SCResult<MultiResultVec<FeePolicyDivergence>> {
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        require!(max_count <= MAX_PAGE_SIZE, "Page too large");
+
+        let pair_count = self.pair_count().get();
+        let mut divergences = Vec::new();
+        for pair_id in (from_pair_id..=pair_count).take(max_count) {
+            let pair_info = self.pair_info(pair_id).get();
+            if pair_info.state == PairState::Sunset
+                || self.is_delisted_pair(&pair_info.address)
+                || self.pair_fee_override(&pair_info.address).get()
+            {
+                continue;
+            }
+
+            let current = self.get_pair_fee_percents(&pair_info.address);
+            let policy = self
+                .get_fee_policy_for_tokens(&pair_info.first_token_id, &pair_info.second_token_id);
+            if current != policy {
+                divergences.push(FeePolicyDivergence {
+                    pair_id,
+                    pair_address: pair_info.address,
+                    current,
+                    policy,
+                });
+            }
+        }
+        Ok(MultiResultVec::from_iter(divergences))
+    }
+
+    #[view(getFeePolicy)]
+    fn get_fee_policy(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+    ) -> FeePercents {
+        self.get_fee_policy_for_tokens(&first_token_id, &second_token_id)
+    }
+
+    #[view(getTokenClass)]
+    fn get_token_class(&self, token_id: TokenIdentifier) -> u64 {
+        self.token_class(&token_id).get()
+    }
+
+    #[view(getClassFeePercents)]
+    fn get_class_fee_percents(&self) -> MultiResultVec<MultiResult2<u64, FeePercents>> {
+        let overrides: Vec<MultiResult2<u64, FeePercents>> = self
+            .class_fee_percents()
+            .iter()
+            .map(|(token_class, fee_percents)| (token_class, fee_percents).into())
+            .collect();
+        MultiResultVec::from_iter(overrides)
+    }
+
+    #[view(getDefaultFeePercents)]
+    #[storage_mapper("default_fee_percents")]
+    fn default_fee_percents(&self) -> SingleValueMapper<Self::Storage, FeePercents>;
+
+    #[storage_mapper("class_fee_percents")]
+    fn class_fee_percents(&self) -> SafeMapMapper<Self::Storage, u64, FeePercents>;
+
+    #[view(hasPairFeeOverride)]
+    #[storage_mapper("pair_fee_override")]
+    fn pair_fee_override(&self, pair_address: &Address) -> SingleValueMapper<Self::Storage, bool>;
+
+    #[storage_mapper("token_class")]
+    fn token_class(&self, token_id: &TokenIdentifier) -> SingleValueMapper<Self::Storage, u64>;
+}
@@ -0,0 +1,299 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+use permissions::Role;
+
+use super::delisting;
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pair_registry::MAX_PAGE_SIZE;
+use super::pool_factory;
+use super::referral;
+use super::state;
+
+use elrond_dex_pair::fee::ProxyTrait as _;
+
+const PUSH_FEE_ROUTING_GAS_THRESHOLD: u64 = 30_000_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct FeeRoutingConfig {
+    pub fee_to_address: Address,
+    pub fee_token: TokenIdentifier,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi, Clone, Copy)]
+pub enum FeeRouteType {
+    Direct,
+    Extern,
+    Unroutable,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct FeeRouteHop {
+    pub token_id: TokenIdentifier,
+    pub swap_pair_address: Address,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct FeeRoute {
+    pub pair_address: Address,
+    pub first_token_id: TokenIdentifier,
+    pub second_token_id: TokenIdentifier,
+    pub route_type: FeeRouteType,
+    pub hops: Vec<FeeRouteHop>,
+    pub is_configured: bool,
+}
+
+#[elrond_wasm::module]
+pub trait FeeRoutingModule:
+    state::StateModule
+    + delisting::DelistingModule
+    + factory::FactoryModule
+    + pair_manager::PairManagerModule
+    + pair_registry::PairRegistryModule
+    + events::EventsModule
+    + pool_factory::PoolFactoryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + token_send::TokenSendModule
+    + permissions::PermissionsModule
+{
+    #[endpoint(pushFeeRouting)]
+    fn push_fee_routing(&self, pair_address: Address) -> SCResult<()> {
+        self.require_role(Role::FeeManager)?;
+        require!(self.is_active(), "Not active");
+        require!(
+            self.is_registered_pair(&pair_address),
+            "Pair not registered"
+        );
+        require!(
+            !self.sunset_pairs().contains(&pair_address),
+            "Pair is in sunset"
+        );
+        require!(!self.is_delisted_pair(&pair_address), "Pair is delisted");
+        let config = self.get_fee_routing_config()?;
+
+        let pair_info = self
+            .pair_info(self.pair_id_by_address(&pair_address).get())
+            .get();
+        self.push_pair_fee_routing(
+            pair_address,
+            pair_info.first_token_id,
+            pair_info.second_token_id,
+            &config,
+        );
+        Ok(())
+    }
+
+    // Pushes the fee routing to every pair from the given ID that is not in sunset or delisted.
+    // Returns the pair ID to continue from when gas runs low, or 0 once all pairs are done.
+    #[endpoint(pushFeeRoutingToAllPairs)]
+    fn push_fee_routing_to_all_pairs(
+        &self,
+        from_pair_id: u64,
+    ) -> SCResult<MultiResult2<u64, usize>> {
+        self.require_role(Role::FeeManager)?;
+        require!(self.is_active(), "Not active");
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        require!(
+            self.pair_registry_backfilled().get(),
+            "Pair registry not backfilled"
+        );
+        let config = self.get_fee_routing_config()?;
+
+        let pair_count = self.pair_count().get();
+        let mut pair_id = from_pair_id;
+        let mut pushed = 0;
+
+        while pair_id <= pair_count {
+            if self.blockchain().get_gas_left() < PUSH_FEE_ROUTING_GAS_THRESHOLD {
+                return Ok((pair_id, pushed).into());
+            }
+
+            let pair_info = self.pair_info(pair_id).get();
+            pair_id += 1;
+            if pair_info.state == PairState::Sunset || self.is_delisted_pair(&pair_info.address) {
+                continue;
+            }
+            self.push_pair_fee_routing(
+                pair_info.address,
+                pair_info.first_token_id,
+                pair_info.second_token_id,
+                &config,
+            );
+            pushed += 1;
+        }
+
+        Ok((0, pushed).into())
+    }
+
+    // The pair gets the fee destination and one trusted swap pair for each of its tokens that
+    // has a pair with the fee token. Each of those swap pairs has to whitelist it in return.
+    // Routes are a single swap: a token with no direct pair to the fee token is not routed.
+    fn push_pair_fee_routing(
+        &self,
+        pair_address: Address,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        config: &FeeRoutingConfig,
+    ) {
+        let route = self.compute_fee_route(
+            pair_address.clone(),
+            first_token_id,
+            second_token_id,
+            &config.fee_token,
+        );
+        let swap_pair_addresses: Vec<Address> = route
+            .hops
+            .iter()
+            .map(|hop| hop.swap_pair_address.clone())
+            .collect();
+        let trusted_swap_pairs: Vec<MultiArg3<TokenIdentifier, TokenIdentifier, Address>> = route
+            .hops
+            .iter()
+            .map(|hop| {
+                (
+                    hop.token_id.clone(),
+                    config.fee_token.clone(),
+                    hop.swap_pair_address.clone(),
+                )
+                    .into()
+            })
+            .collect();
+
+        self.pair_contract_proxy(pair_address.clone())
+            .set_fee_routing(
+                config.fee_to_address.clone(),
+                config.fee_token.clone(),
+                trusted_swap_pairs.into(),
+            )
+            .execute_on_dest_context();
+
+        for hop in route.hops.iter() {
+            if !self.is_whitelisted_on_pair(&hop.swap_pair_address, &pair_address) {
+                self.set_pair_whitelist(hop.swap_pair_address.clone(), pair_address.clone(), true);
+            }
+        }
+
+        self.emit_pair_fee_routing_event(
+            pair_address,
+            config.fee_to_address.clone(),
+            config.fee_token.clone(),
+            swap_pair_addresses,
+        );
+    }
+
+    fn compute_fee_route(
+        &self,
+        pair_address: Address,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        fee_token: &TokenIdentifier,
+    ) -> FeeRoute {
+        let mut hops = Vec::new();
+        let route_type = if &first_token_id == fee_token || &second_token_id == fee_token {
+            FeeRouteType::Direct
+        } else {
+            for token_id in [&first_token_id, &second_token_id].iter() {
+                if let Some(swap_pair_address) =
+                    self.get_pair((*token_id).clone(), fee_token.clone())
+                {
+                    hops.push(FeeRouteHop {
+                        token_id: (*token_id).clone(),
+                        swap_pair_address,
+                    });
+                }
+            }
+
+            if hops.is_empty() {
+                FeeRouteType::Unroutable
+            } else {
+                FeeRouteType::Extern
+            }
+        };
+
+        FeeRoute {
+            pair_address,
+            first_token_id,
+            second_token_id,
+            route_type,
+            hops,
+            is_configured: false,
+        }
+    }
+
+    fn is_fee_route_configured(&self, route: &FeeRoute, config: &FeeRoutingConfig) -> bool {
+        let destination_token = self
+            .pair_contract_proxy(route.pair_address.clone())
+            .get_fee_destination_token(config.fee_to_address.clone())
+            .execute_on_dest_context();
+        if destination_token != Some(config.fee_token.clone()) {
+            return false;
+        }
+
+        route.hops.iter().all(|hop| {
+            let trusted_swap_pair = self
+                .pair_contract_proxy(route.pair_address.clone())
+                .get_trusted_swap_pair(hop.token_id.clone(), config.fee_token.clone())
+                .execute_on_dest_context();
+            trusted_swap_pair == hop.swap_pair_address
+                && self.is_whitelisted_on_pair(&hop.swap_pair_address, &route.pair_address)
+        })
+    }
+
+    fn is_whitelisted_on_pair(&self, pair_address: &Address, address: &Address) -> bool {
+        self.pair_contract_proxy(pair_address.clone())
+            .is_whitelisted(address.clone())
+            .execute_on_dest_context()
+    }
+
+    fn get_fee_routing_config(&self) -> SCResult<FeeRoutingConfig> {
+        require!(
+            !self.fee_routing_config().is_empty(),
+            "Fee routing not configured"
+        );
+        Ok(self.fee_routing_config().get())
+    }
+
+    // Lists, for up to max_count pairs starting from the given ID, how the fees of those not
+    // in sunset or delisted reach the fee token and whether the configuration on them matches.
+    #[view(getFeeRoutingGraph)]
+    fn get_fee_routing_graph(
+        &self,
+        from_pair_id: u64,
+        max_count: usize,
+    ) -> SCResult<MultiResultVec<FeeRoute>> {
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        require!(max_count <= MAX_PAGE_SIZE, "Page too large");
+        let config = self.get_fee_routing_config()?;
+
+        let pair_count = self.pair_count().get();
+        let mut routes = Vec::new();
+        for pair_id in (from_pair_id..=pair_count).take(max_count) {
+            let pair_info = self.pair_info(pair_id).get();
+            if pair_info.state == PairState::Sunset || self.is_delisted_pair(&pair_info.address) {
+                continue;
+            }
+            let mut route = self.compute_fee_route(
+                pair_info.address,
+                pair_info.first_token_id,
+                pair_info.second_token_id,
+                &config.fee_token,
+            );
+            route.is_configured = self.is_fee_route_configured(&route, &config);
+            routes.push(route);
+        }
+        Ok(MultiResultVec::from_iter(routes))
+    }
+
+    #[view(getFeeRoutingConfig)]
+    #[storage_mapper("fee_routing_config")]
+    fn fee_routing_config(&self) -> SingleValueMapper<Self::Storage, FeeRoutingConfig>;
+}
@@ -3,24 +3,63 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+mod delisting;
+mod egld_wrapper;
+mod emergency;
 mod events;
 mod factory;
+mod fee_policy;
+mod fee_routing;
 mod lp_tokens;
+mod pair_bonds;
+mod pair_code_versions;
 mod pair_manager;
+mod pair_registry;
+mod pair_setup;
+mod path_quoter;
+mod pool_factory;
+mod price_aggregator;
+mod referral;
+mod route_finder;
+mod split_swap;
 mod state;
+mod timelock;
+mod token_policy;
 
-const DEFAULT_TOTAL_FEE_PERCENT: u64 = 300;
-const DEFAULT_SPECIAL_FEE_PERCENT: u64 = 50;
-const MAX_TOTAL_FEE_PERCENT: u64 = 100_000;
+use elrond_dex_pair::config::State as PairState;
+use fee_policy::{FeePercents, MAX_TOTAL_FEE_PERCENT};
+use permissions::Role;
+use pool_factory::PoolType;
+use timelock::TimelockActionType;
+
+const UPGRADE_PAIRS_GAS_THRESHOLD: u64 = 50_000_000;
 
 #[elrond_wasm::contract]
 pub trait Router:
-    factory::FactoryModule
+    delisting::DelistingModule
+    + egld_wrapper::EgldWrapperModule
+    + emergency::EmergencyModule
+    + factory::FactoryModule
+    + fee_policy::FeePolicyModule
+    + fee_routing::FeeRoutingModule
+    + pair_bonds::PairBondsModule
+    + pair_code_versions::PairCodeVersionsModule
     + pair_manager::PairManagerModule
+    + pair_registry::PairRegistryModule
+    + pair_setup::PairSetupModule
+    + path_quoter::PathQuoterModule
     + lp_tokens::LpTokensModule
+    + pool_factory::PoolFactoryModule
+    + price_aggregator::PriceAggregatorModule
+    + referral::ReferralModule
+    + route_finder::RouteFinderModule
+    + split_swap::SplitSwapModule
     + state::StateModule
+    + timelock::TimelockModule
+    + token_policy::TokenPolicyModule
     + events::EventsModule
     + token_send::TokenSendModule
+    + permissions::PermissionsModule
 {
     #[proxy]
     fn pair_contract_proxy(&self, to: Address) -> elrond_dex_pair::Proxy<Self::SendApi>;
@@ -31,7 +70,11 @@ pub trait Router:
         self.pair_creation_enabled().set_if_empty(&false);
 
         self.init_factory();
+        self.init_fee_policy();
+        self.init_delisting();
+        self.init_timelock();
         self.owner().set(&self.blockchain().get_caller());
+        self.init_permissions(&self.blockchain().get_caller());
     }
 
     #[endpoint(createPair)]
@@ -42,16 +85,46 @@ pub trait Router:
         #[var_args] fee_percents: VarArgs<u64>,
     ) -> SCResult<Address> {
         require!(self.is_active(), "Not active");
-        let owner = self.owner().get();
         let caller = self.blockchain().get_caller();
 
-        if caller != owner {
+        if !self.has_role(Role::Admin, caller) {
             require!(
                 self.pair_creation_enabled().get(),
                 "Pair creation is disabled"
             );
         }
 
+        self.create_pair_for_caller(first_token_id, second_token_id, fee_percents.into_vec())
+    }
+
+    #[payable("*")]
+    #[endpoint(createPairWithBond)]
+    fn create_pair_with_bond(
+        &self,
+        #[payment_token] bond_token_id: TokenIdentifier,
+        #[payment_amount] bond_amount: Self::BigUint,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        #[var_args] fee_percents: VarArgs<u64>,
+    ) -> SCResult<Address> {
+        require!(self.is_active(), "Not active");
+        self.require_valid_bond_payment(&bond_token_id, &bond_amount)?;
+
+        let address =
+            self.create_pair_for_caller(first_token_id, second_token_id, fee_percents.into_vec())?;
+        self.lock_pair_bond(&address, bond_token_id, bond_amount);
+        Ok(address)
+    }
+
+    fn create_pair_for_caller(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        fee_percents_vec: Vec<u64>,
+    ) -> SCResult<Address> {
+        let owner = self.owner().get();
+        let caller = self.blockchain().get_caller();
+
         require!(first_token_id != second_token_id, "Identical tokens");
         require!(
             first_token_id.is_valid_esdt_identifier(),
@@ -61,14 +134,15 @@ pub trait Router:
             second_token_id.is_valid_esdt_identifier(),
             "Second Token ID is not a valid esdt token ID"
         );
+        self.require_allowed_token_pair(&first_token_id, &second_token_id)?;
         let pair_address = self.get_pair(first_token_id.clone(), second_token_id.clone());
         require!(pair_address.is_none(), "Pair already exists");
 
-        let mut total_fee_percent_requested = DEFAULT_TOTAL_FEE_PERCENT;
-        let mut special_fee_percent_requested = DEFAULT_SPECIAL_FEE_PERCENT;
-        let fee_percents_vec = fee_percents.into_vec();
+        let fee_policy = self.get_fee_policy_for_tokens(&first_token_id, &second_token_id);
+        let mut total_fee_percent_requested = fee_policy.total_fee_percent;
+        let mut special_fee_percent_requested = fee_policy.special_fee_percent;
 
-        if caller == owner {
+        if self.has_role(Role::Admin, caller.clone()) {
             require!(fee_percents_vec.len() == 2, "Bad percents length");
             total_fee_percent_requested = fee_percents_vec[0];
             special_fee_percent_requested = fee_percents_vec[1];
@@ -86,6 +160,15 @@ pub trait Router:
             total_fee_percent_requested,
             special_fee_percent_requested,
         )?;
+        self.update_pair_fee_override(
+            &address,
+            &first_token_id,
+            &second_token_id,
+            &FeePercents {
+                total_fee_percent: total_fee_percent_requested,
+                special_fee_percent: special_fee_percent_requested,
+            },
+        );
 
         self.emit_create_pair_event(
             caller,
@@ -98,14 +181,167 @@ pub trait Router:
         Ok(address)
     }
 
+    #[payable("EGLD")]
+    #[endpoint(createPairAndSetup)]
+    fn create_pair_and_setup(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        lp_token_display_name: BoxedBytes,
+        lp_token_ticker: BoxedBytes,
+        #[payment_amount] issue_cost: Self::BigUint,
+        #[var_args] fee_percents: VarArgs<u64>,
+    ) -> SCResult<AsyncCall<Self::SendApi>> {
+        let pair_address =
+            self.create_pair_endpoint(first_token_id, second_token_id, fee_percents)?;
+        self.start_pair_setup(
+            pair_address,
+            lp_token_display_name,
+            lp_token_ticker,
+            issue_cost,
+        )
+    }
+
+    #[endpoint(createWeightedPool)]
+    fn create_weighted_pool_endpoint(
+        &self,
+        #[var_args] tokens_and_weights: MultiArgVec<MultiArg2<TokenIdentifier, u64>>,
+    ) -> SCResult<Address> {
+        require!(self.is_active(), "Not active");
+        let owner = self.owner().get();
+        let caller = self.blockchain().get_caller();
+
+        if !self.has_role(Role::Admin, caller.clone()) {
+            require!(
+                self.pair_creation_enabled().get(),
+                "Pair creation is disabled"
+            );
+        }
+
+        let tokens_and_weights_vec: Vec<(TokenIdentifier, u64)> = tokens_and_weights
+            .into_vec()
+            .into_iter()
+            .map(|entry| entry.into_tuple())
+            .collect();
+        for (token_id, _) in tokens_and_weights_vec.iter() {
+            require!(
+                token_id.is_valid_esdt_identifier(),
+                "Token ID is not a valid esdt token ID"
+            );
+        }
+        let token_ids: Vec<TokenIdentifier> = tokens_and_weights_vec
+            .iter()
+            .map(|(token_id, _)| token_id.clone())
+            .collect();
+        self.require_allowed_pool_tokens(&token_ids)?;
+
+        let pool_tokens = self.build_pool_tokens(PoolType::Weighted, tokens_and_weights_vec);
+        require!(
+            self.pool_map().get(&pool_tokens).is_none(),
+            "Pool already exists"
+        );
+
+        let fee_percents = self.default_fee_percents().get();
+        let address = self.create_weighted_pool(
+            &pool_tokens,
+            &owner,
+            fee_percents.total_fee_percent,
+            fee_percents.special_fee_percent,
+        )?;
+        self.pair_temporary_owner().insert(
+            address.clone(),
+            (caller.clone(), self.blockchain().get_block_nonce()),
+        );
+
+        self.emit_create_pool_event(
+            caller,
+            pool_tokens.tokens,
+            pool_tokens.weights,
+            fee_percents.total_fee_percent,
+            fee_percents.special_fee_percent,
+            address.clone(),
+        );
+        Ok(address)
+    }
+
+    #[endpoint(createConcentratedPool)]
+    fn create_concentrated_pool_endpoint(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        tick_spacing: u64,
+        initial_sqrt_price: Self::BigUint,
+    ) -> SCResult<Address> {
+        require!(self.is_active(), "Not active");
+        let owner = self.owner().get();
+        let caller = self.blockchain().get_caller();
+
+        if !self.has_role(Role::Admin, caller.clone()) {
+            require!(
+                self.pair_creation_enabled().get(),
+                "Pair creation is disabled"
+            );
+        }
+
+        require!(first_token_id != second_token_id, "Identical tokens");
+        require!(
+            first_token_id.is_valid_esdt_identifier(),
+            "First Token ID is not a valid esdt token ID"
+        );
+        require!(
+            second_token_id.is_valid_esdt_identifier(),
+            "Second Token ID is not a valid esdt token ID"
+        );
+        self.require_allowed_token_pair(&first_token_id, &second_token_id)?;
+
+        let pool_tokens = self.build_pool_tokens(
+            PoolType::Concentrated,
+            [
+                (first_token_id.clone(), 0u64),
+                (second_token_id.clone(), 0u64),
+            ]
+            .to_vec(),
+        );
+        require!(
+            self.pool_map().get(&pool_tokens).is_none(),
+            "Pool already exists"
+        );
+
+        let fee_percents = self.default_fee_percents().get();
+        let address = self.create_concentrated_pool(
+            &pool_tokens,
+            &first_token_id,
+            &second_token_id,
+            &owner,
+            fee_percents.total_fee_percent,
+            fee_percents.special_fee_percent,
+            tick_spacing,
+            &initial_sqrt_price,
+        )?;
+        self.pair_temporary_owner().insert(
+            address.clone(),
+            (caller.clone(), self.blockchain().get_block_nonce()),
+        );
+
+        self.emit_create_pool_event(
+            caller,
+            pool_tokens.tokens,
+            pool_tokens.weights,
+            fee_percents.total_fee_percent,
+            fee_percents.special_fee_percent,
+            address.clone(),
+        );
+        Ok(address)
+    }
+
     #[endpoint(upgradePair)]
     fn upgrade_pair_endpoint(
         &self,
         first_token_id: TokenIdentifier,
         second_token_id: TokenIdentifier,
         #[var_args] fee_percents: VarArgs<u64>,
-    ) -> SCResult<()> {
-        only_owner!(self, "No permissions");
+    ) -> SCResult<u64> {
+        self.require_role(Role::Upgrader)?;
         require!(self.is_active(), "Not active");
 
         require!(first_token_id != second_token_id, "Identical tokens");
@@ -131,37 +367,88 @@ pub trait Router:
             "Bad percents"
         );
 
-        self.upgrade_pair(
-            &pair_address.unwrap(),
-            &first_token_id,
-            &second_token_id,
-            &self.owner().get(),
-            total_fee_percent_requested,
-            special_fee_percent_requested,
-        )
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::UpgradePair,
+            Vec::new(),
+            [first_token_id, second_token_id].to_vec(),
+            [
+                total_fee_percent_requested,
+                special_fee_percent_requested,
+                self.current_pair_code_version().get(),
+            ]
+            .to_vec(),
+        ))
+    }
+
+    // Returns the pair ID to resume from when gas runs low, or 0 once all pairs are upgraded.
+    // Delisted and archived pairs are not upgraded.
+    #[endpoint(upgradePairs)]
+    fn upgrade_pairs(&self, version: u64, from_pair_id: u64) -> SCResult<MultiResult2<u64, usize>> {
+        self.require_role(Role::Upgrader)?;
+        require!(self.is_active(), "Not active");
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        self.require_pair_code_version_exists(version)?;
+        require!(
+            self.approved_pair_code_version().get() == version,
+            "Pair code version not approved"
+        );
+
+        let owner = self.owner().get();
+        let pair_count = self.pair_count().get();
+        let mut pair_id = from_pair_id;
+        let mut upgraded = 0;
+
+        while pair_id <= pair_count {
+            if self.blockchain().get_gas_left() < UPGRADE_PAIRS_GAS_THRESHOLD {
+                return Ok((pair_id, upgraded).into());
+            }
+
+            let pair_info = self.pair_info(pair_id).get();
+            if self.pair_code_version_of(&pair_info.address).get() != version
+                && !self.is_delisted_pair(&pair_info.address)
+            {
+                self.upgrade_pair(
+                    &pair_info.address,
+                    &pair_info.first_token_id,
+                    &pair_info.second_token_id,
+                    &owner,
+                    pair_info.total_fee_percent,
+                    pair_info.special_fee_percent,
+                    version,
+                )?;
+                upgraded += 1;
+            }
+            pair_id += 1;
+        }
+
+        Ok((0, upgraded).into())
     }
 
-    #[only_owner]
     #[endpoint]
     fn pause(&self, address: Address) -> SCResult<()> {
+        self.require_role(Role::Pauser)?;
         if address == self.blockchain().get_sc_address() {
-            self.state().set(&false);
+            self.set_router_state(false);
         } else {
             self.check_is_pair_sc(&address)?;
-            self.pause_pair(address);
+            self.change_pair_state(address, PairState::Inactive);
         }
         Ok(())
     }
 
-    #[only_owner]
     #[endpoint]
-    fn resume(&self, address: Address) -> SCResult<()> {
-        if address == self.blockchain().get_sc_address() {
-            self.state().set(&true);
-        } else {
+    fn resume(&self, address: Address) -> SCResult<u64> {
+        self.require_role(Role::Pauser)?;
+        if address != self.blockchain().get_sc_address() {
             self.check_is_pair_sc(&address)?;
-            self.resume_pair(address);
+            require!(!self.is_delisted_pair(&address), "Pair is delisted");
         }
-        Ok(())
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::Resume,
+            [address].to_vec(),
+            Vec::new(),
+            Vec::new(),
+        ))
     }
 }
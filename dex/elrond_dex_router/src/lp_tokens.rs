@@ -1,9 +1,18 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+use permissions::Role;
+
+use super::egld_wrapper;
+use super::events;
 use super::factory;
+use super::pair_code_versions;
 use super::pair_manager;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
 use super::state;
+use super::token_policy;
 
 const LP_TOKEN_DECIMALS: usize = 18;
 const LP_TOKEN_INITIAL_SUPPLY: u64 = 1000;
@@ -13,7 +22,15 @@ pub trait LpTokensModule:
     pair_manager::PairManagerModule
     + state::StateModule
     + factory::FactoryModule
+    + pair_registry::PairRegistryModule
+    + token_policy::TokenPolicyModule
     + token_send::TokenSendModule
+    + egld_wrapper::EgldWrapperModule
+    + pool_factory::PoolFactoryModule
+    + referral::ReferralModule
+    + events::EventsModule
+    + pair_code_versions::PairCodeVersionsModule
+    + permissions::PermissionsModule
 {
     #[payable("EGLD")]
     #[endpoint(issueLpToken)]
@@ -26,7 +43,7 @@ pub trait LpTokensModule:
     ) -> SCResult<AsyncCall<Self::SendApi>> {
         require!(self.is_active(), "Not active");
         let caller = self.blockchain().get_caller();
-        if caller != self.owner().get() {
+        if !self.has_role(Role::Admin, caller.clone()) {
             require!(
                 self.pair_creation_enabled().get(),
                 "Pair creation is disabled"
@@ -45,24 +62,12 @@ pub trait LpTokensModule:
         let result = self.get_lp_token_for_pair(&pair_address);
         require!(result.is_egld(), "LP Token already issued");
 
-        Ok(ESDTSystemSmartContractProxy::new_proxy_obj(self.send())
-            .issue_fungible(
-                issue_cost,
-                &tp_token_display_name,
-                &tp_token_ticker,
-                &Self::BigUint::from(LP_TOKEN_INITIAL_SUPPLY),
-                FungibleTokenProperties {
-                    num_decimals: LP_TOKEN_DECIMALS,
-                    can_freeze: true,
-                    can_wipe: true,
-                    can_pause: true,
-                    can_mint: true,
-                    can_burn: true,
-                    can_change_owner: true,
-                    can_upgrade: true,
-                    can_add_special_roles: true,
-                },
-            )
+        if let Some(pair_info) = self.get_pair_info(pair_address.clone()) {
+            self.require_allowed_token_pair(&pair_info.first_token_id, &pair_info.second_token_id)?;
+        }
+
+        Ok(self
+            .issue_lp_token_call(issue_cost, &tp_token_display_name, &tp_token_ticker)
             .async_call()
             .with_callback(
                 self.callbacks()
@@ -78,17 +83,17 @@ pub trait LpTokensModule:
         let pair_token = self.get_lp_token_for_pair(&pair_address);
         require!(pair_token.is_esdt(), "LP token not issued");
 
-        Ok(ESDTSystemSmartContractProxy::new_proxy_obj(self.send())
-            .set_special_roles(
+        Ok(self
+            .set_lp_local_roles_call(&pair_address, &pair_token)
+            .async_call()
+            .with_callback(self.callbacks().change_roles_callback(
+                &self.blockchain().get_caller(),
                 &pair_address,
                 &pair_token,
-                &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
-            )
-            .async_call()
-            .with_callback(self.callbacks().change_roles_callback()))
+                [EsdtLocalRole::Mint, EsdtLocalRole::Burn].to_vec(),
+            )))
     }
 
-    #[only_owner]
     #[endpoint(setLocalRolesOwner)]
     fn set_local_roles_owner(
         &self,
@@ -96,12 +101,56 @@ pub trait LpTokensModule:
         address: Address,
         #[var_args] roles: VarArgs<EsdtLocalRole>,
     ) -> SCResult<AsyncCall<Self::SendApi>> {
+        self.require_role(Role::Admin)?;
         require!(self.is_active(), "Not active");
         require!(!roles.is_empty(), "Empty roles");
+        let roles = roles.into_vec();
         Ok(ESDTSystemSmartContractProxy::new_proxy_obj(self.send())
             .set_special_roles(&address, &token, roles.as_slice())
             .async_call()
-            .with_callback(self.callbacks().change_roles_callback()))
+            .with_callback(self.callbacks().change_roles_callback(
+                &self.blockchain().get_caller(),
+                &address,
+                &token,
+                roles,
+            )))
+    }
+
+    fn issue_lp_token_call(
+        &self,
+        issue_cost: Self::BigUint,
+        lp_token_display_name: &BoxedBytes,
+        lp_token_ticker: &BoxedBytes,
+    ) -> ContractCall<Self::SendApi, ()> {
+        ESDTSystemSmartContractProxy::new_proxy_obj(self.send()).issue_fungible(
+            issue_cost,
+            lp_token_display_name,
+            lp_token_ticker,
+            &Self::BigUint::from(LP_TOKEN_INITIAL_SUPPLY),
+            FungibleTokenProperties {
+                num_decimals: LP_TOKEN_DECIMALS,
+                can_freeze: true,
+                can_wipe: true,
+                can_pause: true,
+                can_mint: true,
+                can_burn: true,
+                can_change_owner: true,
+                can_upgrade: true,
+                can_add_special_roles: true,
+            },
+        )
+    }
+
+    fn set_lp_local_roles_call(
+        &self,
+        pair_address: &Address,
+        lp_token_id: &TokenIdentifier,
+    ) -> ContractCall<Self::SendApi, ()> {
+        ESDTSystemSmartContractProxy::new_proxy_obj(self.send()).set_special_roles(
+            pair_address,
+            lp_token_id,
+            &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+        )
     }
 
     #[callback]
@@ -117,6 +166,8 @@ pub trait LpTokensModule:
             AsyncCallResult::Ok(()) => {
                 self.pair_temporary_owner().remove(address);
                 self.set_lp_token_for_pair(address, &token_id);
+                self.update_pair_info(address, |info| info.lp_token_id = token_id.clone());
+                self.emit_issue_lp_token_event(caller.clone(), address.clone(), token_id);
             }
             AsyncCallResult::Err(message) => {
                 self.last_error_message().set(&message.err_msg);
@@ -129,10 +180,23 @@ pub trait LpTokensModule:
     }
 
     #[callback]
-    fn change_roles_callback(&self, #[call_result] result: AsyncCallResult<()>) {
+    fn change_roles_callback(
+        &self,
+        caller: &Address,
+        address: &Address,
+        token_id: &TokenIdentifier,
+        roles: Vec<EsdtLocalRole>,
+        #[call_result] result: AsyncCallResult<()>,
+    ) {
         match result {
             AsyncCallResult::Ok(()) => {
                 self.last_error_message().clear();
+                self.emit_set_local_roles_event(
+                    caller.clone(),
+                    address.clone(),
+                    token_id.clone(),
+                    roles,
+                );
             }
             AsyncCallResult::Err(message) => {
                 self.last_error_message().set(&message.err_msg);
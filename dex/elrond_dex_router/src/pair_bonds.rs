@@ -0,0 +1,281 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+use permissions::Role;
+
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
+use super::state;
+
+mod pair_liquidity_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait PairLiquidity {
+        #[view(getTotalSupply)]
+        fn get_total_lp_token_supply(&self) -> Self::BigUint;
+    }
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PairBond<BigUint: BigUintApi> {
+    pub creator: Address,
+    pub token_id: TokenIdentifier,
+    pub amount: BigUint,
+    pub deadline_epoch: u64,
+    pub liquidity_since_epoch: Option<u64>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PairBondConfig<BigUint: BigUintApi> {
+    pub token_id: TokenIdentifier,
+    pub amount: BigUint,
+    pub deadline_epochs: u64,
+    pub min_liquidity: BigUint,
+    pub min_liquidity_epochs: u64,
+    pub treasury: Address,
+}
+
+#[elrond_wasm::module]
+pub trait PairBondsModule:
+    state::StateModule
+    + factory::FactoryModule
+    + pair_manager::PairManagerModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + pool_factory::PoolFactoryModule
+    + pair_registry::PairRegistryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + permissions::PermissionsModule
+{
+    #[proxy]
+    fn pair_liquidity_proxy(&self, to: Address) -> pair_liquidity_proxy::Proxy<Self::SendApi>;
+
+    #[endpoint(setPairBondConfig)]
+    fn set_pair_bond_config(
+        &self,
+        token_id: TokenIdentifier,
+        amount: Self::BigUint,
+        deadline_epochs: u64,
+        min_liquidity: Self::BigUint,
+        min_liquidity_epochs: u64,
+        treasury: Address,
+    ) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        require!(
+            token_id.is_egld() || token_id.is_valid_esdt_identifier(),
+            "Bad bond token"
+        );
+        require!(amount > 0, "Bond amount cannot be zero");
+        require!(deadline_epochs > 0, "Deadline cannot be zero");
+        require!(
+            min_liquidity_epochs <= deadline_epochs,
+            "Liquidity epochs exceed deadline"
+        );
+
+        let old_config = self.get_pair_bond_config();
+        self.pair_bond_token_id().set(&token_id);
+        self.pair_bond_amount().set(&amount);
+        self.pair_bond_deadline_epochs().set(&deadline_epochs);
+        self.pair_bond_min_liquidity().set(&min_liquidity);
+        self.pair_bond_min_liquidity_epochs()
+            .set(&min_liquidity_epochs);
+        self.pair_bond_treasury().set(&treasury);
+
+        self.emit_pair_bond_config_change_event(old_config, self.get_pair_bond_config());
+        Ok(())
+    }
+
+    fn get_pair_bond_config(&self) -> PairBondConfig<Self::BigUint> {
+        PairBondConfig {
+            token_id: self.pair_bond_token_id().get(),
+            amount: self.pair_bond_amount().get(),
+            deadline_epochs: self.pair_bond_deadline_epochs().get(),
+            min_liquidity: self.pair_bond_min_liquidity().get(),
+            min_liquidity_epochs: self.pair_bond_min_liquidity_epochs().get(),
+            treasury: self.pair_bond_treasury().get(),
+        }
+    }
+
+    fn require_valid_bond_payment(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: &Self::BigUint,
+    ) -> SCResult<()> {
+        let bond_amount = self.pair_bond_amount().get();
+        require!(bond_amount > 0, "Bonded pair creation is disabled");
+        require!(
+            token_id == &self.pair_bond_token_id().get(),
+            "Bad bond token"
+        );
+        require!(amount == &bond_amount, "Bad bond amount");
+        Ok(())
+    }
+
+    fn lock_pair_bond(
+        &self,
+        pair_address: &Address,
+        token_id: TokenIdentifier,
+        amount: Self::BigUint,
+    ) {
+        let deadline_epoch =
+            self.blockchain().get_block_epoch() + self.pair_bond_deadline_epochs().get();
+        self.pair_bonds().insert(
+            pair_address.clone(),
+            PairBond {
+                creator: self.blockchain().get_caller(),
+                token_id,
+                amount,
+                deadline_epoch,
+                liquidity_since_epoch: None,
+            },
+        );
+    }
+
+    // Records whether the pair is above the liquidity threshold in the current epoch. A check
+    // below the threshold resets the streak, so anyone watching the pair can catch liquidity
+    // that was only added around the checks.
+    #[endpoint(checkPairBondLiquidity)]
+    fn check_pair_bond_liquidity(&self, pair_address: Address) -> SCResult<Option<u64>> {
+        let mut bond = self.get_pair_bond_or_err(&pair_address)?;
+        let epoch = self.blockchain().get_block_epoch();
+        require!(epoch <= bond.deadline_epoch, "Bond deadline passed");
+
+        if self.get_pair_lp_supply(&pair_address) >= self.pair_bond_min_liquidity().get() {
+            if bond.liquidity_since_epoch.is_none() {
+                bond.liquidity_since_epoch = Some(epoch);
+            }
+        } else {
+            bond.liquidity_since_epoch = None;
+        }
+
+        let liquidity_since_epoch = bond.liquidity_since_epoch;
+        self.pair_bonds().insert(pair_address, bond);
+        Ok(liquidity_since_epoch)
+    }
+
+    #[endpoint(releasePairBond)]
+    fn release_pair_bond(&self, pair_address: Address) -> SCResult<()> {
+        let bond = self.get_pair_bond_or_err(&pair_address)?;
+        let epoch = self.blockchain().get_block_epoch();
+        require!(epoch <= bond.deadline_epoch, "Bond deadline passed");
+        require!(
+            self.get_pair_lp_supply(&pair_address) >= self.pair_bond_min_liquidity().get(),
+            "Pair liquidity below threshold"
+        );
+        let held_enough = match bond.liquidity_since_epoch {
+            Some(since_epoch) => epoch >= since_epoch + self.pair_bond_min_liquidity_epochs().get(),
+            None => false,
+        };
+        require!(held_enough, "Liquidity not held long enough");
+
+        self.pair_bonds().remove(&pair_address);
+        let destination = bond.creator.clone();
+        self.send_bond(&bond, &destination)?;
+
+        self.emit_release_pair_bond_event(pair_address, bond, destination);
+        Ok(())
+    }
+
+    // A bond that was not released by its deadline is slashed and the pair goes into sunset.
+    #[endpoint(slashPairBond)]
+    fn slash_pair_bond(&self, pair_address: Address) -> SCResult<()> {
+        let bond = self.get_pair_bond_or_err(&pair_address)?;
+        require!(
+            self.blockchain().get_block_epoch() > bond.deadline_epoch,
+            "Bond deadline not passed"
+        );
+
+        self.pair_bonds().remove(&pair_address);
+        let destination = self.pair_bond_treasury().get();
+        self.send_bond(&bond, &destination)?;
+
+        let pair_id = self.pair_id_by_address(&pair_address).get();
+        if pair_id == 0 || self.pair_info(pair_id).get().state != PairState::Sunset {
+            self.change_pair_state(pair_address.clone(), PairState::Sunset);
+        }
+
+        self.emit_slash_pair_bond_event(pair_address, bond, destination);
+        Ok(())
+    }
+
+    fn get_pair_bond_or_err(&self, pair_address: &Address) -> SCResult<PairBond<Self::BigUint>> {
+        let bond = self.pair_bonds().get(pair_address);
+        require!(bond.is_some(), "No bond for pair");
+        Ok(bond.unwrap())
+    }
+
+    fn get_pair_lp_supply(&self, pair_address: &Address) -> Self::BigUint {
+        self.pair_liquidity_proxy(pair_address.clone())
+            .get_total_lp_token_supply()
+            .execute_on_dest_context()
+    }
+
+    fn send_bond(&self, bond: &PairBond<Self::BigUint>, destination: &Address) -> SCResult<()> {
+        if bond.token_id.is_egld() {
+            self.send().direct_egld(destination, &bond.amount, &[]);
+            Ok(())
+        } else {
+            self.send_fft_tokens(
+                &bond.token_id,
+                &bond.amount,
+                destination,
+                &OptionalArg::None,
+            )
+        }
+    }
+
+    #[view(getPendingPairBonds)]
+    fn get_pending_pair_bonds(
+        &self,
+    ) -> MultiResultVec<MultiResult2<Address, PairBond<Self::BigUint>>> {
+        let bonds: Vec<MultiResult2<Address, PairBond<Self::BigUint>>> = self
+            .pair_bonds()
+            .iter()
+            .map(|(pair_address, bond)| (pair_address, bond).into())
+            .collect();
+        MultiResultVec::from_iter(bonds)
+    }
+
+    #[view(getPairBond)]
+    fn get_pair_bond(&self, pair_address: Address) -> Option<PairBond<Self::BigUint>> {
+        self.pair_bonds().get(&pair_address)
+    }
+
+    #[storage_mapper("pair_bonds")]
+    fn pair_bonds(&self) -> SafeMapMapper<Self::Storage, Address, PairBond<Self::BigUint>>;
+
+    #[view(getPairBondTokenId)]
+    #[storage_mapper("pair_bond_token_id")]
+    fn pair_bond_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[view(getPairBondAmount)]
+    #[storage_mapper("pair_bond_amount")]
+    fn pair_bond_amount(&self) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+
+    #[view(getPairBondDeadlineEpochs)]
+    #[storage_mapper("pair_bond_deadline_epochs")]
+    fn pair_bond_deadline_epochs(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getPairBondMinLiquidity)]
+    #[storage_mapper("pair_bond_min_liquidity")]
+    fn pair_bond_min_liquidity(&self) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+
+    #[view(getPairBondMinLiquidityEpochs)]
+    #[storage_mapper("pair_bond_min_liquidity_epochs")]
+    fn pair_bond_min_liquidity_epochs(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getPairBondTreasury)]
+    #[storage_mapper("pair_bond_treasury")]
+    fn pair_bond_treasury(&self) -> SingleValueMapper<Self::Storage, Address>;
+}
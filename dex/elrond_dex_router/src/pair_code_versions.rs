@@ -0,0 +1,145 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+
+use super::events;
+use super::state;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi)]
+pub struct PairCodeVersion {
+    pub version: u64,
+    pub code_hash: H256,
+    pub label: BoxedBytes,
+    pub created_block: u64,
+}
+
+#[elrond_wasm::module]
+pub trait PairCodeVersionsModule:
+    state::StateModule + events::EventsModule + permissions::PermissionsModule
+{
+    // Code under construction is written to the chunks of the next version ID.
+    fn pending_pair_code_version(&self) -> u64 {
+        self.pair_code_version_count().get() + 1
+    }
+
+    fn clear_pending_pair_code(&self) {
+        self.pair_code_chunks(self.pending_pair_code_version())
+            .clear();
+    }
+
+    fn append_pending_pair_code(&self, part: &BoxedBytes) {
+        self.pair_code_chunks(self.pending_pair_code_version())
+            .push(part);
+    }
+
+    fn save_pending_pair_code_version(
+        &self,
+        expected_hash: &H256,
+        label: BoxedBytes,
+    ) -> SCResult<u64> {
+        let version = self.pending_pair_code_version();
+        let code = self.get_pair_code_version_code(version);
+        require!(!code.is_empty(), "Empty pair code");
+
+        let code_hash = self.crypto().keccak256(code.as_slice());
+        require!(&code_hash == expected_hash, "Pair code hash mismatch");
+
+        let existing_version = self.pair_code_version_by_hash(&code_hash).get();
+        if existing_version != 0 {
+            self.clear_pending_pair_code();
+            return Ok(existing_version);
+        }
+
+        if !label.is_empty() {
+            require!(
+                self.pair_code_version_by_label(&label).is_empty(),
+                "Label already used"
+            );
+        }
+
+        self.pair_code_version_count().set(&version);
+        self.pair_code_version_by_hash(&code_hash).set(&version);
+        if !label.is_empty() {
+            self.pair_code_version_by_label(&label).set(&version);
+        }
+        self.pair_code_version_info(version).set(&PairCodeVersion {
+            version,
+            code_hash,
+            label,
+            created_block: self.blockchain().get_block_nonce(),
+        });
+        Ok(version)
+    }
+
+    fn get_pair_code_version_code(&self, version: u64) -> BoxedBytes {
+        let chunks_mapper = self.pair_code_chunks(version);
+        let chunks: Vec<BoxedBytes> = (1..=chunks_mapper.len())
+            .map(|index| chunks_mapper.get(index))
+            .collect();
+        let slices: Vec<&[u8]> = chunks.iter().map(|chunk| chunk.as_slice()).collect();
+        BoxedBytes::from_concat(slices.as_slice())
+    }
+
+    fn require_pair_code_version_exists(&self, version: u64) -> SCResult<()> {
+        require!(
+            version != 0 && version <= self.pair_code_version_count().get(),
+            "Unknown pair code version"
+        );
+        Ok(())
+    }
+
+    fn update_current_pair_code_version(&self, version: u64) {
+        let old_version = self.current_pair_code_version().get();
+        self.current_pair_code_version().set(&version);
+        self.emit_config_change_event(b"current_pair_code_version", old_version, version);
+    }
+
+    #[view(getPairCodeVersions)]
+    fn get_pair_code_versions(&self) -> MultiResultVec<PairCodeVersion> {
+        let versions: Vec<PairCodeVersion> = (1..=self.pair_code_version_count().get())
+            .map(|version| self.pair_code_version_info(version).get())
+            .collect();
+        MultiResultVec::from_iter(versions)
+    }
+
+    #[view(getPairCodeVersion)]
+    fn get_pair_code_version(&self, version: u64) -> SCResult<PairCodeVersion> {
+        self.require_pair_code_version_exists(version)?;
+
+        Ok(self.pair_code_version_info(version).get())
+    }
+
+    #[view(getPairCodeVersionCount)]
+    #[storage_mapper("pair_code_version_count")]
+    fn pair_code_version_count(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getCurrentPairCodeVersion)]
+    #[storage_mapper("current_pair_code_version")]
+    fn current_pair_code_version(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getPairCodeVersionByHash)]
+    #[storage_mapper("pair_code_version_by_hash")]
+    fn pair_code_version_by_hash(&self, code_hash: &H256) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getPairCodeVersionByLabel)]
+    #[storage_mapper("pair_code_version_by_label")]
+    fn pair_code_version_by_label(
+        &self,
+        label: &BoxedBytes,
+    ) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getPairCodeVersionOf)]
+    #[storage_mapper("pair_code_version_of")]
+    fn pair_code_version_of(&self, pair_address: &Address)
+        -> SingleValueMapper<Self::Storage, u64>;
+
+    #[storage_mapper("pair_code_version_info")]
+    fn pair_code_version_info(
+        &self,
+        version: u64,
+    ) -> SingleValueMapper<Self::Storage, PairCodeVersion>;
+
+    #[storage_mapper("pair_code_chunks")]
+    fn pair_code_chunks(&self, version: u64) -> VecMapper<Self::Storage, BoxedBytes>;
+}
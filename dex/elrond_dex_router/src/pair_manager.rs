@@ -1,9 +1,19 @@
+#![allow(clippy::too_many_arguments)]
+
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
 use common_structs::{FftTokenAmountPair, Nonce};
+use elrond_dex_pair::config::State as PairState;
+use permissions::Role;
 
+use super::egld_wrapper;
+use super::events;
 use super::factory;
+use super::pair_code_versions;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
 use super::state;
 
 type SwapOperationType<BigUint> = MultiArg4<Address, BoxedBytes, TokenIdentifier, BigUint>;
@@ -12,46 +22,146 @@ const ACCEPT_PAY_FUNC_NAME: &[u8] = b"acceptPay";
 const SWAP_TOKENS_FIXED_INPUT_FUNC_NAME: &[u8] = b"swapTokensFixedInput";
 const SWAP_TOKENS_FIXED_OUTPUT_FUNC_NAME: &[u8] = b"swapTokensFixedOutput";
 
+use super::pair_registry::pair_info_proxy::ProxyTrait as _;
 use elrond_dex_pair::config::ProxyTrait as _;
 use elrond_dex_pair::fee::ProxyTrait as _;
+use elrond_dex_pair::migration::ProxyTrait as _;
 
 #[elrond_wasm::module]
 pub trait PairManagerModule:
-    state::StateModule + factory::FactoryModule + token_send::TokenSendModule
+    state::StateModule
+    + egld_wrapper::EgldWrapperModule
+    + factory::FactoryModule
+    + pool_factory::PoolFactoryModule
+    + referral::ReferralModule
+    + events::EventsModule
+    + token_send::TokenSendModule
+    + pair_registry::PairRegistryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + permissions::PermissionsModule
 {
-    #[only_owner]
-    #[endpoint(setFeeOn)]
-    fn set_fee_on(
+    fn set_pair_fee(
         &self,
         pair_address: Address,
+        enabled: bool,
         fee_to_address: Address,
         fee_token: TokenIdentifier,
+    ) {
+        let old_fee_token = self
+            .pair_contract_proxy(pair_address.clone())
+            .get_fee_destination_token(fee_to_address.clone())
+            .execute_on_dest_context();
+        self.pair_contract_proxy(pair_address.clone())
+            .set_fee_on(enabled, fee_to_address.clone(), fee_token.clone())
+            .execute_on_dest_context();
+
+        let new_fee_token = if enabled { Some(fee_token) } else { None };
+        self.emit_pair_fee_change_event(pair_address, fee_to_address, old_fee_token, new_fee_token);
+    }
+
+    fn set_pair_whitelist(&self, pair_address: Address, address: Address, whitelisted: bool) {
+        let old_whitelisted = self
+            .pair_contract_proxy(pair_address.clone())
+            .is_whitelisted(address.clone())
+            .execute_on_dest_context();
+        if whitelisted {
+            self.pair_contract_proxy(pair_address.clone())
+                .whitelist_endpoint(address.clone())
+                .execute_on_dest_context();
+        } else {
+            self.pair_contract_proxy(pair_address.clone())
+                .remove_whitelist(address.clone())
+                .execute_on_dest_context();
+        }
+        self.emit_pair_whitelist_change_event(pair_address, address, old_whitelisted, whitelisted);
+    }
+
+    // Moves the pair to the given state and keeps the registry in sync.
+    fn change_pair_state(&self, pair_address: Address, new_state: PairState) {
+        let old_state = self
+            .pair_info_proxy(pair_address.clone())
+            .get_state()
+            .execute_on_dest_context();
+        match new_state {
+            PairState::Inactive => self.pause_pair(pair_address.clone()),
+            PairState::Active => self.resume_pair(pair_address.clone()),
+            PairState::ActiveNoSwaps => self.set_pair_active_no_swaps(pair_address.clone()),
+            PairState::ExitOnly => self.set_pair_exit_only(pair_address.clone()),
+            PairState::Sunset => {
+                self.pair_contract_proxy(pair_address.clone())
+                    .start_sunset()
+                    .execute_on_dest_context();
+            }
+        }
+        self.update_pair_info(&pair_address, |info| info.state = new_state);
+        self.emit_pair_state_change_event(pair_address, old_state, new_state);
+    }
+
+    #[endpoint(openPairMigration)]
+    fn open_pair_migration(
+        &self,
+        pair_address: Address,
+        destination_address: Address,
     ) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
         require!(self.is_active(), "Not active");
+        require!(pair_address != destination_address, "Same pair");
         self.check_is_pair_sc(&pair_address)?;
+        self.check_is_pair_sc(&destination_address)?;
+        require!(
+            !self.is_pool_sc(&pair_address) && !self.is_pool_sc(&destination_address),
+            "Pools cannot migrate liquidity"
+        );
 
-        self.pair_contract_proxy(pair_address)
-            .set_fee_on(true, fee_to_address, fee_token)
+        self.pair_contract_proxy(pair_address.clone())
+            .open_migration(destination_address.clone())
             .execute_on_dest_context();
 
+        self.emit_pair_migration_event(pair_address, destination_address, true);
+        Ok(())
+    }
+
+    #[endpoint(closePairMigration)]
+    fn close_pair_migration(&self, pair_address: Address) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        require!(self.is_active(), "Not active");
+        self.check_is_pair_sc(&pair_address)?;
+
+        self.pair_contract_proxy(pair_address.clone())
+            .close_migration()
+            .execute_on_dest_context();
+
+        self.emit_pair_migration_event(pair_address, Address::zero(), false);
+        Ok(())
+    }
+
+    #[endpoint(startPairSunset)]
+    fn start_pair_sunset(&self, pair_address: Address) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        require!(self.is_active(), "Not active");
+        self.check_is_pair_sc(&pair_address)?;
+
+        self.change_pair_state(pair_address, PairState::Sunset);
+
         Ok(())
     }
 
-    #[only_owner]
-    #[endpoint(setFeeOff)]
-    fn set_fee_off(
+    #[endpoint(finalizePairSunset)]
+    fn finalize_pair_sunset(
         &self,
         pair_address: Address,
-        fee_to_address: Address,
-        fee_token: TokenIdentifier,
+        treasury_address: Address,
     ) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
         require!(self.is_active(), "Not active");
         self.check_is_pair_sc(&pair_address)?;
 
-        self.pair_contract_proxy(pair_address)
-            .set_fee_on(false, fee_to_address, fee_token)
+        self.pair_contract_proxy(pair_address.clone())
+            .finalize_sunset(treasury_address.clone())
             .execute_on_dest_context();
+        self.sunset_pairs().insert(pair_address.clone());
 
+        self.emit_finalize_pair_sunset_event(pair_address, treasury_address);
         Ok(())
     }
 
@@ -68,6 +178,76 @@ pub trait PairManagerModule:
         #[payment_nonce] nonce: Nonce,
         swap_operations: MultiArgVec<SwapOperationType<Self::BigUint>>,
         #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        self.perform_multi_pair_swap(
+            token_id,
+            amount,
+            nonce,
+            swap_operations,
+            opt_accept_funds_func,
+            None,
+            false,
+        )
+    }
+
+    // Same as multiPairSwap, but wrapped EGLD sent back to the caller is unwrapped first.
+    #[payable("*")]
+    #[endpoint(multiPairSwapToEgld)]
+    fn multi_pair_swap_to_egld(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: Self::BigUint,
+        #[payment_nonce] nonce: Nonce,
+        swap_operations: MultiArgVec<SwapOperationType<Self::BigUint>>,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        self.get_egld_wrapper_config()?;
+        self.perform_multi_pair_swap(
+            token_id,
+            amount,
+            nonce,
+            swap_operations,
+            opt_accept_funds_func,
+            None,
+            true,
+        )
+    }
+
+    #[payable("*")]
+    #[endpoint(multiPairSwapWithReferrer)]
+    fn multi_pair_swap_with_referrer(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: Self::BigUint,
+        #[payment_nonce] nonce: Nonce,
+        referrer: Address,
+        swap_operations: MultiArgVec<SwapOperationType<Self::BigUint>>,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        require!(
+            referrer != self.blockchain().get_caller(),
+            "Cannot refer yourself"
+        );
+        self.perform_multi_pair_swap(
+            token_id,
+            amount,
+            nonce,
+            swap_operations,
+            opt_accept_funds_func,
+            Some(referrer),
+            false,
+        )
+    }
+
+    fn perform_multi_pair_swap(
+        &self,
+        token_id: TokenIdentifier,
+        amount: Self::BigUint,
+        nonce: Nonce,
+        swap_operations: MultiArgVec<SwapOperationType<Self::BigUint>>,
+        opt_accept_funds_func: OptionalArg<BoxedBytes>,
+        opt_referrer: Option<Address>,
+        unwrap_output: bool,
     ) -> SCResult<()> {
         require!(nonce == 0, "Invalid nonce. Should be zero");
         require!(amount > 0, "Invalid amount. Should not be zero");
@@ -76,33 +256,69 @@ pub trait PairManagerModule:
             "Invalid swap operations chain. Should not be empty"
         );
 
+        // What is left of an EGLD payment goes back as EGLD.
+        let unwrap_residuum = unwrap_output || token_id.is_egld();
+        let token_id = self.wrap_egld_payment(token_id, &amount)?;
+
+        // Weighted and concentrated pools do not implement the referrer swap endpoints.
+        let swap_operations = swap_operations.into_vec();
+        if opt_referrer.is_some() {
+            for entry in swap_operations.iter() {
+                let (pair_address, _, _, _) = &entry.0;
+                require!(
+                    !self.is_pool_sc(pair_address),
+                    "Referrer swaps are not supported by pools"
+                );
+            }
+        }
+
         let caller = self.blockchain().get_caller();
         let mut residuum_vec = Vec::new();
         let mut last_received_token_id = token_id;
         let mut last_received_amount = amount;
 
-        for entry in swap_operations.into_vec() {
+        for entry in swap_operations.into_iter() {
             let (pair_address, function, token_wanted, amount_wanted) = entry.into_tuple();
             self.check_is_pair_sc(&pair_address)?;
 
             if function == BoxedBytes::from(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME) {
-                let token_amount_out = self.actual_swap_fixed_input(
-                    pair_address,
-                    last_received_token_id,
-                    last_received_amount,
-                    token_wanted,
-                    amount_wanted,
-                );
+                let token_amount_out = match opt_referrer.clone() {
+                    Some(referrer) => self.actual_swap_fixed_input_with_referrer(
+                        pair_address,
+                        last_received_token_id,
+                        last_received_amount,
+                        token_wanted,
+                        amount_wanted,
+                        referrer,
+                    ),
+                    None => self.actual_swap_fixed_input(
+                        pair_address,
+                        last_received_token_id,
+                        last_received_amount,
+                        token_wanted,
+                        amount_wanted,
+                    ),
+                };
                 last_received_token_id = token_amount_out.token_id;
                 last_received_amount = token_amount_out.amount;
             } else if function == BoxedBytes::from(SWAP_TOKENS_FIXED_OUTPUT_FUNC_NAME) {
-                let (token_amount_out, residuum) = self.actual_swap_fixed_output(
-                    pair_address,
-                    last_received_token_id,
-                    last_received_amount,
-                    token_wanted,
-                    amount_wanted,
-                );
+                let (token_amount_out, residuum) = match opt_referrer.clone() {
+                    Some(referrer) => self.actual_swap_fixed_output_with_referrer(
+                        pair_address,
+                        last_received_token_id,
+                        last_received_amount,
+                        token_wanted,
+                        amount_wanted,
+                        referrer,
+                    ),
+                    None => self.actual_swap_fixed_output(
+                        pair_address,
+                        last_received_token_id,
+                        last_received_amount,
+                        token_wanted,
+                        amount_wanted,
+                    ),
+                };
                 last_received_token_id = token_amount_out.token_id;
                 last_received_amount = token_amount_out.amount;
                 residuum_vec.push(residuum);
@@ -113,19 +329,21 @@ pub trait PairManagerModule:
 
         while !residuum_vec.is_empty() {
             let residuum = residuum_vec.pop().unwrap_or_default();
-            self.send_fft_tokens(
+            self.send_tokens_or_unwrapped_egld(
                 &residuum.token_id,
                 &residuum.amount,
                 &caller,
                 &opt_accept_funds_func,
+                unwrap_residuum,
             )?;
         }
 
-        self.send_fft_tokens(
+        self.send_tokens_or_unwrapped_egld(
             &last_received_token_id,
             &last_received_amount,
             &caller,
             &opt_accept_funds_func,
+            unwrap_output,
         )?;
 
         Ok(())
@@ -173,6 +391,58 @@ pub trait PairManagerModule:
             .into_tuple()
     }
 
+    fn actual_swap_fixed_input_with_referrer(
+        &self,
+        pair_address: Address,
+        token_in: TokenIdentifier,
+        amount_in: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        referrer: Address,
+    ) -> FftTokenAmountPair<Self::BigUint> {
+        let referral_fee_cap = self.referral_fee_cap(&token_in).get();
+        self.pair_contract_proxy(pair_address)
+            .swap_tokens_fixed_input_with_referrer(
+                token_in,
+                amount_in,
+                token_out,
+                amount_out_min,
+                referrer,
+                self.referral_fee_percent().get(),
+                referral_fee_cap,
+                OptionalArg::Some(BoxedBytes::from(ACCEPT_PAY_FUNC_NAME)),
+            )
+            .execute_on_dest_context_custom_range(|_, after| (after - 1, after))
+    }
+
+    fn actual_swap_fixed_output_with_referrer(
+        &self,
+        pair_address: Address,
+        token_in: TokenIdentifier,
+        amount_in_max: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out: Self::BigUint,
+        referrer: Address,
+    ) -> (
+        FftTokenAmountPair<Self::BigUint>,
+        FftTokenAmountPair<Self::BigUint>,
+    ) {
+        let referral_fee_cap = self.referral_fee_cap(&token_in).get();
+        self.pair_contract_proxy(pair_address)
+            .swap_tokens_fixed_output_with_referrer(
+                token_in,
+                amount_in_max,
+                token_out,
+                amount_out,
+                referrer,
+                self.referral_fee_percent().get(),
+                referral_fee_cap,
+                OptionalArg::Some(BoxedBytes::from(ACCEPT_PAY_FUNC_NAME)),
+            )
+            .execute_on_dest_context_custom_range(|_, after| (after - 2, after))
+            .into_tuple()
+    }
+
     fn pause_pair(&self, address: Address) {
         self.pair_contract_proxy(address)
             .pause()
@@ -185,6 +455,18 @@ pub trait PairManagerModule:
             .execute_on_dest_context();
     }
 
+    fn set_pair_active_no_swaps(&self, address: Address) {
+        self.pair_contract_proxy(address)
+            .set_state_active_no_swaps()
+            .execute_on_dest_context();
+    }
+
+    fn set_pair_exit_only(&self, address: Address) {
+        self.pair_contract_proxy(address)
+            .set_state_exit_only()
+            .execute_on_dest_context();
+    }
+
     fn get_lp_token_for_pair(&self, address: &Address) -> TokenIdentifier {
         self.pair_contract_proxy(address.clone())
             .get_lp_token_identifier()
@@ -0,0 +1,271 @@
+#![allow(clippy::too_many_arguments)]
+
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+
+use super::events;
+use super::fee_policy::FeePercents;
+use super::state;
+
+pub const MAX_PAGE_SIZE: usize = 100;
+
+pub mod pair_info_proxy {
+    elrond_wasm::imports!();
+
+    use elrond_dex_pair::config::State;
+
+    #[elrond_wasm::proxy]
+    pub trait PairInfo {
+        #[view(getState)]
+        fn get_state(&self) -> State;
+
+        #[view(getLpTokenIdentifier)]
+        fn get_lp_token_identifier(&self) -> TokenIdentifier;
+
+        #[view(getTotalFeePercent)]
+        fn get_total_fee_percent(&self) -> u64;
+
+        #[view(getSpecialFee)]
+        fn get_special_fee_percent(&self) -> u64;
+    }
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi)]
+pub struct PairInfo {
+    pub pair_id: u64,
+    pub address: Address,
+    pub first_token_id: TokenIdentifier,
+    pub second_token_id: TokenIdentifier,
+    pub creator: Address,
+    pub creation_block: u64,
+    pub lp_token_id: TokenIdentifier,
+    pub total_fee_percent: u64,
+    pub special_fee_percent: u64,
+    pub state: PairState,
+}
+
+#[elrond_wasm::module]
+pub trait PairRegistryModule:
+    state::StateModule + events::EventsModule + permissions::PermissionsModule
+{
+    #[proxy]
+    fn pair_info_proxy(&self, to: Address) -> pair_info_proxy::Proxy<Self::SendApi>;
+
+    fn register_pair(
+        &self,
+        address: &Address,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+        creator: &Address,
+        creation_block: u64,
+        lp_token_id: TokenIdentifier,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+        state: PairState,
+    ) -> u64 {
+        let pair_id = self.pair_count().get() + 1;
+        self.pair_count().set(&pair_id);
+
+        self.pair_info(pair_id).set(&PairInfo {
+            pair_id,
+            address: address.clone(),
+            first_token_id: first_token_id.clone(),
+            second_token_id: second_token_id.clone(),
+            creator: creator.clone(),
+            creation_block,
+            lp_token_id,
+            total_fee_percent,
+            special_fee_percent,
+            state,
+        });
+        self.pair_id_by_address(address).set(&pair_id);
+        self.pair_id_by_tokens(&self.get_sorted_token_pair(first_token_id, second_token_id))
+            .set(&pair_id);
+        self.token_pair_ids(first_token_id).push(&pair_id);
+        self.token_pair_ids(second_token_id).push(&pair_id);
+        pair_id
+    }
+
+    fn update_pair_info<F: FnOnce(&mut PairInfo)>(&self, address: &Address, f: F) {
+        let pair_id = self.pair_id_by_address(address).get();
+        if pair_id != 0 {
+            self.pair_info(pair_id).update(f);
+        }
+    }
+
+    fn get_registered_pair(
+        &self,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+    ) -> Option<Address> {
+        let pair_id = self
+            .pair_id_by_tokens(&self.get_sorted_token_pair(first_token_id, second_token_id))
+            .get();
+        if pair_id == 0 {
+            None
+        } else {
+            Some(self.pair_info(pair_id).get().address)
+        }
+    }
+
+    #[inline]
+    fn is_registered_pair(&self, address: &Address) -> bool {
+        self.pair_id_by_address(address).get() != 0
+    }
+
+    // Archived pairs keep their ID and address lookup, but are left out of every listing.
+    #[inline]
+    fn is_archived_pair(&self, address: &Address) -> bool {
+        self.archived_pair_index(address).get() != 0
+    }
+
+    fn remove_token_pair_id(&self, token_id: &TokenIdentifier, pair_id: u64) {
+        let mut pair_ids = self.token_pair_ids(token_id);
+        let remaining: Vec<u64> = (1..=pair_ids.len())
+            .map(|index| pair_ids.get(index))
+            .filter(|id| *id != pair_id)
+            .collect();
+        pair_ids.clear();
+        for id in remaining.iter() {
+            pair_ids.push(id);
+        }
+    }
+
+    fn get_sorted_token_pair(
+        &self,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+    ) -> (TokenIdentifier, TokenIdentifier) {
+        if first_token_id.as_esdt_identifier() <= second_token_id.as_esdt_identifier() {
+            (first_token_id.clone(), second_token_id.clone())
+        } else {
+            (second_token_id.clone(), first_token_id.clone())
+        }
+    }
+
+    fn get_pair_fee_percents(&self, pair_address: &Address) -> FeePercents {
+        FeePercents {
+            total_fee_percent: self
+                .pair_info_proxy(pair_address.clone())
+                .get_total_fee_percent()
+                .execute_on_dest_context(),
+            special_fee_percent: self
+                .pair_info_proxy(pair_address.clone())
+                .get_special_fee_percent()
+                .execute_on_dest_context(),
+        }
+    }
+
+    #[endpoint(refreshPairInfo)]
+    fn refresh_pair_info(&self, address: Address) -> SCResult<()> {
+        require!(self.is_registered_pair(&address), "Pair not registered");
+
+        let state = self
+            .pair_info_proxy(address.clone())
+            .get_state()
+            .execute_on_dest_context();
+        let lp_token_id = self
+            .pair_info_proxy(address.clone())
+            .get_lp_token_identifier()
+            .execute_on_dest_context();
+        let total_fee_percent = self
+            .pair_info_proxy(address.clone())
+            .get_total_fee_percent()
+            .execute_on_dest_context();
+        let special_fee_percent = self
+            .pair_info_proxy(address.clone())
+            .get_special_fee_percent()
+            .execute_on_dest_context();
+
+        self.update_pair_info(&address, |info| {
+            info.state = state;
+            info.lp_token_id = lp_token_id;
+            info.total_fee_percent = total_fee_percent;
+            info.special_fee_percent = special_fee_percent;
+        });
+        Ok(())
+    }
+
+    #[view(getPairCount)]
+    #[storage_mapper("pair_count")]
+    fn pair_count(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getPairInfo)]
+    fn get_pair_info(&self, address: Address) -> Option<PairInfo> {
+        let pair_id = self.pair_id_by_address(&address).get();
+        if pair_id == 0 {
+            None
+        } else {
+            Some(self.pair_info(pair_id).get())
+        }
+    }
+
+    // Archived pairs are skipped, so a page can hold fewer than max_count pairs.
+    #[view(getPairsPage)]
+    fn get_pairs_page(
+        &self,
+        from_pair_id: u64,
+        max_count: usize,
+    ) -> SCResult<MultiResultVec<PairInfo>> {
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        require!(max_count <= MAX_PAGE_SIZE, "Page too large");
+
+        let pair_count = self.pair_count().get();
+        let pairs: Vec<PairInfo> = (from_pair_id..=pair_count)
+            .take(max_count)
+            .map(|pair_id| self.pair_info(pair_id).get())
+            .filter(|pair_info| !self.is_archived_pair(&pair_info.address))
+            .collect();
+        Ok(MultiResultVec::from_iter(pairs))
+    }
+
+    #[view(getPairsForToken)]
+    fn get_pairs_for_token(
+        &self,
+        token_id: TokenIdentifier,
+        offset: usize,
+        max_count: usize,
+    ) -> SCResult<MultiResultVec<PairInfo>> {
+        require!(max_count <= MAX_PAGE_SIZE, "Page too large");
+
+        let pair_ids = self.token_pair_ids(&token_id);
+        let pairs: Vec<PairInfo> = (offset + 1..=pair_ids.len())
+            .take(max_count)
+            .map(|index| self.pair_info(pair_ids.get(index)).get())
+            .collect();
+        Ok(MultiResultVec::from_iter(pairs))
+    }
+
+    #[view(getPairCountForToken)]
+    fn get_pair_count_for_token(&self, token_id: TokenIdentifier) -> usize {
+        self.token_pair_ids(&token_id).len()
+    }
+
+    #[storage_mapper("pair_info")]
+    fn pair_info(&self, pair_id: u64) -> SingleValueMapper<Self::Storage, PairInfo>;
+
+    #[storage_mapper("pair_id_by_address")]
+    fn pair_id_by_address(&self, address: &Address) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[storage_mapper("pair_id_by_tokens")]
+    fn pair_id_by_tokens(
+        &self,
+        sorted_tokens: &(TokenIdentifier, TokenIdentifier),
+    ) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[storage_mapper("token_pair_ids")]
+    fn token_pair_ids(&self, token_id: &TokenIdentifier) -> VecMapper<Self::Storage, u64>;
+
+    #[storage_mapper("archived_pair_index")]
+    fn archived_pair_index(
+        &self,
+        pair_address: &Address,
+    ) -> SingleValueMapper<Self::Storage, usize>;
+
+    #[view(isPairRegistryBackfilled)]
+    #[storage_mapper("pair_registry_backfilled")]
+    fn pair_registry_backfilled(&self) -> SingleValueMapper<Self::Storage, bool>;
+}
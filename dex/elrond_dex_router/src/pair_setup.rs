@@ -0,0 +1,340 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::{FftTokenAmountPair, Nonce};
+
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::lp_tokens;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
+use super::state;
+use super::token_policy;
+use elrond_dex_pair::ProxyTrait as _;
+
+type SeedPairLiquidityResultType<BigUint> = MultiResult3<
+    FftTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+    FftTokenAmountPair<BigUint>,
+>;
+
+const ACCEPT_PAY_FUNC_NAME: &[u8] = b"acceptPay";
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi, Clone, Copy)]
+pub enum PairSetupStage {
+    IssuingLpToken,
+    SettingLocalRoles,
+    AwaitingLiquidity,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi)]
+pub struct PairSetup<BigUint: BigUintApi> {
+    pub creator: Address,
+    pub stage: PairSetupStage,
+    pub issue_cost: BigUint,
+    pub start_block: u64,
+}
+
+#[elrond_wasm::module]
+pub trait PairSetupModule:
+    lp_tokens::LpTokensModule
+    + egld_wrapper::EgldWrapperModule
+    + pair_manager::PairManagerModule
+    + pair_registry::PairRegistryModule
+    + factory::FactoryModule
+    + state::StateModule
+    + token_send::TokenSendModule
+    + token_policy::TokenPolicyModule
+    + pool_factory::PoolFactoryModule
+    + referral::ReferralModule
+    + events::EventsModule
+    + pair_code_versions::PairCodeVersionsModule
+    + permissions::PermissionsModule
+{
+    fn start_pair_setup(
+        &self,
+        pair_address: Address,
+        lp_token_display_name: BoxedBytes,
+        lp_token_ticker: BoxedBytes,
+        issue_cost: Self::BigUint,
+    ) -> SCResult<AsyncCall<Self::SendApi>> {
+        let caller = self.blockchain().get_caller();
+        self.pair_setup(&pair_address).set(&PairSetup {
+            creator: caller.clone(),
+            stage: PairSetupStage::IssuingLpToken,
+            issue_cost: issue_cost.clone(),
+            start_block: self.blockchain().get_block_nonce(),
+        });
+
+        Ok(self
+            .issue_lp_token_call(issue_cost, &lp_token_display_name, &lp_token_ticker)
+            .async_call()
+            .with_callback(
+                PairSetupModule::callbacks(self).pair_setup_issue_callback(&caller, &pair_address),
+            ))
+    }
+
+    #[payable("*")]
+    #[endpoint(depositSetupLiquidity)]
+    fn deposit_setup_liquidity(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: Self::BigUint,
+        #[payment_nonce] nonce: Nonce,
+        pair_address: Address,
+    ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
+        require!(nonce == 0, "Invalid nonce. Should be zero");
+        require!(amount > 0, "Invalid amount. Should not be zero");
+        self.require_pair_setup_stage(&pair_address, PairSetupStage::AwaitingLiquidity)?;
+        self.require_pair_setup_creator(&pair_address)?;
+
+        let pair_info = self
+            .pair_info(self.pair_id_by_address(&pair_address).get())
+            .get();
+        let token_id = self.wrap_egld_payment(token_id, &amount)?;
+        require!(
+            token_id == pair_info.first_token_id || token_id == pair_info.second_token_id,
+            "Bad token for pair"
+        );
+
+        self.setup_liquidity_deposit(&pair_address, &token_id)
+            .update(|deposit| *deposit += &amount);
+        Ok(())
+    }
+
+    #[endpoint(seedPairLiquidity)]
+    fn seed_pair_liquidity(
+        &self,
+        pair_address: Address,
+        first_token_amount_min: Self::BigUint,
+        second_token_amount_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SeedPairLiquidityResultType<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        self.require_pair_setup_stage(&pair_address, PairSetupStage::AwaitingLiquidity)?;
+        self.require_pair_setup_creator(&pair_address)?;
+
+        let pair_info = self
+            .pair_info(self.pair_id_by_address(&pair_address).get())
+            .get();
+        let first_token_amount = self
+            .setup_liquidity_deposit(&pair_address, &pair_info.first_token_id)
+            .get();
+        let second_token_amount = self
+            .setup_liquidity_deposit(&pair_address, &pair_info.second_token_id)
+            .get();
+        require!(
+            first_token_amount > 0 && second_token_amount > 0,
+            "Both tokens must be deposited"
+        );
+        self.setup_liquidity_deposit(&pair_address, &pair_info.first_token_id)
+            .clear();
+        self.setup_liquidity_deposit(&pair_address, &pair_info.second_token_id)
+            .clear();
+
+        self.pair_contract_proxy(pair_address.clone())
+            .accept_esdt_payment(pair_info.first_token_id.clone(), first_token_amount.clone())
+            .execute_on_dest_context();
+        self.pair_contract_proxy(pair_address.clone())
+            .accept_esdt_payment(
+                pair_info.second_token_id.clone(),
+                second_token_amount.clone(),
+            )
+            .execute_on_dest_context();
+        let result: SeedPairLiquidityResultType<Self::BigUint> = self
+            .pair_contract_proxy(pair_address.clone())
+            .add_liquidity(
+                first_token_amount.clone(),
+                second_token_amount.clone(),
+                first_token_amount_min,
+                second_token_amount_min,
+                OptionalArg::Some(BoxedBytes::from(ACCEPT_PAY_FUNC_NAME)),
+            )
+            .execute_on_dest_context_custom_range(|_, after| (after - 3, after));
+        let (lp_token_amount, first_token_added, second_token_added) = result.0;
+
+        // The pair sends back what it did not use, all of it belongs to the creator.
+        let caller = self.blockchain().get_caller();
+        self.send_fft_tokens(
+            &lp_token_amount.token_id,
+            &lp_token_amount.amount,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+        let first_token_unused = &first_token_amount - &first_token_added.amount;
+        if first_token_unused > 0 {
+            self.send_fft_tokens(
+                &pair_info.first_token_id,
+                &first_token_unused,
+                &caller,
+                &opt_accept_funds_func,
+            )?;
+        }
+        let second_token_unused = &second_token_amount - &second_token_added.amount;
+        if second_token_unused > 0 {
+            self.send_fft_tokens(
+                &pair_info.second_token_id,
+                &second_token_unused,
+                &caller,
+                &opt_accept_funds_func,
+            )?;
+        }
+
+        self.pair_setup(&pair_address)
+            .update(|setup| setup.stage = PairSetupStage::Completed);
+        Ok((lp_token_amount, first_token_added, second_token_added).into())
+    }
+
+    #[endpoint(cancelPairSetup)]
+    fn cancel_pair_setup(
+        &self,
+        pair_address: Address,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        require!(!self.pair_setup(&pair_address).is_empty(), "No pair setup");
+        self.require_pair_setup_creator(&pair_address)?;
+        let stage = self.pair_setup(&pair_address).get().stage;
+        require!(
+            stage == PairSetupStage::AwaitingLiquidity || stage == PairSetupStage::Failed,
+            "Pair setup cannot be cancelled"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let pair_info = self
+            .pair_info(self.pair_id_by_address(&pair_address).get())
+            .get();
+        for token_id in [pair_info.first_token_id, pair_info.second_token_id].iter() {
+            let amount = self.setup_liquidity_deposit(&pair_address, token_id).get();
+            if amount > 0 {
+                self.setup_liquidity_deposit(&pair_address, token_id)
+                    .clear();
+                self.send_fft_tokens(token_id, &amount, &caller, &opt_accept_funds_func)?;
+            }
+        }
+
+        self.pair_setup(&pair_address)
+            .update(|setup| setup.stage = PairSetupStage::Cancelled);
+        Ok(())
+    }
+
+    fn require_pair_setup_stage(
+        &self,
+        pair_address: &Address,
+        stage: PairSetupStage,
+    ) -> SCResult<()> {
+        require!(!self.pair_setup(pair_address).is_empty(), "No pair setup");
+        require!(
+            self.pair_setup(pair_address).get().stage == stage,
+            "Bad pair setup stage"
+        );
+        Ok(())
+    }
+
+    fn require_pair_setup_creator(&self, pair_address: &Address) -> SCResult<()> {
+        require!(
+            self.pair_setup(pair_address).get().creator == self.blockchain().get_caller(),
+            "Not the pair creator"
+        );
+        Ok(())
+    }
+
+    #[callback]
+    fn pair_setup_issue_callback(
+        &self,
+        caller: &Address,
+        address: &Address,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] returned_tokens: Self::BigUint,
+        #[call_result] result: AsyncCallResult<()>,
+    ) -> OptionalResult<AsyncCall<Self::SendApi>> {
+        match result {
+            AsyncCallResult::Ok(()) => {
+                self.pair_temporary_owner().remove(address);
+                self.set_lp_token_for_pair(address, &token_id);
+                self.update_pair_info(address, |info| info.lp_token_id = token_id.clone());
+                self.emit_issue_lp_token_event(caller.clone(), address.clone(), token_id.clone());
+                self.pair_setup(address)
+                    .update(|setup| setup.stage = PairSetupStage::SettingLocalRoles);
+
+                OptionalResult::Some(
+                    self.set_lp_local_roles_call(address, &token_id)
+                        .async_call()
+                        .with_callback(
+                            PairSetupModule::callbacks(self)
+                                .pair_setup_roles_callback(caller, address, &token_id),
+                        ),
+                )
+            }
+            AsyncCallResult::Err(message) => {
+                self.last_error_message().set(&message.err_msg);
+                self.pair_setup(address)
+                    .update(|setup| setup.stage = PairSetupStage::Failed);
+
+                if token_id.is_egld() && returned_tokens > 0 {
+                    self.send().direct_egld(caller, &returned_tokens, &[]);
+                }
+                OptionalResult::None
+            }
+        }
+    }
+
+    #[callback]
+    fn pair_setup_roles_callback(
+        &self,
+        caller: &Address,
+        address: &Address,
+        token_id: &TokenIdentifier,
+        #[call_result] result: AsyncCallResult<()>,
+    ) {
+        match result {
+            AsyncCallResult::Ok(()) => {
+                self.last_error_message().clear();
+                self.emit_set_local_roles_event(
+                    caller.clone(),
+                    address.clone(),
+                    token_id.clone(),
+                    [EsdtLocalRole::Mint, EsdtLocalRole::Burn].to_vec(),
+                );
+                self.pair_setup(address)
+                    .update(|setup| setup.stage = PairSetupStage::AwaitingLiquidity);
+            }
+            AsyncCallResult::Err(message) => {
+                self.last_error_message().set(&message.err_msg);
+                self.pair_setup(address)
+                    .update(|setup| setup.stage = PairSetupStage::Failed);
+            }
+        }
+    }
+
+    #[view(getPairSetupStage)]
+    fn get_pair_setup_stage(&self, pair_address: Address) -> Option<PairSetupStage> {
+        if self.pair_setup(&pair_address).is_empty() {
+            None
+        } else {
+            Some(self.pair_setup(&pair_address).get().stage)
+        }
+    }
+
+    #[view(getPairSetup)]
+    #[storage_mapper("pair_setup")]
+    fn pair_setup(
+        &self,
+        pair_address: &Address,
+    ) -> SingleValueMapper<Self::Storage, PairSetup<Self::BigUint>>;
+
+    #[view(getSetupLiquidityDeposit)]
+    #[storage_mapper("setup_liquidity_deposit")]
+    fn setup_liquidity_deposit(
+        &self,
+        pair_address: &Address,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+}
@@ -0,0 +1,188 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::{FftTokenAmountPair, Nonce};
+use core::iter::FromIterator;
+
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
+use super::route_finder;
+use super::route_finder::pair_quote_proxy::ProxyTrait as _;
+use super::state;
+
+const MAX_PATH_LENGTH: usize = 5;
+
+type MultiPairSwapExactOutResultType<BigUint> =
+    MultiResult2<FftTokenAmountPair<BigUint>, FftTokenAmountPair<BigUint>>;
+
+#[elrond_wasm::module]
+pub trait PathQuoterModule:
+    state::StateModule
+    + factory::FactoryModule
+    + pair_manager::PairManagerModule
+    + route_finder::RouteFinderModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + pool_factory::PoolFactoryModule
+    + pair_registry::PairRegistryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + permissions::PermissionsModule
+{
+    #[view(getAmountsOut)]
+    fn get_amounts_out_view(
+        &self,
+        amount_in: Self::BigUint,
+        #[var_args] path: VarArgs<TokenIdentifier>,
+    ) -> SCResult<MultiResultVec<Self::BigUint>> {
+        let path = path.into_vec();
+        let pairs = self.get_pairs_for_path(&path)?;
+        let amounts = self.get_amounts_out(&amount_in, &path, &pairs)?;
+        Ok(MultiResultVec::from_iter(amounts))
+    }
+
+    #[view(getAmountsIn)]
+    fn get_amounts_in_view(
+        &self,
+        amount_out: Self::BigUint,
+        #[var_args] path: VarArgs<TokenIdentifier>,
+    ) -> SCResult<MultiResultVec<Self::BigUint>> {
+        let path = path.into_vec();
+        let pairs = self.get_pairs_for_path(&path)?;
+        let amounts = self.get_amounts_in(&amount_out, &path, &pairs)?;
+        Ok(MultiResultVec::from_iter(amounts))
+    }
+
+    #[payable("*")]
+    #[endpoint(multiPairSwapExactOut)]
+    fn multi_pair_swap_exact_out(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in_max: Self::BigUint,
+        #[payment_nonce] nonce: Nonce,
+        amount_out: Self::BigUint,
+        #[var_args] path: VarArgs<TokenIdentifier>,
+    ) -> SCResult<MultiPairSwapExactOutResultType<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        require!(nonce == 0, "Invalid nonce. Should be zero");
+
+        let path = path.into_vec();
+        require!(
+            !path.is_empty() && path[0] == token_in,
+            "Path should start with the payment token"
+        );
+        let pairs = self.get_pairs_for_path(&path)?;
+        let amounts = self.get_amounts_in(&amount_out, &path, &pairs)?;
+        require!(
+            amounts[0] <= amount_in_max,
+            "Computed amount in greater than maximum amount in"
+        );
+
+        // Every hop is paid exactly the amount it needs, so the only excess is on the input.
+        let mut refund_amount = &amount_in_max - &amounts[0];
+        let caller = self.blockchain().get_caller();
+        for (index, pair_address) in pairs.into_iter().enumerate() {
+            let (_, residuum) = self.actual_swap_fixed_output(
+                pair_address,
+                path[index].clone(),
+                amounts[index].clone(),
+                path[index + 1].clone(),
+                amounts[index + 1].clone(),
+            );
+
+            if residuum.amount > 0 {
+                if residuum.token_id == token_in {
+                    refund_amount += &residuum.amount;
+                } else {
+                    self.send_fft_tokens(
+                        &residuum.token_id,
+                        &residuum.amount,
+                        &caller,
+                        &OptionalArg::None,
+                    )?;
+                }
+            }
+        }
+
+        let token_out = path[path.len() - 1].clone();
+        self.send_fft_tokens(&token_out, &amount_out, &caller, &OptionalArg::None)?;
+        if refund_amount > 0 {
+            self.send_fft_tokens(&token_in, &refund_amount, &caller, &OptionalArg::None)?;
+        }
+
+        let token_amount_out = FftTokenAmountPair {
+            token_id: token_out,
+            amount: amount_out,
+        };
+        let refund_token_amount = FftTokenAmountPair {
+            token_id: token_in,
+            amount: refund_amount,
+        };
+        Ok((token_amount_out, refund_token_amount).into())
+    }
+
+    fn get_pairs_for_path(&self, path: &[TokenIdentifier]) -> SCResult<Vec<Address>> {
+        require!(
+            path.len() >= 2 && path.len() <= MAX_PATH_LENGTH,
+            "Invalid path length"
+        );
+
+        let mut pairs = Vec::new();
+        for index in 0..path.len() - 1 {
+            require!(path[index] != path[index + 1], "Identical tokens");
+            let pair_address = self.get_pair(path[index].clone(), path[index + 1].clone());
+            require!(pair_address.is_some(), "Pair does not exists");
+            pairs.push(pair_address.unwrap());
+        }
+        Ok(pairs)
+    }
+
+    fn get_amounts_out(
+        &self,
+        amount_in: &Self::BigUint,
+        path: &[TokenIdentifier],
+        pairs: &[Address],
+    ) -> SCResult<Vec<Self::BigUint>> {
+        require!(amount_in > &0, "Invalid amount. Should not be zero");
+
+        let mut amounts = Vec::new();
+        amounts.push(amount_in.clone());
+        for (index, pair_address) in pairs.iter().enumerate() {
+            let amount_out = self
+                .pair_quote_proxy(pair_address.clone())
+                .get_amount_out(path[index].clone(), amounts[index].clone())
+                .execute_on_dest_context();
+            require!(amount_out > 0, "Zero output");
+            amounts.push(amount_out);
+        }
+        Ok(amounts)
+    }
+
+    fn get_amounts_in(
+        &self,
+        amount_out: &Self::BigUint,
+        path: &[TokenIdentifier],
+        pairs: &[Address],
+    ) -> SCResult<Vec<Self::BigUint>> {
+        require!(amount_out > &0, "Invalid amount. Should not be zero");
+
+        let mut amounts = Vec::new();
+        amounts.push(amount_out.clone());
+        for (index, pair_address) in pairs.iter().enumerate().rev() {
+            let amount_in = self
+                .pair_quote_proxy(pair_address.clone())
+                .get_amount_in(path[index + 1].clone(), amounts[amounts.len() - 1].clone())
+                .execute_on_dest_context();
+            amounts.push(amount_in);
+        }
+        amounts.reverse();
+        Ok(amounts)
+    }
+}
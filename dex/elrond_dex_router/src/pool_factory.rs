@@ -0,0 +1,231 @@
+#![allow(clippy::too_many_arguments)]
+
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use super::events;
+use super::events::CodeConstructionStage;
+use super::state;
+use core::iter::FromIterator;
+use permissions::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy, TypeAbi)]
+pub enum PoolType {
+    Weighted,
+    Concentrated,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, TypeAbi)]
+pub struct PoolTokens {
+    pub pool_type: PoolType,
+    pub tokens: Vec<TokenIdentifier>,
+    pub weights: Vec<u64>,
+}
+
+#[derive(TopEncode, TopDecode, PartialEq, TypeAbi)]
+pub struct PoolContractMetadata {
+    pool_type: PoolType,
+    tokens: Vec<TokenIdentifier>,
+    weights: Vec<u64>,
+    address: Address,
+}
+
+#[elrond_wasm::module]
+pub trait PoolFactoryModule:
+    state::StateModule + events::EventsModule + permissions::PermissionsModule
+{
+    fn create_weighted_pool(
+        &self,
+        pool_tokens: &PoolTokens,
+        owner: &Address,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+    ) -> SCResult<Address> {
+        require!(
+            self.pool_code_ready(&pool_tokens.pool_type).get(),
+            "Pool code not ready"
+        );
+
+        let mut arg_buffer = ArgBuffer::new();
+        arg_buffer.push_argument_bytes(self.blockchain().get_sc_address().as_bytes());
+        arg_buffer.push_argument_bytes(owner.as_bytes());
+        arg_buffer.push_argument_bytes(&total_fee_percent.to_be_bytes()[..]);
+        arg_buffer.push_argument_bytes(&special_fee_percent.to_be_bytes()[..]);
+        for (token_id, weight) in pool_tokens.tokens.iter().zip(pool_tokens.weights.iter()) {
+            arg_buffer.push_argument_bytes(token_id.as_esdt_identifier());
+            arg_buffer.push_argument_bytes(&weight.to_be_bytes()[..]);
+        }
+
+        let new_address = self.send().deploy_contract(
+            self.blockchain().get_gas_left(),
+            &0u64.into(),
+            &self.pool_code(&pool_tokens.pool_type).get(),
+            CodeMetadata::UPGRADEABLE,
+            &arg_buffer,
+        );
+        require!(new_address.is_some(), "deploy failed");
+
+        let address = new_address.unwrap();
+        self.pool_map().insert(pool_tokens.clone(), address.clone());
+        Ok(address)
+    }
+
+    fn create_concentrated_pool(
+        &self,
+        pool_tokens: &PoolTokens,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+        owner: &Address,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+        tick_spacing: u64,
+        initial_sqrt_price: &Self::BigUint,
+    ) -> SCResult<Address> {
+        require!(
+            self.pool_code_ready(&pool_tokens.pool_type).get(),
+            "Pool code not ready"
+        );
+
+        let mut arg_buffer = ArgBuffer::new();
+        arg_buffer.push_argument_bytes(first_token_id.as_esdt_identifier());
+        arg_buffer.push_argument_bytes(second_token_id.as_esdt_identifier());
+        arg_buffer.push_argument_bytes(self.blockchain().get_sc_address().as_bytes());
+        arg_buffer.push_argument_bytes(owner.as_bytes());
+        arg_buffer.push_argument_bytes(&total_fee_percent.to_be_bytes()[..]);
+        arg_buffer.push_argument_bytes(&special_fee_percent.to_be_bytes()[..]);
+        arg_buffer.push_argument_bytes(&tick_spacing.to_be_bytes()[..]);
+        arg_buffer.push_argument_bytes(initial_sqrt_price.to_bytes_be().as_slice());
+
+        let new_address = self.send().deploy_contract(
+            self.blockchain().get_gas_left(),
+            &0u64.into(),
+            &self.pool_code(&pool_tokens.pool_type).get(),
+            CodeMetadata::UPGRADEABLE,
+            &arg_buffer,
+        );
+        require!(new_address.is_some(), "deploy failed");
+
+        let address = new_address.unwrap();
+        self.pool_map().insert(pool_tokens.clone(), address.clone());
+        Ok(address)
+    }
+
+    // Tokens are kept sorted so that the same pool cannot be registered twice
+    // by passing its tokens in a different order.
+    fn build_pool_tokens(
+        &self,
+        pool_type: PoolType,
+        mut tokens_and_weights: Vec<(TokenIdentifier, u64)>,
+    ) -> PoolTokens {
+        tokens_and_weights.sort_by(|a, b| a.0.as_esdt_identifier().cmp(b.0.as_esdt_identifier()));
+        let (tokens, weights) = tokens_and_weights.into_iter().unzip();
+        PoolTokens {
+            pool_type,
+            tokens,
+            weights,
+        }
+    }
+
+    #[endpoint(startPoolCodeConstruction)]
+    fn start_pool_code_construction(&self, pool_type: PoolType) -> SCResult<()> {
+        self.require_role(Role::Upgrader)?;
+        require!(self.is_active(), "Not active");
+
+        self.pool_code_ready(&pool_type).set(&false);
+        self.pool_code(&pool_type).set(&BoxedBytes::empty());
+
+        self.emit_code_construction_event(
+            Some(pool_type),
+            CodeConstructionStage::Start,
+            0,
+            H256::zero(),
+        );
+        Ok(())
+    }
+
+    #[endpoint(endPoolCodeConstruction)]
+    fn end_pool_code_construction(&self, pool_type: PoolType) -> SCResult<()> {
+        self.require_role(Role::Upgrader)?;
+        require!(self.is_active(), "Not active");
+
+        self.pool_code_ready(&pool_type).set(&true);
+
+        let code_hash = self
+            .crypto()
+            .keccak256(self.pool_code(&pool_type).get().as_slice());
+        self.emit_code_construction_event(
+            Some(pool_type),
+            CodeConstructionStage::End,
+            0,
+            code_hash,
+        );
+        Ok(())
+    }
+
+    #[endpoint(appendPoolCode)]
+    fn append_pool_code(&self, pool_type: PoolType, part: BoxedBytes) -> SCResult<()> {
+        self.require_role(Role::Upgrader)?;
+        require!(self.is_active(), "Not active");
+        require!(
+            !self.pool_code_ready(&pool_type).get(),
+            "Pool construction not started"
+        );
+
+        let existent = self.pool_code(&pool_type).get();
+        let new_code = BoxedBytes::from_concat(&[existent.as_slice(), part.as_slice()]);
+        self.pool_code(&pool_type).set(&new_code);
+        Ok(())
+    }
+
+    fn is_pool_sc(&self, address: &Address) -> bool {
+        self.pool_map().values().any(|pool| &pool == address)
+    }
+
+    #[view(getPool)]
+    fn get_pool(
+        &self,
+        pool_type: PoolType,
+        #[var_args] tokens_and_weights: MultiArgVec<MultiArg2<TokenIdentifier, u64>>,
+    ) -> Option<Address> {
+        let pool_tokens = self.build_pool_tokens(
+            pool_type,
+            tokens_and_weights
+                .into_vec()
+                .into_iter()
+                .map(|entry| entry.into_tuple())
+                .collect(),
+        );
+        self.pool_map().get(&pool_tokens)
+    }
+
+    #[view(getAllPoolsAddresses)]
+    fn get_all_pools_addresses(&self) -> MultiResultVec<Address> {
+        self.pool_map().values().collect()
+    }
+
+    #[view(getAllPoolContractMetadata)]
+    fn get_all_pool_contract_metadata(&self) -> MultiResultVec<PoolContractMetadata> {
+        let map: Vec<PoolContractMetadata> = self
+            .pool_map()
+            .iter()
+            .map(|x| PoolContractMetadata {
+                pool_type: x.0.pool_type,
+                tokens: x.0.tokens,
+                weights: x.0.weights,
+                address: x.1,
+            })
+            .collect();
+        MultiResultVec::from_iter(map)
+    }
+
+    #[storage_mapper("pool_map")]
+    fn pool_map(&self) -> SafeMapMapper<Self::Storage, PoolTokens, Address>;
+
+    #[view(getPoolCode)]
+    #[storage_mapper("pool_code")]
+    fn pool_code(&self, pool_type: &PoolType) -> SingleValueMapper<Self::Storage, BoxedBytes>;
+
+    #[view(getPoolCodeReady)]
+    #[storage_mapper("pool_code_ready")]
+    fn pool_code_ready(&self, pool_type: &PoolType) -> SingleValueMapper<Self::Storage, bool>;
+}
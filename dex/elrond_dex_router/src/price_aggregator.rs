@@ -0,0 +1,426 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+
+use super::delisting;
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pair_registry::{PairInfo, MAX_PAGE_SIZE};
+use super::pool_factory;
+use super::referral;
+use super::state;
+
+const MAX_PRICE_ROUTE_HOPS: usize = 3;
+const PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+// Scale of the prices in the pair's oracle, see getPriceDivisionSafetyConstant on the pair.
+const TWAP_PRICE_PRECISION: u64 = 1_000_000_000_000;
+// Six of the pair's committed price records, one every 600 blocks.
+const TWAP_WINDOW_BLOCKS: u64 = 3_600;
+
+mod pair_price_proxy {
+    elrond_wasm::imports!();
+
+    use elrond_dex_pair::oracle::PriceRecord;
+
+    #[elrond_wasm::proxy]
+    pub trait PairPrice {
+        #[view(getReservesAndTotalSupply)]
+        fn get_reserves_and_total_supply(
+            &self,
+        ) -> MultiResult3<Self::BigUint, Self::BigUint, Self::BigUint>;
+
+        #[view(getTwapPriceRecord)]
+        fn get_twap_price_record(&self, window_blocks: u64) -> Option<PriceRecord<Self::BigUint>>;
+    }
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy, TypeAbi)]
+pub enum PriceSource {
+    Reserves,
+    Twap,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PairTvl<BigUint: BigUintApi> {
+    pub pair_id: u64,
+    pub address: Address,
+    pub first_token_id: TokenIdentifier,
+    pub first_token_reserve: BigUint,
+    pub second_token_id: TokenIdentifier,
+    pub second_token_reserve: BigUint,
+    pub tvl: Option<BigUint>,
+}
+
+pub struct PriceHop<BigUint: BigUintApi> {
+    pair_address: Address,
+    token_in_is_first: bool,
+    reserve_in: BigUint,
+    reserve_out: BigUint,
+}
+
+pub struct PriceRouteNode<BigUint: BigUintApi> {
+    token_id: TokenIdentifier,
+    parent: usize,
+    hop: Option<PriceHop<BigUint>>,
+}
+
+#[elrond_wasm::module]
+pub trait PriceAggregatorModule:
+    state::StateModule
+    + delisting::DelistingModule
+    + pair_registry::PairRegistryModule
+    + events::EventsModule
+    + factory::FactoryModule
+    + pair_manager::PairManagerModule
+    + pool_factory::PoolFactoryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + token_send::TokenSendModule
+    + permissions::PermissionsModule
+{
+    #[proxy]
+    fn pair_price_proxy(&self, to: Address) -> pair_price_proxy::Proxy<Self::SendApi>;
+
+    // Price of one unit of the token in units of the reference token, scaled by 10^18.
+    #[view(getTokenPrice)]
+    fn get_token_price(
+        &self,
+        token_id: TokenIdentifier,
+        reference_token_id: TokenIdentifier,
+        price_source: PriceSource,
+    ) -> SCResult<Self::BigUint> {
+        self.require_pair_registry_backfilled()?;
+
+        let route = self.find_price_route(&token_id, &reference_token_id);
+        require!(route.is_some(), "No price route found");
+        match self.get_route_price(&route.unwrap(), price_source) {
+            Some(price) => Ok(price),
+            None => sc_error!("No TWAP price for route"),
+        }
+    }
+
+    #[view(getPriceRoute)]
+    fn get_price_route(
+        &self,
+        token_id: TokenIdentifier,
+        reference_token_id: TokenIdentifier,
+    ) -> SCResult<MultiResultVec<Address>> {
+        self.require_pair_registry_backfilled()?;
+
+        match self.find_price_route(&token_id, &reference_token_id) {
+            Some(route) => Ok(MultiResultVec::from_iter(
+                route.into_iter().map(|hop| hop.pair_address),
+            )),
+            None => sc_error!("No price route found"),
+        }
+    }
+
+    #[view(getPairTvl)]
+    fn get_pair_tvl(
+        &self,
+        pair_address: Address,
+        reference_token_id: TokenIdentifier,
+        price_source: PriceSource,
+    ) -> SCResult<PairTvl<Self::BigUint>> {
+        self.require_pair_registry_backfilled()?;
+        let pair_id = self.pair_id_by_address(&pair_address).get();
+        require!(pair_id != 0, "Pair not registered");
+        require!(!self.is_archived_pair(&pair_address), "Pair is archived");
+
+        let mut price_cache = Vec::new();
+        Ok(self.compute_pair_tvl(
+            &self.pair_info(pair_id).get(),
+            &reference_token_id,
+            price_source,
+            &mut price_cache,
+        ))
+    }
+
+    #[view(getPairsTvlPage)]
+    fn get_pairs_tvl_page(
+        &self,
+        reference_token_id: TokenIdentifier,
+        price_source: PriceSource,
+        from_pair_id: u64,
+        max_count: usize,
+    ) -> SCResult<MultiResultVec<PairTvl<Self::BigUint>>> {
+        self.require_pair_registry_backfilled()?;
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        require!(max_count <= MAX_PAGE_SIZE, "Page too large");
+
+        let pair_count = self.pair_count().get();
+        let mut price_cache = Vec::new();
+        let pairs: Vec<PairTvl<Self::BigUint>> = (from_pair_id..=pair_count)
+            .take(max_count)
+            .map(|pair_id| self.pair_info(pair_id).get())
+            .filter(|pair_info| !self.is_archived_pair(&pair_info.address))
+            .map(|pair_info| {
+                self.compute_pair_tvl(
+                    &pair_info,
+                    &reference_token_id,
+                    price_source,
+                    &mut price_cache,
+                )
+            })
+            .collect();
+        Ok(MultiResultVec::from_iter(pairs))
+    }
+
+    // Sums the TVL of a page of pairs. Returns the sum and the pair ID the next page starts
+    // from, or 0 for the last page. Archived pairs and pairs that cannot be priced are left
+    // out of the sum.
+    #[view(getTotalTvl)]
+    fn get_total_tvl(
+        &self,
+        reference_token_id: TokenIdentifier,
+        price_source: PriceSource,
+        from_pair_id: u64,
+        max_count: usize,
+    ) -> SCResult<MultiResult2<Self::BigUint, u64>> {
+        self.require_pair_registry_backfilled()?;
+        require!(from_pair_id > 0, "Pair IDs start from 1");
+        require!(max_count <= MAX_PAGE_SIZE, "Page too large");
+
+        let pair_count = self.pair_count().get();
+        let mut price_cache = Vec::new();
+        let mut total_tvl = Self::BigUint::zero();
+        let mut pair_id = from_pair_id;
+        while pair_id <= pair_count && pair_id < from_pair_id + max_count as u64 {
+            let pair_info = self.pair_info(pair_id).get();
+            pair_id += 1;
+            if self.is_archived_pair(&pair_info.address) {
+                continue;
+            }
+            let pair_tvl = self.compute_pair_tvl(
+                &pair_info,
+                &reference_token_id,
+                price_source,
+                &mut price_cache,
+            );
+            if let Some(tvl) = pair_tvl.tvl {
+                total_tvl += &tvl;
+            }
+        }
+
+        let next_pair_id = if pair_id > pair_count { 0 } else { pair_id };
+        Ok((total_tvl, next_pair_id).into())
+    }
+
+    fn require_pair_registry_backfilled(&self) -> SCResult<()> {
+        require!(
+            self.pair_registry_backfilled().get(),
+            "Pair registry not backfilled"
+        );
+        Ok(())
+    }
+
+    // When only one of the tokens can be priced, the other side of the pool is assumed to be
+    // worth the same, as it is for any pool with balanced reserves.
+    fn compute_pair_tvl(
+        &self,
+        pair_info: &PairInfo,
+        reference_token_id: &TokenIdentifier,
+        price_source: PriceSource,
+        price_cache: &mut Vec<(TokenIdentifier, Option<Self::BigUint>)>,
+    ) -> PairTvl<Self::BigUint> {
+        let (first_token_reserve, second_token_reserve) =
+            self.get_pair_reserves(&pair_info.address);
+
+        let first_token_value = self
+            .get_cached_token_price(
+                &pair_info.first_token_id,
+                reference_token_id,
+                price_source,
+                price_cache,
+            )
+            .map(|price| &(&first_token_reserve * &price) / &Self::BigUint::from(PRICE_PRECISION));
+        let second_token_value = self
+            .get_cached_token_price(
+                &pair_info.second_token_id,
+                reference_token_id,
+                price_source,
+                price_cache,
+            )
+            .map(|price| &(&second_token_reserve * &price) / &Self::BigUint::from(PRICE_PRECISION));
+
+        let tvl = match (first_token_value, second_token_value) {
+            (Some(first_value), Some(second_value)) => Some(first_value + second_value),
+            (Some(value), None) | (None, Some(value)) => Some(value * Self::BigUint::from(2u64)),
+            (None, None) => None,
+        };
+
+        PairTvl {
+            pair_id: pair_info.pair_id,
+            address: pair_info.address.clone(),
+            first_token_id: pair_info.first_token_id.clone(),
+            first_token_reserve,
+            second_token_id: pair_info.second_token_id.clone(),
+            second_token_reserve,
+            tvl,
+        }
+    }
+
+    fn get_cached_token_price(
+        &self,
+        token_id: &TokenIdentifier,
+        reference_token_id: &TokenIdentifier,
+        price_source: PriceSource,
+        price_cache: &mut Vec<(TokenIdentifier, Option<Self::BigUint>)>,
+    ) -> Option<Self::BigUint> {
+        if let Some((_, price)) = price_cache.iter().find(|(cached, _)| cached == token_id) {
+            return price.clone();
+        }
+
+        let price = self
+            .find_price_route(token_id, reference_token_id)
+            .and_then(|route| self.get_route_price(&route, price_source));
+        price_cache.push((token_id.clone(), price.clone()));
+        price
+    }
+
+    // Breadth first search over the registered pairs, so the route with the fewest hops wins.
+    // Pairs in sunset, delisted pairs and pairs with an empty reserve are skipped.
+    fn find_price_route(
+        &self,
+        token_id: &TokenIdentifier,
+        reference_token_id: &TokenIdentifier,
+    ) -> Option<Vec<PriceHop<Self::BigUint>>> {
+        if token_id == reference_token_id {
+            return Some(Vec::new());
+        }
+
+        let mut nodes = Vec::new();
+        nodes.push(PriceRouteNode {
+            token_id: token_id.clone(),
+            parent: 0,
+            hop: None,
+        });
+        let mut level_start = 0;
+        for _ in 0..MAX_PRICE_ROUTE_HOPS {
+            let level_end = nodes.len();
+            for index in level_start..level_end {
+                let current_token_id = nodes[index].token_id.clone();
+                let pair_ids = self.token_pair_ids(&current_token_id);
+                for pair_index in 1..=pair_ids.len() {
+                    let pair_info = self.pair_info(pair_ids.get(pair_index)).get();
+                    if pair_info.state == PairState::Sunset
+                        || self.is_delisted_pair(&pair_info.address)
+                    {
+                        continue;
+                    }
+
+                    let token_in_is_first = pair_info.first_token_id == current_token_id;
+                    let next_token_id = if token_in_is_first {
+                        pair_info.second_token_id
+                    } else {
+                        pair_info.first_token_id
+                    };
+                    if nodes.iter().any(|node| node.token_id == next_token_id) {
+                        continue;
+                    }
+
+                    let hop = match self.get_price_hop(pair_info.address, token_in_is_first) {
+                        Some(hop) => hop,
+                        None => continue,
+                    };
+                    let is_reference = &next_token_id == reference_token_id;
+                    nodes.push(PriceRouteNode {
+                        token_id: next_token_id,
+                        parent: index,
+                        hop: Some(hop),
+                    });
+                    if is_reference {
+                        return Some(self.collect_price_route(nodes));
+                    }
+                }
+            }
+            level_start = level_end;
+        }
+
+        None
+    }
+
+    fn collect_price_route(
+        &self,
+        mut nodes: Vec<PriceRouteNode<Self::BigUint>>,
+    ) -> Vec<PriceHop<Self::BigUint>> {
+        let mut route = Vec::new();
+        let mut index = nodes.len() - 1;
+        while index != 0 {
+            if let Some(hop) = nodes[index].hop.take() {
+                route.push(hop);
+            }
+            index = nodes[index].parent;
+        }
+        route.reverse();
+        route
+    }
+
+    fn get_price_hop(
+        &self,
+        pair_address: Address,
+        token_in_is_first: bool,
+    ) -> Option<PriceHop<Self::BigUint>> {
+        let (first_token_reserve, second_token_reserve) = self.get_pair_reserves(&pair_address);
+        if first_token_reserve == 0 || second_token_reserve == 0 {
+            return None;
+        }
+
+        let (reserve_in, reserve_out) = if token_in_is_first {
+            (first_token_reserve, second_token_reserve)
+        } else {
+            (second_token_reserve, first_token_reserve)
+        };
+        Some(PriceHop {
+            pair_address,
+            token_in_is_first,
+            reserve_in,
+            reserve_out,
+        })
+    }
+
+    // Multiplies the price of each hop, either from the reserves or from the pair's TWAP over
+    // the last TWAP_WINDOW_BLOCKS blocks. Returns None if a pair on the route has no committed
+    // price record in that window.
+    fn get_route_price(
+        &self,
+        route: &[PriceHop<Self::BigUint>],
+        price_source: PriceSource,
+    ) -> Option<Self::BigUint> {
+        let mut price = Self::BigUint::from(PRICE_PRECISION);
+        for hop in route.iter() {
+            price = match price_source {
+                PriceSource::Reserves => &(&price * &hop.reserve_out) / &hop.reserve_in,
+                PriceSource::Twap => {
+                    let record = self
+                        .pair_price_proxy(hop.pair_address.clone())
+                        .get_twap_price_record(TWAP_WINDOW_BLOCKS)
+                        .execute_on_dest_context()?;
+                    let twap_price = if hop.token_in_is_first {
+                        record.first_token_price
+                    } else {
+                        record.second_token_price
+                    };
+                    &(&price * &twap_price) / &Self::BigUint::from(TWAP_PRICE_PRECISION)
+                }
+            };
+        }
+        Some(price)
+    }
+
+    fn get_pair_reserves(&self, pair_address: &Address) -> (Self::BigUint, Self::BigUint) {
+        let (first_token_reserve, second_token_reserve, _) = self
+            .pair_price_proxy(pair_address.clone())
+            .get_reserves_and_total_supply()
+            .execute_on_dest_context_custom_range(|_, after| (after - 3, after))
+            .into_tuple();
+        (first_token_reserve, second_token_reserve)
+    }
+}
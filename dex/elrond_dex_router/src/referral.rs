@@ -0,0 +1,146 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::FftTokenAmountPair;
+use core::iter::FromIterator;
+use permissions::Role;
+
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_registry;
+use super::pool_factory;
+use super::state;
+
+// Out of the special fee of each swap, with a base of 100_000.
+const MAX_REFERRAL_FEE_PERCENT: u64 = 50_000;
+
+#[elrond_wasm::module]
+pub trait ReferralModule:
+    state::StateModule
+    + factory::FactoryModule
+    + pool_factory::PoolFactoryModule
+    + events::EventsModule
+    + token_send::TokenSendModule
+    + pair_registry::PairRegistryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + permissions::PermissionsModule
+{
+    #[endpoint(setReferralFeePercent)]
+    fn set_referral_fee_percent(&self, fee_percent: u64) -> SCResult<()> {
+        self.require_role(Role::FeeManager)?;
+        require!(
+            fee_percent <= MAX_REFERRAL_FEE_PERCENT,
+            "Bad referral fee percent"
+        );
+        let old_fee_percent = self.referral_fee_percent().get();
+        self.referral_fee_percent().set(&fee_percent);
+
+        self.emit_config_change_event(b"referral_fee_percent", old_fee_percent, fee_percent);
+        Ok(())
+    }
+
+    #[endpoint(setReferralFeeCap)]
+    fn set_referral_fee_cap(
+        &self,
+        token_id: TokenIdentifier,
+        fee_cap: Self::BigUint,
+    ) -> SCResult<()> {
+        self.require_role(Role::FeeManager)?;
+        require!(
+            token_id.is_valid_esdt_identifier(),
+            "Token ID is not a valid esdt token ID"
+        );
+        let old_fee_cap = self.referral_fee_cap(&token_id).get();
+        self.referral_fee_cap(&token_id).set(&fee_cap);
+
+        self.emit_token_config_change_event(token_id, b"referral_fee_cap", old_fee_cap, fee_cap);
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(acceptReferralFee)]
+    fn accept_referral_fee(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: Self::BigUint,
+        referrer: Address,
+    ) -> SCResult<()> {
+        let caller = self.blockchain().get_caller();
+        self.check_is_pair_sc(&caller)?;
+        require!(amount > 0, "Invalid amount. Should not be zero");
+
+        self.referral_balance(&referrer, &token_id)
+            .update(|balance| *balance += &amount);
+        self.referral_tokens(&referrer).insert(token_id.clone());
+
+        self.emit_referral_fee_event(referrer, caller, FftTokenAmountPair { token_id, amount });
+        Ok(())
+    }
+
+    #[endpoint(claimReferralRewards)]
+    fn claim_referral_rewards(
+        &self,
+        #[var_args] token_ids: VarArgs<TokenIdentifier>,
+    ) -> SCResult<MultiResultVec<FftTokenAmountPair<Self::BigUint>>> {
+        let caller = self.blockchain().get_caller();
+        let token_ids = if token_ids.is_empty() {
+            self.referral_tokens(&caller).iter().collect()
+        } else {
+            token_ids.into_vec()
+        };
+
+        let mut rewards = Vec::new();
+        for token_id in token_ids.into_iter() {
+            let amount = self.referral_balance(&caller, &token_id).get();
+            self.referral_balance(&caller, &token_id).clear();
+            self.referral_tokens(&caller).remove(&token_id);
+
+            if amount > 0 {
+                self.send_fft_tokens(&token_id, &amount, &caller, &OptionalArg::None)?;
+                rewards.push(FftTokenAmountPair { token_id, amount });
+            }
+        }
+        require!(!rewards.is_empty(), "No referral rewards to claim");
+
+        self.emit_claim_referral_rewards_event(caller, rewards.clone());
+        Ok(MultiResultVec::from_iter(rewards))
+    }
+
+    #[view(getReferralBalances)]
+    fn get_referral_balances(
+        &self,
+        referrer: Address,
+    ) -> MultiResultVec<FftTokenAmountPair<Self::BigUint>> {
+        let balances: Vec<FftTokenAmountPair<Self::BigUint>> = self
+            .referral_tokens(&referrer)
+            .iter()
+            .map(|token_id| FftTokenAmountPair {
+                amount: self.referral_balance(&referrer, &token_id).get(),
+                token_id,
+            })
+            .collect();
+        MultiResultVec::from_iter(balances)
+    }
+
+    #[view(getReferralFeePercent)]
+    #[storage_mapper("referral_fee_percent")]
+    fn referral_fee_percent(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getReferralFeeCap)]
+    #[storage_mapper("referral_fee_cap")]
+    fn referral_fee_cap(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+
+    #[storage_mapper("referral_balance")]
+    fn referral_balance(
+        &self,
+        referrer: &Address,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+
+    #[storage_mapper("referral_tokens")]
+    fn referral_tokens(&self, referrer: &Address) -> SafeSetMapper<Self::Storage, TokenIdentifier>;
+}
@@ -0,0 +1,310 @@
+#![allow(clippy::too_many_arguments)]
+
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::{FftTokenAmountPair, Nonce};
+use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
+use super::state;
+
+const MAX_ROUTE_HOPS: usize = 4;
+// Each quote costs three calls to a pair, this keeps the search within the gas limit.
+const MAX_ROUTE_QUOTES: usize = 40;
+
+pub mod pair_quote_proxy {
+    elrond_wasm::imports!();
+
+    use elrond_dex_pair::config::State;
+
+    #[elrond_wasm::proxy]
+    pub trait PairQuote {
+        #[view(getState)]
+        fn get_state(&self) -> State;
+
+        #[view(getReservesAndTotalSupply)]
+        fn get_reserves_and_total_supply(
+            &self,
+        ) -> MultiResult3<Self::BigUint, Self::BigUint, Self::BigUint>;
+
+        #[view(getAmountOut)]
+        fn get_amount_out(
+            &self,
+            token_in: TokenIdentifier,
+            amount_in: Self::BigUint,
+        ) -> Self::BigUint;
+
+        #[view(getAmountIn)]
+        fn get_amount_in(
+            &self,
+            token_wanted: TokenIdentifier,
+            amount_wanted: Self::BigUint,
+        ) -> Self::BigUint;
+    }
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct RouteStep<BigUint: BigUintApi> {
+    pub pair_address: Address,
+    pub token_in: TokenIdentifier,
+    pub token_out: TokenIdentifier,
+    pub amount_out: BigUint,
+}
+
+pub struct RouteEdge {
+    pair_address: Address,
+    first_token_id: TokenIdentifier,
+    second_token_id: TokenIdentifier,
+}
+
+#[elrond_wasm::module]
+pub trait RouteFinderModule:
+    state::StateModule
+    + factory::FactoryModule
+    + pair_manager::PairManagerModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + pool_factory::PoolFactoryModule
+    + pair_registry::PairRegistryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + permissions::PermissionsModule
+{
+    #[proxy]
+    fn pair_quote_proxy(&self, to: Address) -> pair_quote_proxy::Proxy<Self::SendApi>;
+
+    #[view(getBestRoute)]
+    fn get_best_route(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+        amount_in: Self::BigUint,
+        max_hops: usize,
+    ) -> SCResult<MultiResultVec<RouteStep<Self::BigUint>>> {
+        let route = self.find_best_route(&token_in, &token_out, &amount_in, max_hops)?;
+        Ok(MultiResultVec::from_iter(route))
+    }
+
+    #[payable("*")]
+    #[endpoint(swapExactInBestRoute)]
+    fn swap_exact_in_best_route(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in: Self::BigUint,
+        #[payment_nonce] nonce: Nonce,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        max_hops: usize,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<FftTokenAmountPair<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        require!(nonce == 0, "Invalid nonce. Should be zero");
+
+        let route = self.find_best_route(&token_in, &token_out, &amount_in, max_hops)?;
+
+        let mut last_received_token_id = token_in;
+        let mut last_received_amount = amount_in;
+        for step in route.into_iter() {
+            let token_amount_out = self.actual_swap_fixed_input(
+                step.pair_address,
+                last_received_token_id,
+                last_received_amount,
+                step.token_out,
+                Self::BigUint::zero(),
+            );
+            last_received_token_id = token_amount_out.token_id;
+            last_received_amount = token_amount_out.amount;
+        }
+        require!(
+            last_received_amount >= amount_out_min,
+            "Computed amount out lesser than minimum amount out"
+        );
+
+        let caller = self.blockchain().get_caller();
+        self.send_fft_tokens(
+            &last_received_token_id,
+            &last_received_amount,
+            &caller,
+            &opt_accept_funds_func,
+        )?;
+
+        Ok(FftTokenAmountPair {
+            token_id: last_received_token_id,
+            amount: last_received_amount,
+        })
+    }
+
+    fn find_best_route(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_in: &Self::BigUint,
+        max_hops: usize,
+    ) -> SCResult<Vec<RouteStep<Self::BigUint>>> {
+        require!(token_in != token_out, "Identical tokens");
+        require!(amount_in > &0, "Invalid amount. Should not be zero");
+        require!(
+            max_hops > 0 && max_hops <= MAX_ROUTE_HOPS,
+            "Invalid max hops"
+        );
+
+        let edges = self.get_route_edges();
+        let mut visited = Vec::new();
+        visited.push(token_in.clone());
+        let mut current = Vec::new();
+        let mut best = None;
+        let mut quotes_left = MAX_ROUTE_QUOTES;
+        self.search_routes(
+            &edges,
+            token_in,
+            token_out,
+            amount_in,
+            max_hops,
+            &mut visited,
+            &mut current,
+            &mut best,
+            &mut quotes_left,
+        );
+
+        match best {
+            Some(route) => Ok(route),
+            None => sc_error!("No route found"),
+        }
+    }
+
+    // Depth first search over the simple paths of at most max_hops pairs.
+    // Each hop is quoted with the pair's getAmountOut, for the output of the previous hop.
+    // Direct pairs to the wanted token are tried first, and the search stops once
+    // MAX_ROUTE_QUOTES quotes were made, keeping the best route found until then.
+    fn search_routes(
+        &self,
+        edges: &[RouteEdge],
+        token: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount: &Self::BigUint,
+        hops_left: usize,
+        visited: &mut Vec<TokenIdentifier>,
+        current: &mut Vec<RouteStep<Self::BigUint>>,
+        best: &mut Option<Vec<RouteStep<Self::BigUint>>>,
+        quotes_left: &mut usize,
+    ) {
+        let (direct_edges, other_edges): (Vec<&RouteEdge>, Vec<&RouteEdge>) =
+            edges.iter().partition(|edge| {
+                (&edge.first_token_id == token && &edge.second_token_id == token_out)
+                    || (&edge.second_token_id == token && &edge.first_token_id == token_out)
+            });
+        for edge in direct_edges.into_iter().chain(other_edges) {
+            let next_token = if &edge.first_token_id == token {
+                &edge.second_token_id
+            } else if &edge.second_token_id == token {
+                &edge.first_token_id
+            } else {
+                continue;
+            };
+            if visited.contains(next_token) {
+                continue;
+            }
+            if *quotes_left == 0 {
+                return;
+            }
+            *quotes_left -= 1;
+
+            let amount_out = match self.quote_pair(&edge.pair_address, token, amount) {
+                Some(amount_out) => amount_out,
+                None => continue,
+            };
+            current.push(RouteStep {
+                pair_address: edge.pair_address.clone(),
+                token_in: token.clone(),
+                token_out: next_token.clone(),
+                amount_out: amount_out.clone(),
+            });
+
+            if next_token == token_out {
+                let is_better = match best.as_ref().and_then(|route| route.last()) {
+                    Some(best_step) => amount_out > best_step.amount_out,
+                    None => true,
+                };
+                if is_better {
+                    *best = Some(current.clone());
+                }
+            } else if hops_left > 1 {
+                visited.push(next_token.clone());
+                self.search_routes(
+                    edges,
+                    next_token,
+                    token_out,
+                    &amount_out,
+                    hops_left - 1,
+                    visited,
+                    current,
+                    best,
+                    quotes_left,
+                );
+                visited.pop();
+            }
+
+            current.pop();
+        }
+    }
+
+    fn quote_pair(
+        &self,
+        pair_address: &Address,
+        token_in: &TokenIdentifier,
+        amount_in: &Self::BigUint,
+    ) -> Option<Self::BigUint> {
+        if amount_in == &0 {
+            return None;
+        }
+
+        let state = self
+            .pair_quote_proxy(pair_address.clone())
+            .get_state()
+            .execute_on_dest_context();
+        if state != PairState::Active {
+            return None;
+        }
+
+        let (first_token_reserve, second_token_reserve, _) = self
+            .pair_quote_proxy(pair_address.clone())
+            .get_reserves_and_total_supply()
+            .execute_on_dest_context_custom_range(|_, after| (after - 3, after))
+            .into_tuple();
+        if first_token_reserve == 0 || second_token_reserve == 0 {
+            return None;
+        }
+
+        let amount_out = self
+            .pair_quote_proxy(pair_address.clone())
+            .get_amount_out(token_in.clone(), amount_in.clone())
+            .execute_on_dest_context();
+        if amount_out == 0 {
+            None
+        } else {
+            Some(amount_out)
+        }
+    }
+
+    fn get_route_edges(&self) -> Vec<RouteEdge> {
+        self.pair_map()
+            .iter()
+            .filter(|(_, pair_address)| !self.sunset_pairs().contains(pair_address))
+            .map(|(pair_tokens, pair_address)| RouteEdge {
+                pair_address,
+                first_token_id: pair_tokens.first_token_id,
+                second_token_id: pair_tokens.second_token_id,
+            })
+            .collect()
+    }
+}
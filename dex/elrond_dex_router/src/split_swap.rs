@@ -0,0 +1,187 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::{FftTokenAmountPair, Nonce};
+
+use super::egld_wrapper;
+use super::events;
+use super::factory;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
+use super::route_finder;
+use super::state;
+
+pub const SPLIT_WEIGHT_TOTAL: u64 = 100_000;
+const MAX_SPLIT_ROUTES: usize = 5;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct SplitRouteHop {
+    pub pair_address: Address,
+    pub token_out: TokenIdentifier,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct SplitRoute {
+    pub weight: u64,
+    pub hops: Vec<SplitRouteHop>,
+}
+
+#[elrond_wasm::module]
+pub trait SplitSwapModule:
+    state::StateModule
+    + factory::FactoryModule
+    + pair_manager::PairManagerModule
+    + route_finder::RouteFinderModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + pool_factory::PoolFactoryModule
+    + pair_registry::PairRegistryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + permissions::PermissionsModule
+{
+    #[payable("*")]
+    #[endpoint(splitSwapFixedInput)]
+    fn split_swap_fixed_input(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in: Self::BigUint,
+        #[payment_nonce] nonce: Nonce,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        #[var_args] routes: VarArgs<SplitRoute>,
+    ) -> SCResult<FftTokenAmountPair<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        require!(nonce == 0, "Invalid nonce. Should be zero");
+        require!(amount_in > 0, "Invalid amount. Should not be zero");
+
+        let routes = routes.into_vec();
+        self.require_valid_split_routes(&token_in, &token_out, &routes)?;
+        let route_amounts = self.split_amount(&amount_in, &routes);
+
+        let mut total_amount_out = Self::BigUint::zero();
+        for (route, route_amount) in routes.into_iter().zip(route_amounts) {
+            if route_amount == 0 {
+                continue;
+            }
+
+            let mut last_received_token_id = token_in.clone();
+            let mut last_received_amount = route_amount;
+            for hop in route.hops.into_iter() {
+                let token_amount_out = self.actual_swap_fixed_input(
+                    hop.pair_address,
+                    last_received_token_id,
+                    last_received_amount,
+                    hop.token_out,
+                    Self::BigUint::zero(),
+                );
+                last_received_token_id = token_amount_out.token_id;
+                last_received_amount = token_amount_out.amount;
+            }
+            total_amount_out += &last_received_amount;
+        }
+        require!(
+            total_amount_out >= amount_out_min,
+            "Computed amount out lesser than minimum amount out"
+        );
+
+        let caller = self.blockchain().get_caller();
+        self.send_fft_tokens(&token_out, &total_amount_out, &caller, &OptionalArg::None)?;
+
+        Ok(FftTokenAmountPair {
+            token_id: token_out,
+            amount: total_amount_out,
+        })
+    }
+
+    // Each route is quoted independently, so routes that share a pair get an optimistic estimate.
+    #[view(getSplitSwapAmountOut)]
+    fn get_split_swap_amount_out(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: Self::BigUint,
+        token_out: TokenIdentifier,
+        #[var_args] routes: VarArgs<SplitRoute>,
+    ) -> SCResult<Self::BigUint> {
+        require!(amount_in > 0, "Invalid amount. Should not be zero");
+
+        let routes = routes.into_vec();
+        self.require_valid_split_routes(&token_in, &token_out, &routes)?;
+        let route_amounts = self.split_amount(&amount_in, &routes);
+
+        let mut total_amount_out = Self::BigUint::zero();
+        for (route, route_amount) in routes.iter().zip(route_amounts) {
+            if route_amount == 0 {
+                continue;
+            }
+
+            let mut last_received_token_id = token_in.clone();
+            let mut last_received_amount = route_amount;
+            for hop in route.hops.iter() {
+                let amount_out = self.quote_pair(
+                    &hop.pair_address,
+                    &last_received_token_id,
+                    &last_received_amount,
+                );
+                require!(amount_out.is_some(), "Route cannot be quoted");
+
+                last_received_token_id = hop.token_out.clone();
+                last_received_amount = amount_out.unwrap();
+            }
+            total_amount_out += &last_received_amount;
+        }
+
+        Ok(total_amount_out)
+    }
+
+    fn require_valid_split_routes(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        routes: &[SplitRoute],
+    ) -> SCResult<()> {
+        require!(token_in != token_out, "Identical tokens");
+        require!(
+            !routes.is_empty() && routes.len() <= MAX_SPLIT_ROUTES,
+            "Invalid number of routes"
+        );
+
+        let mut total_weight = 0u64;
+        for route in routes.iter() {
+            require!(route.weight > 0, "Route weight cannot be zero");
+            require!(!route.hops.is_empty(), "Route cannot be empty");
+            total_weight += route.weight;
+
+            for hop in route.hops.iter() {
+                self.check_is_pair_sc(&hop.pair_address)?;
+            }
+            require!(
+                &route.hops[route.hops.len() - 1].token_out == token_out,
+                "Route does not end with token out"
+            );
+        }
+        require!(
+            total_weight == SPLIT_WEIGHT_TOTAL,
+            "Route weights should sum up to 100_000"
+        );
+        Ok(())
+    }
+
+    // The last route gets whatever is left after the others, so no dust stays in the router.
+    fn split_amount(&self, amount: &Self::BigUint, routes: &[SplitRoute]) -> Vec<Self::BigUint> {
+        let mut amounts = Vec::new();
+        let mut remaining = amount.clone();
+        for route in routes.iter().take(routes.len() - 1) {
+            let route_amount = &(amount * &Self::BigUint::from(route.weight))
+                / &Self::BigUint::from(SPLIT_WEIGHT_TOTAL);
+            remaining -= &route_amount;
+            amounts.push(route_amount);
+        }
+        amounts.push(remaining);
+        amounts
+    }
+}
@@ -1,20 +1,33 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+use permissions::Role;
+
+use super::events;
+
 #[elrond_wasm::module]
-pub trait StateModule {
+pub trait StateModule: permissions::PermissionsModule + events::EventsModule {
     #[inline]
     fn is_active(&self) -> bool {
         self.state().get()
     }
 
-    #[only_owner]
     #[endpoint(setPairCreationEnabled)]
     fn set_pair_creation_enabled(&self, enabled: bool) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        let old_enabled = self.pair_creation_enabled().get();
         self.pair_creation_enabled().set(&enabled);
+
+        self.emit_config_change_event(b"pair_creation_enabled", old_enabled as u64, enabled as u64);
         Ok(())
     }
 
+    fn set_router_state(&self, active: bool) {
+        let old_active = self.state().get();
+        self.state().set(&active);
+        self.emit_config_change_event(b"state", old_active as u64, active as u64);
+    }
+
     #[view(getOwner)]
     #[storage_mapper("owner")]
     fn owner(&self) -> SingleValueMapper<Self::Storage, Address>;
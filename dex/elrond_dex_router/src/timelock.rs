@@ -0,0 +1,552 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use core::iter::FromIterator;
+use elrond_dex_pair::config::State as PairState;
+use permissions::Role;
+
+use super::delisting;
+use super::egld_wrapper;
+use super::egld_wrapper::EgldWrapperConfig;
+use super::events;
+use super::factory;
+use super::fee_policy;
+use super::fee_policy::FeePercents;
+use super::fee_routing;
+use super::fee_routing::FeeRoutingConfig;
+use super::pair_code_versions;
+use super::pair_manager;
+use super::pair_registry;
+use super::pool_factory;
+use super::referral;
+use super::state;
+
+const DEFAULT_TIMELOCK_DELAY_EPOCHS: u64 = 2;
+const MIN_TIMELOCK_DELAY_EPOCHS: u64 = 1;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi, Clone, Copy)]
+pub enum TimelockActionType {
+    UpgradePair,
+    SetFeeOn,
+    SetFeeOff,
+    WhitelistPairAddress,
+    RemoveWhitelistPairAddress,
+    Resume,
+    ApprovePairCodeVersion,
+    SetTimelockDelay,
+    SetFeeRoutingConfig,
+    SetDefaultFeePercents,
+    SetClassFeePercents,
+    RemoveClassFeePercents,
+    SetTokenClass,
+    SetEgldWrapper,
+    SetCurrentPairCodeVersion,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct TimelockedAction {
+    pub action_type: TimelockActionType,
+    pub addresses: Vec<Address>,
+    pub token_ids: Vec<TokenIdentifier>,
+    pub values: Vec<u64>,
+    pub execution_epoch: u64,
+}
+
+#[elrond_wasm::module]
+pub trait TimelockModule:
+    state::StateModule
+    + delisting::DelistingModule
+    + egld_wrapper::EgldWrapperModule
+    + factory::FactoryModule
+    + fee_policy::FeePolicyModule
+    + fee_routing::FeeRoutingModule
+    + pair_manager::PairManagerModule
+    + pair_registry::PairRegistryModule
+    + pair_code_versions::PairCodeVersionsModule
+    + events::EventsModule
+    + token_send::TokenSendModule
+    + pool_factory::PoolFactoryModule
+    + referral::ReferralModule
+    + permissions::PermissionsModule
+{
+    fn init_timelock(&self) {
+        self.timelock_delay_epochs()
+            .set_if_empty(&DEFAULT_TIMELOCK_DELAY_EPOCHS);
+    }
+
+    fn queue_timelocked_action(
+        &self,
+        action_type: TimelockActionType,
+        addresses: Vec<Address>,
+        token_ids: Vec<TokenIdentifier>,
+        values: Vec<u64>,
+    ) -> u64 {
+        let action_id = self.last_timelocked_action_id().get() + 1;
+        self.last_timelocked_action_id().set(&action_id);
+
+        let action = TimelockedAction {
+            action_type,
+            addresses,
+            token_ids,
+            values,
+            execution_epoch: self.blockchain().get_block_epoch()
+                + self.timelock_delay_epochs().get(),
+        };
+        self.timelocked_actions().insert(action_id, action.clone());
+        self.emit_timelock_queued_event(action_id, action);
+        action_id
+    }
+
+    #[endpoint(setFeeOn)]
+    fn set_fee_on(
+        &self,
+        pair_address: Address,
+        fee_to_address: Address,
+        fee_token: TokenIdentifier,
+    ) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        require!(self.is_active(), "Not active");
+        self.check_is_pair_sc(&pair_address)?;
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetFeeOn,
+            [pair_address, fee_to_address].to_vec(),
+            [fee_token].to_vec(),
+            Vec::new(),
+        ))
+    }
+
+    #[endpoint(setFeeOff)]
+    fn set_fee_off(
+        &self,
+        pair_address: Address,
+        fee_to_address: Address,
+        fee_token: TokenIdentifier,
+    ) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        require!(self.is_active(), "Not active");
+        self.check_is_pair_sc(&pair_address)?;
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetFeeOff,
+            [pair_address, fee_to_address].to_vec(),
+            [fee_token].to_vec(),
+            Vec::new(),
+        ))
+    }
+
+    #[endpoint(whitelistPairAddress)]
+    fn whitelist_pair_address(&self, pair_address: Address, address: Address) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        require!(self.is_active(), "Not active");
+        self.check_is_pair_sc(&pair_address)?;
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::WhitelistPairAddress,
+            [pair_address, address].to_vec(),
+            Vec::new(),
+            Vec::new(),
+        ))
+    }
+
+    #[endpoint(removeWhitelistPairAddress)]
+    fn remove_whitelist_pair_address(
+        &self,
+        pair_address: Address,
+        address: Address,
+    ) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        require!(self.is_active(), "Not active");
+        self.check_is_pair_sc(&pair_address)?;
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::RemoveWhitelistPairAddress,
+            [pair_address, address].to_vec(),
+            Vec::new(),
+            Vec::new(),
+        ))
+    }
+
+    #[endpoint(setFeeRoutingConfig)]
+    fn set_fee_routing_config(
+        &self,
+        fee_to_address: Address,
+        fee_token: TokenIdentifier,
+    ) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        require!(fee_token.is_valid_esdt_identifier(), "Invalid fee token");
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetFeeRoutingConfig,
+            [fee_to_address].to_vec(),
+            [fee_token].to_vec(),
+            Vec::new(),
+        ))
+    }
+
+    #[endpoint(setDefaultFeePercents)]
+    fn set_default_fee_percents(
+        &self,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+    ) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        self.require_valid_fee_percents(&FeePercents {
+            total_fee_percent,
+            special_fee_percent,
+        })?;
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetDefaultFeePercents,
+            Vec::new(),
+            Vec::new(),
+            [total_fee_percent, special_fee_percent].to_vec(),
+        ))
+    }
+
+    #[endpoint(setClassFeePercents)]
+    fn set_class_fee_percents(
+        &self,
+        token_class: u64,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+    ) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        require!(token_class != 0, "Invalid token class");
+        self.require_valid_fee_percents(&FeePercents {
+            total_fee_percent,
+            special_fee_percent,
+        })?;
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetClassFeePercents,
+            Vec::new(),
+            Vec::new(),
+            [token_class, total_fee_percent, special_fee_percent].to_vec(),
+        ))
+    }
+
+    #[endpoint(removeClassFeePercents)]
+    fn remove_class_fee_percents(&self, token_class: u64) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        require!(
+            self.class_fee_percents().get(&token_class).is_some(),
+            "No fee override for token class"
+        );
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::RemoveClassFeePercents,
+            Vec::new(),
+            Vec::new(),
+            [token_class].to_vec(),
+        ))
+    }
+
+    // Class 0 removes the tokens from their class.
+    #[endpoint(setTokenClass)]
+    fn set_token_class(
+        &self,
+        token_class: u64,
+        #[var_args] token_ids: VarArgs<TokenIdentifier>,
+    ) -> SCResult<u64> {
+        self.require_role(Role::FeeManager)?;
+        let token_ids = token_ids.into_vec();
+        require!(!token_ids.is_empty(), "No tokens");
+        for token_id in token_ids.iter() {
+            require!(token_id.is_valid_esdt_identifier(), "Invalid token ID");
+        }
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetTokenClass,
+            Vec::new(),
+            token_ids,
+            [token_class].to_vec(),
+        ))
+    }
+
+    #[endpoint(setEgldWrapper)]
+    fn set_egld_wrapper(
+        &self,
+        wrapper_address: Address,
+        wrapped_egld_token_id: TokenIdentifier,
+    ) -> SCResult<u64> {
+        self.require_role(Role::Admin)?;
+        require!(
+            wrapped_egld_token_id.is_valid_esdt_identifier(),
+            "Invalid wrapped EGLD token"
+        );
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetEgldWrapper,
+            [wrapper_address].to_vec(),
+            [wrapped_egld_token_id].to_vec(),
+            Vec::new(),
+        ))
+    }
+
+    #[endpoint(approvePairCodeVersion)]
+    fn approve_pair_code_version(&self, version: u64) -> SCResult<u64> {
+        self.require_role(Role::Upgrader)?;
+        self.require_pair_code_version_exists(version)?;
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::ApprovePairCodeVersion,
+            Vec::new(),
+            Vec::new(),
+            [version].to_vec(),
+        ))
+    }
+
+    // The version given to newly created pairs and to upgradePair.
+    #[endpoint(setCurrentPairCodeVersion)]
+    fn set_current_pair_code_version(&self, version: u64) -> SCResult<u64> {
+        self.require_role(Role::Upgrader)?;
+        self.require_pair_code_version_exists(version)?;
+
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetCurrentPairCodeVersion,
+            Vec::new(),
+            Vec::new(),
+            [version].to_vec(),
+        ))
+    }
+
+    #[endpoint(setTimelockDelay)]
+    fn set_timelock_delay(&self, delay_epochs: u64) -> SCResult<u64> {
+        self.require_role(Role::Admin)?;
+        require!(
+            delay_epochs >= MIN_TIMELOCK_DELAY_EPOCHS,
+            "Timelock delay too short"
+        );
+        Ok(self.queue_timelocked_action(
+            TimelockActionType::SetTimelockDelay,
+            Vec::new(),
+            Vec::new(),
+            [delay_epochs].to_vec(),
+        ))
+    }
+
+    #[endpoint(executeTimelockedAction)]
+    fn execute_timelocked_action(&self, action_id: u64) -> SCResult<()> {
+        let action = self.timelocked_actions().get(&action_id);
+        require!(action.is_some(), "Unknown timelocked action");
+        let action = action.unwrap();
+        self.require_role(self.get_timelocked_action_role(action.action_type))?;
+        require!(
+            self.blockchain().get_block_epoch() >= action.execution_epoch,
+            "Timelock has not expired"
+        );
+        self.timelocked_actions().remove(&action_id);
+
+        let addresses = &action.addresses;
+        let token_ids = &action.token_ids;
+        let values = &action.values;
+        match action.action_type {
+            TimelockActionType::UpgradePair => {
+                let pair_address = self.get_pair(token_ids[0].clone(), token_ids[1].clone());
+                require!(pair_address.is_some(), "Pair does not exists");
+                let pair_address = pair_address.unwrap();
+                self.upgrade_pair(
+                    &pair_address,
+                    &token_ids[0],
+                    &token_ids[1],
+                    &self.owner().get(),
+                    values[0],
+                    values[1],
+                    values[2],
+                )?;
+                self.update_pair_fee_override(
+                    &pair_address,
+                    &token_ids[0],
+                    &token_ids[1],
+                    &FeePercents {
+                        total_fee_percent: values[0],
+                        special_fee_percent: values[1],
+                    },
+                );
+            }
+            TimelockActionType::SetFeeOn => {
+                self.set_pair_fee(
+                    addresses[0].clone(),
+                    true,
+                    addresses[1].clone(),
+                    token_ids[0].clone(),
+                );
+            }
+            TimelockActionType::SetFeeOff => {
+                self.set_pair_fee(
+                    addresses[0].clone(),
+                    false,
+                    addresses[1].clone(),
+                    token_ids[0].clone(),
+                );
+            }
+            TimelockActionType::WhitelistPairAddress => {
+                self.set_pair_whitelist(addresses[0].clone(), addresses[1].clone(), true);
+            }
+            TimelockActionType::RemoveWhitelistPairAddress => {
+                self.set_pair_whitelist(addresses[0].clone(), addresses[1].clone(), false);
+            }
+            TimelockActionType::Resume => {
+                if addresses[0] == self.blockchain().get_sc_address() {
+                    self.set_router_state(true);
+                } else {
+                    require!(!self.is_delisted_pair(&addresses[0]), "Pair is delisted");
+                    self.change_pair_state(addresses[0].clone(), PairState::Active);
+                }
+            }
+            TimelockActionType::ApprovePairCodeVersion => {
+                let old_version = self.approved_pair_code_version().get();
+                self.approved_pair_code_version().set(&values[0]);
+                self.emit_config_change_event(
+                    b"approved_pair_code_version",
+                    old_version,
+                    values[0],
+                );
+            }
+            TimelockActionType::SetCurrentPairCodeVersion => {
+                self.update_current_pair_code_version(values[0]);
+            }
+            TimelockActionType::SetTimelockDelay => {
+                let old_delay = self.timelock_delay_epochs().get();
+                self.timelock_delay_epochs().set(&values[0]);
+                self.emit_config_change_event(b"timelock_delay_epochs", old_delay, values[0]);
+            }
+            TimelockActionType::SetFeeRoutingConfig => {
+                let old_config = if self.fee_routing_config().is_empty() {
+                    None
+                } else {
+                    Some(self.fee_routing_config().get())
+                };
+                let new_config = FeeRoutingConfig {
+                    fee_to_address: addresses[0].clone(),
+                    fee_token: token_ids[0].clone(),
+                };
+                self.fee_routing_config().set(&new_config);
+                self.emit_fee_routing_config_change_event(old_config, new_config);
+            }
+            TimelockActionType::SetDefaultFeePercents => {
+                let old_fee_percents = self.default_fee_percents().get();
+                let new_fee_percents = FeePercents {
+                    total_fee_percent: values[0],
+                    special_fee_percent: values[1],
+                };
+                self.default_fee_percents().set(&new_fee_percents);
+                self.emit_fee_policy_change_event(
+                    0,
+                    Some(old_fee_percents),
+                    Some(new_fee_percents),
+                );
+            }
+            TimelockActionType::SetClassFeePercents => {
+                let new_fee_percents = FeePercents {
+                    total_fee_percent: values[1],
+                    special_fee_percent: values[2],
+                };
+                let old_fee_percents = self
+                    .class_fee_percents()
+                    .insert(values[0], new_fee_percents);
+                self.emit_fee_policy_change_event(
+                    values[0],
+                    old_fee_percents,
+                    Some(new_fee_percents),
+                );
+            }
+            TimelockActionType::RemoveClassFeePercents => {
+                let old_fee_percents = self.class_fee_percents().remove(&values[0]);
+                self.emit_fee_policy_change_event(values[0], old_fee_percents, None);
+            }
+            TimelockActionType::SetTokenClass => {
+                for token_id in token_ids.iter() {
+                    let old_class = self.token_class(token_id).get();
+                    self.token_class(token_id).set(&values[0]);
+                    self.emit_token_config_change_event(
+                        token_id.clone(),
+                        b"token_class",
+                        Self::BigUint::from(old_class),
+                        Self::BigUint::from(values[0]),
+                    );
+                }
+            }
+            TimelockActionType::SetEgldWrapper => {
+                let old_config = if self.egld_wrapper_config().is_empty() {
+                    None
+                } else {
+                    Some(self.egld_wrapper_config().get())
+                };
+                let new_config = EgldWrapperConfig {
+                    wrapper_address: addresses[0].clone(),
+                    wrapped_egld_token_id: token_ids[0].clone(),
+                };
+                self.egld_wrapper_config().set(&new_config);
+                self.emit_egld_wrapper_config_change_event(old_config, new_config);
+            }
+        }
+
+        self.emit_timelock_executed_event(action_id, action);
+        Ok(())
+    }
+
+    #[endpoint(cancelTimelockedAction)]
+    fn cancel_timelocked_action(&self, action_id: u64) -> SCResult<()> {
+        let action = self.timelocked_actions().get(&action_id);
+        require!(action.is_some(), "Unknown timelocked action");
+        let action = action.unwrap();
+        self.require_role(self.get_timelocked_action_role(action.action_type))?;
+        self.timelocked_actions().remove(&action_id);
+
+        self.emit_timelock_cancelled_event(action_id, action);
+        Ok(())
+    }
+
+    // Executing or cancelling an action needs the same role as queueing it.
+    fn get_timelocked_action_role(&self, action_type: TimelockActionType) -> Role {
+        match action_type {
+            TimelockActionType::UpgradePair
+            | TimelockActionType::ApprovePairCodeVersion
+            | TimelockActionType::SetCurrentPairCodeVersion => Role::Upgrader,
+            TimelockActionType::SetFeeOn
+            | TimelockActionType::SetFeeOff
+            | TimelockActionType::WhitelistPairAddress
+            | TimelockActionType::RemoveWhitelistPairAddress
+            | TimelockActionType::SetFeeRoutingConfig
+            | TimelockActionType::SetDefaultFeePercents
+            | TimelockActionType::SetClassFeePercents
+            | TimelockActionType::RemoveClassFeePercents
+            | TimelockActionType::SetTokenClass => Role::FeeManager,
+            TimelockActionType::Resume => Role::Pauser,
+            TimelockActionType::SetTimelockDelay | TimelockActionType::SetEgldWrapper => {
+                Role::Admin
+            }
+        }
+    }
+
+    #[view(getTimelockedActions)]
+    fn get_timelocked_actions(&self) -> MultiResultVec<MultiResult2<u64, TimelockedAction>> {
+        let actions: Vec<MultiResult2<u64, TimelockedAction>> = self
+            .timelocked_actions()
+            .iter()
+            .map(|(action_id, action)| (action_id, action).into())
+            .collect();
+        MultiResultVec::from_iter(actions)
+    }
+
+    #[view(getTimelockedAction)]
+    fn get_timelocked_action(&self, action_id: u64) -> Option<TimelockedAction> {
+        self.timelocked_actions().get(&action_id)
+    }
+
+    #[storage_mapper("timelocked_actions")]
+    fn timelocked_actions(&self) -> SafeMapMapper<Self::Storage, u64, TimelockedAction>;
+
+    #[storage_mapper("last_timelocked_action_id")]
+    fn last_timelocked_action_id(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getTimelockDelay)]
+    #[storage_mapper("timelock_delay_epochs")]
+    fn timelock_delay_epochs(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getApprovedPairCodeVersion)]
+    #[storage_mapper("approved_pair_code_version")]
+    fn approved_pair_code_version(&self) -> SingleValueMapper<Self::Storage, u64>;
+}
@@ -0,0 +1,149 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use permissions::Role;
+
+use super::events;
+use super::state;
+
+#[elrond_wasm::module]
+pub trait TokenPolicyModule:
+    state::StateModule + events::EventsModule + permissions::PermissionsModule
+{
+    #[endpoint(addQuoteTokens)]
+    fn add_quote_tokens(&self, #[var_args] tokens: VarArgs<TokenIdentifier>) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        for token_id in tokens.into_vec() {
+            require!(
+                token_id.is_valid_esdt_identifier(),
+                "Token ID is not a valid esdt token ID"
+            );
+            let inserted = self.quote_tokens().insert(token_id.clone());
+            self.emit_token_set_change_event(token_id, b"quote_token", !inserted, true);
+        }
+        Ok(())
+    }
+
+    #[endpoint(removeQuoteTokens)]
+    fn remove_quote_tokens(&self, #[var_args] tokens: VarArgs<TokenIdentifier>) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        for token_id in tokens.into_vec() {
+            let removed = self.quote_tokens().remove(&token_id);
+            self.emit_token_set_change_event(token_id, b"quote_token", removed, false);
+        }
+        Ok(())
+    }
+
+    #[endpoint(addDeniedTokens)]
+    fn add_denied_tokens(&self, #[var_args] tokens: VarArgs<TokenIdentifier>) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        for token_id in tokens.into_vec() {
+            let inserted = self.denied_tokens().insert(token_id.clone());
+            self.emit_token_set_change_event(token_id, b"denied_token", !inserted, true);
+        }
+        Ok(())
+    }
+
+    #[endpoint(removeDeniedTokens)]
+    fn remove_denied_tokens(&self, #[var_args] tokens: VarArgs<TokenIdentifier>) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        for token_id in tokens.into_vec() {
+            let removed = self.denied_tokens().remove(&token_id);
+            self.emit_token_set_change_event(token_id, b"denied_token", removed, false);
+        }
+        Ok(())
+    }
+
+    fn emit_token_set_change_event(
+        &self,
+        token_id: TokenIdentifier,
+        name: &[u8],
+        was_in_set: bool,
+        is_in_set: bool,
+    ) {
+        self.emit_token_config_change_event(
+            token_id,
+            name,
+            Self::BigUint::from(was_in_set as u64),
+            Self::BigUint::from(is_in_set as u64),
+        );
+    }
+
+    fn require_allowed_token_pair(
+        &self,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+    ) -> SCResult<()> {
+        // Admins may pair any two tokens that are not denied.
+        let quote_token_required = !self.has_role(Role::Admin, self.blockchain().get_caller());
+        self.check_token_pair_policy(first_token_id, second_token_id, quote_token_required)
+    }
+
+    // Same policy as for pairs, for pools with any number of tokens.
+    fn require_allowed_pool_tokens(&self, token_ids: &[TokenIdentifier]) -> SCResult<()> {
+        require!(
+            token_ids
+                .iter()
+                .all(|token_id| !self.denied_tokens().contains(token_id)),
+            "Token is denied"
+        );
+        if !self.has_role(Role::Admin, self.blockchain().get_caller()) {
+            require!(
+                self.quote_tokens().is_empty()
+                    || token_ids
+                        .iter()
+                        .any(|token_id| self.quote_tokens().contains(token_id)),
+                "One of the tokens must be a quote token"
+            );
+        }
+        Ok(())
+    }
+
+    fn check_token_pair_policy(
+        &self,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+        quote_token_required: bool,
+    ) -> SCResult<()> {
+        require!(
+            !self.denied_tokens().contains(first_token_id)
+                && !self.denied_tokens().contains(second_token_id),
+            "Token is denied"
+        );
+        if quote_token_required {
+            require!(
+                self.quote_tokens().is_empty()
+                    || self.quote_tokens().contains(first_token_id)
+                    || self.quote_tokens().contains(second_token_id),
+                "One of the tokens must be a quote token"
+            );
+        }
+        Ok(())
+    }
+
+    #[view(isTokenPairAllowed)]
+    fn is_token_pair_allowed(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+    ) -> bool {
+        self.check_token_pair_policy(&first_token_id, &second_token_id, true)
+            .is_ok()
+    }
+
+    #[view(getQuoteTokens)]
+    fn get_quote_tokens(&self) -> MultiResultVec<TokenIdentifier> {
+        self.quote_tokens().iter().collect()
+    }
+
+    #[view(getDeniedTokens)]
+    fn get_denied_tokens(&self) -> MultiResultVec<TokenIdentifier> {
+        self.denied_tokens().iter().collect()
+    }
+
+    #[storage_mapper("quote_tokens")]
+    fn quote_tokens(&self) -> SafeSetMapper<Self::Storage, TokenIdentifier>;
+
+    #[storage_mapper("denied_tokens")]
+    fn denied_tokens(&self) -> SafeSetMapper<Self::Storage, TokenIdentifier>;
+}
@@ -0,0 +1,174 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+pub const MIN_POOL_TOKENS: usize = 2;
+pub const MAX_POOL_TOKENS: usize = 8;
+pub const TOTAL_WEIGHT: u64 = 100_000;
+pub const MIN_TOKEN_WEIGHT: u64 = 2_000;
+
+#[derive(TopEncode, TopDecode, PartialEq, TypeAbi)]
+pub enum State {
+    Inactive,
+    Active,
+    ActiveNoSwaps,
+}
+
+#[elrond_wasm::module]
+pub trait ConfigModule: token_send::TokenSendModule {
+    #[endpoint]
+    fn set_transfer_exec_gas_limit(&self, gas_limit: u64) -> SCResult<()> {
+        self.require_permissions()?;
+        self.transfer_exec_gas_limit().set(&gas_limit);
+        Ok(())
+    }
+
+    #[endpoint]
+    fn set_extern_swap_gas_limit(&self, gas_limit: u64) -> SCResult<()> {
+        self.require_permissions()?;
+        self.extern_swap_gas_limit().set(&gas_limit);
+        Ok(())
+    }
+
+    fn require_permissions(&self) -> SCResult<()> {
+        let caller = self.blockchain().get_caller();
+        let owner = self.router_owner_address().get();
+        let router = self.router_address().get();
+        require!(caller == owner || caller == router, "Permission denied");
+        Ok(())
+    }
+
+    #[endpoint]
+    fn pause(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.state().set(&State::Inactive);
+        Ok(())
+    }
+
+    #[endpoint]
+    fn resume(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.state().set(&State::Active);
+        Ok(())
+    }
+
+    #[endpoint(setStateActiveNoSwaps)]
+    fn set_state_active_no_swaps(&self) -> SCResult<()> {
+        self.require_permissions()?;
+        self.state().set(&State::ActiveNoSwaps);
+        Ok(())
+    }
+
+    #[view(getLpTokenIdentifier)]
+    fn get_lp_token_identifier(&self) -> TokenIdentifier {
+        self.lp_token_identifier().get()
+    }
+
+    #[endpoint(setFeePercents)]
+    fn set_fee_percent(&self, total_fee_percent: u64, special_fee_percent: u64) -> SCResult<()> {
+        self.require_permissions()?;
+        self.try_set_fee_percents(total_fee_percent, special_fee_percent)
+    }
+
+    fn try_set_fee_percents(
+        &self,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+    ) -> SCResult<()> {
+        require!(
+            total_fee_percent >= special_fee_percent && total_fee_percent < 100_000,
+            "Bad percents"
+        );
+        self.total_fee_percent().set(&total_fee_percent);
+        self.special_fee_percent().set(&special_fee_percent);
+        Ok(())
+    }
+
+    fn try_set_tokens_and_weights(
+        &self,
+        tokens_and_weights: Vec<(TokenIdentifier, u64)>,
+    ) -> SCResult<()> {
+        require!(
+            tokens_and_weights.len() >= MIN_POOL_TOKENS
+                && tokens_and_weights.len() <= MAX_POOL_TOKENS,
+            "Bad number of tokens"
+        );
+
+        let lp_token_id = self.lp_token_identifier().get();
+        let mut tokens = Vec::with_capacity(tokens_and_weights.len());
+        let mut weights_sum = 0u64;
+        for (token_id, weight) in tokens_and_weights.into_iter() {
+            require!(
+                token_id.is_valid_esdt_identifier(),
+                "Token ID is not a valid ESDT identifier"
+            );
+            require!(token_id != lp_token_id, "Token ID cannot be LP token ID");
+            require!(!tokens.contains(&token_id), "Duplicate token");
+            require!(weight >= MIN_TOKEN_WEIGHT, "Weight too low");
+
+            weights_sum += weight;
+            self.token_weight(&token_id).set(&weight);
+            tokens.push(token_id);
+        }
+        require!(weights_sum == TOTAL_WEIGHT, "Weights should sum up to total");
+
+        self.tokens().set(&tokens);
+        Ok(())
+    }
+
+    #[inline]
+    fn is_pool_token(&self, token_id: &TokenIdentifier) -> bool {
+        self.token_weight(token_id).get() != 0
+    }
+
+    fn require_pool_token(&self, token_id: &TokenIdentifier) -> SCResult<()> {
+        require!(self.is_pool_token(token_id), "Not a pool token");
+        Ok(())
+    }
+
+    #[view(getTokensAndWeights)]
+    fn get_tokens_and_weights(&self) -> MultiResultVec<(TokenIdentifier, u64)> {
+        self.tokens()
+            .get()
+            .into_iter()
+            .map(|token_id| {
+                let weight = self.token_weight(&token_id).get();
+                (token_id, weight)
+            })
+            .collect()
+    }
+
+    #[view(getTotalFeePercent)]
+    #[storage_mapper("total_fee_percent")]
+    fn total_fee_percent(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getSpecialFee)]
+    #[storage_mapper("special_fee_percent")]
+    fn special_fee_percent(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[view(getRouterAddress)]
+    #[storage_mapper("router_address")]
+    fn router_address(&self) -> SingleValueMapper<Self::Storage, Address>;
+
+    #[view(getRouterOwnerAddress)]
+    #[storage_mapper("router_owner_address")]
+    fn router_owner_address(&self) -> SingleValueMapper<Self::Storage, Address>;
+
+    #[view(getState)]
+    #[storage_mapper("state")]
+    fn state(&self) -> SingleValueMapper<Self::Storage, State>;
+
+    #[view(getExternSwapGasLimit)]
+    #[storage_mapper("extern_swap_gas_limit")]
+    fn extern_swap_gas_limit(&self) -> SingleValueMapper<Self::Storage, u64>;
+
+    #[storage_mapper("lpTokenIdentifier")]
+    fn lp_token_identifier(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[view(getTokens)]
+    #[storage_mapper("tokens")]
+    fn tokens(&self) -> SingleValueMapper<Self::Storage, Vec<TokenIdentifier>>;
+
+    #[view(getTokenWeight)]
+    #[storage_mapper("token_weight")]
+    fn token_weight(&self, token_id: &TokenIdentifier) -> SingleValueMapper<Self::Storage, u64>;
+}
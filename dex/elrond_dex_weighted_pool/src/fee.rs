@@ -0,0 +1,348 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use super::config;
+use super::liquidity_pool;
+use super::math;
+use common_structs::TokenPair;
+use core::iter::FromIterator;
+
+const SWAP_NO_FEE_AND_FORWARD_FUNC_NAME: &[u8] = b"swapNoFeeAndForward";
+
+mod farm_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait Farm {
+        #[payable("*")]
+        #[endpoint(acceptFee)]
+        fn accept_fee(
+            &self,
+            #[payment_token] token_in: TokenIdentifier,
+            #[payment_amount] amount: Self::BigUint,
+        );
+    }
+}
+
+#[elrond_wasm::module]
+pub trait FeeModule:
+    config::ConfigModule
+    + liquidity_pool::LiquidityPoolModule
+    + math::WeightedMathModule
+    + token_supply::TokenSupplyModule
+    + token_send::TokenSendModule
+{
+    #[proxy]
+    fn farm_proxy(&self, to: Address) -> farm_proxy::Proxy<Self::SendApi>;
+
+    #[storage_mapper("fee_destination")]
+    fn destination_map(&self) -> SafeMapMapper<Self::Storage, Address, TokenIdentifier>;
+
+    #[storage_mapper("trusted_swap_pair")]
+    fn trusted_swap_pair(&self) -> SafeMapMapper<Self::Storage, TokenPair, Address>;
+
+    #[view(getWhitelistedAddresses)]
+    #[storage_mapper("whitelist")]
+    fn whitelist(&self) -> SafeSetMapper<Self::Storage, Address>;
+
+    #[view(getFeeState)]
+    fn is_fee_enabled(&self) -> bool {
+        !self.destination_map().is_empty()
+    }
+
+    #[endpoint(whitelist)]
+    fn whitelist_endpoint(&self, address: Address) -> SCResult<()> {
+        self.require_permissions()?;
+        let is_new = self.whitelist().insert(address);
+        require!(is_new, "Address already whitelisted");
+        Ok(())
+    }
+
+    #[endpoint(removeWhitelist)]
+    fn remove_whitelist(&self, address: Address) -> SCResult<()> {
+        self.require_permissions()?;
+        let is_removed = self.whitelist().remove(&address);
+        require!(is_removed, "Addresss not whitelisted");
+        Ok(())
+    }
+
+    #[endpoint(addTrustedSwapPair)]
+    fn add_trusted_swap_pair(
+        &self,
+        pair_address: Address,
+        first_token: TokenIdentifier,
+        second_token: TokenIdentifier,
+    ) -> SCResult<()> {
+        self.require_permissions()?;
+        require!(first_token != second_token, "Tokens should differ");
+        require!(
+            self.get_extern_swap_pair_address(&first_token, &second_token) == Address::zero(),
+            "Pair already trusted"
+        );
+        let token_pair = TokenPair {
+            first_token,
+            second_token,
+        };
+        self.trusted_swap_pair().insert(token_pair, pair_address);
+        Ok(())
+    }
+
+    #[endpoint(removeTrustedSwapPair)]
+    fn remove_trusted_swap_pair(
+        &self,
+        first_token: TokenIdentifier,
+        second_token: TokenIdentifier,
+    ) -> SCResult<()> {
+        self.require_permissions()?;
+        let token_pair = TokenPair {
+            first_token: first_token.clone(),
+            second_token: second_token.clone(),
+        };
+
+        let mut is_removed = self.trusted_swap_pair().remove(&token_pair).is_some();
+        if !is_removed {
+            let token_pair_reversed = TokenPair {
+                first_token: second_token,
+                second_token: first_token,
+            };
+            is_removed = self
+                .trusted_swap_pair()
+                .remove(&token_pair_reversed)
+                .is_some();
+            require!(is_removed, "Pair does not exist in trusted pair map");
+        }
+        Ok(())
+    }
+
+    #[endpoint(setFeeOn)]
+    fn set_fee_on(
+        &self,
+        enabled: bool,
+        fee_to_address: Address,
+        fee_token: TokenIdentifier,
+    ) -> SCResult<()> {
+        self.require_permissions()?;
+        let dest_fee_token = self.destination_map().get(&fee_to_address);
+
+        if enabled {
+            require!(dest_fee_token.is_none(), "Is already a fee destination");
+            self.destination_map().insert(fee_to_address, fee_token);
+        } else {
+            require!(dest_fee_token.is_some(), "Is not a fee destination");
+            require!(
+                Some(fee_token) == dest_fee_token,
+                "Destination fee token differs"
+            );
+            self.destination_map().remove(&fee_to_address);
+        }
+        Ok(())
+    }
+
+    fn reinject(&self, token: &TokenIdentifier, amount: &Self::BigUint) {
+        self.increase_token_reserve(token, amount);
+    }
+
+    fn send_fee(&self, fee_token: &TokenIdentifier, fee_amount: &Self::BigUint) {
+        if fee_amount == &0 {
+            return;
+        }
+
+        let slices = self.destination_map().len() as u64;
+        if slices == 0 {
+            self.reinject(fee_token, fee_amount);
+            return;
+        }
+
+        let fee_slice = fee_amount / &slices.into();
+        if fee_slice == 0 {
+            self.reinject(fee_token, fee_amount);
+            return;
+        }
+
+        for (fee_address, fee_token_requested) in self.destination_map().iter() {
+            self.send_fee_slice(fee_token, &fee_slice, &fee_address, &fee_token_requested);
+        }
+
+        let rounding_error = fee_amount - &(fee_slice * slices.into());
+        if rounding_error > 0 {
+            self.reinject(fee_token, &rounding_error);
+        }
+    }
+
+    fn send_fee_slice(
+        &self,
+        fee_token: &TokenIdentifier,
+        fee_slice: &Self::BigUint,
+        fee_address: &Address,
+        requested_fee_token: &TokenIdentifier,
+    ) {
+        if fee_token == requested_fee_token {
+            self.send_fee_or_burn_on_zero_address(fee_token, fee_slice, fee_address);
+        } else if self.is_pool_token(requested_fee_token) {
+            let to_send = self.swap_safe_no_fee(fee_token, requested_fee_token, fee_slice);
+            if to_send > 0 {
+                self.send_fee_or_burn_on_zero_address(requested_fee_token, &to_send, fee_address);
+            } else {
+                self.reinject(fee_token, fee_slice);
+            }
+        } else if self.get_extern_swap_pair_address(fee_token, requested_fee_token)
+            != Address::zero()
+        {
+            let resolved_externally = self.extern_swap_and_forward(
+                fee_token,
+                fee_slice,
+                requested_fee_token,
+                fee_address,
+            );
+            if !resolved_externally {
+                self.reinject(fee_token, fee_slice);
+            }
+        } else {
+            match self.find_intermediate_token(fee_token, requested_fee_token) {
+                Some(intermediate_token) => self.send_fee_slice_through_intermediate(
+                    fee_token,
+                    fee_slice,
+                    &intermediate_token,
+                    fee_address,
+                    requested_fee_token,
+                ),
+                None => self.reinject(fee_token, fee_slice),
+            }
+        }
+    }
+
+    fn find_intermediate_token(
+        &self,
+        fee_token: &TokenIdentifier,
+        requested_fee_token: &TokenIdentifier,
+    ) -> Option<TokenIdentifier> {
+        self.tokens().get().into_iter().find(|token_id| {
+            token_id != fee_token
+                && self.get_extern_swap_pair_address(token_id, requested_fee_token)
+                    != Address::zero()
+        })
+    }
+
+    fn send_fee_slice_through_intermediate(
+        &self,
+        fee_token: &TokenIdentifier,
+        fee_slice: &Self::BigUint,
+        intermediate_token: &TokenIdentifier,
+        fee_address: &Address,
+        requested_fee_token: &TokenIdentifier,
+    ) {
+        let fee_token_reserve = self.pool_reserve(fee_token).get();
+        let intermediate_token_reserve = self.pool_reserve(intermediate_token).get();
+
+        let to_send = self.swap_safe_no_fee(fee_token, intermediate_token, fee_slice);
+        if to_send == 0 {
+            self.reinject(fee_token, fee_slice);
+            return;
+        }
+
+        let resolved_externally = self.extern_swap_and_forward(
+            intermediate_token,
+            &to_send,
+            requested_fee_token,
+            fee_address,
+        );
+        if !resolved_externally {
+            //Revert the previous local swap
+            self.pool_reserve(fee_token).set(&fee_token_reserve);
+            self.pool_reserve(intermediate_token)
+                .set(&intermediate_token_reserve);
+            self.reinject(fee_token, fee_slice);
+        }
+    }
+
+    fn extern_swap_and_forward(
+        &self,
+        available_token: &TokenIdentifier,
+        available_amount: &Self::BigUint,
+        requested_token: &TokenIdentifier,
+        destination_address: &Address,
+    ) -> bool {
+        let pair_address = self.get_extern_swap_pair_address(available_token, requested_token);
+        let mut arg_buffer = ArgBuffer::new();
+        arg_buffer.push_argument_bytes(requested_token.as_esdt_identifier());
+        arg_buffer.push_argument_bytes(destination_address.as_bytes());
+        let result = self.send().direct_esdt_execute(
+            &pair_address,
+            available_token,
+            available_amount,
+            self.extern_swap_gas_limit().get(),
+            SWAP_NO_FEE_AND_FORWARD_FUNC_NAME,
+            &arg_buffer,
+        );
+
+        match result {
+            Result::Ok(()) => true,
+            Result::Err(_) => false,
+        }
+    }
+
+    #[inline]
+    fn send_fee_or_burn_on_zero_address(
+        &self,
+        token: &TokenIdentifier,
+        amount: &Self::BigUint,
+        destination: &Address,
+    ) {
+        if amount > &0 {
+            if destination == &Address::zero() {
+                self.burn_tokens(token, amount);
+            } else {
+                self.farm_proxy(destination.clone())
+                    .accept_fee(token.clone(), amount.clone())
+                    .execute_on_dest_context();
+            }
+        }
+    }
+
+    fn get_extern_swap_pair_address(
+        &self,
+        first_token: &TokenIdentifier,
+        second_token: &TokenIdentifier,
+    ) -> Address {
+        let token_pair = TokenPair {
+            first_token: first_token.clone(),
+            second_token: second_token.clone(),
+        };
+        if let Some(address) = self.trusted_swap_pair().get(&token_pair) {
+            return address;
+        }
+
+        let token_pair_reversed = TokenPair {
+            first_token: second_token.clone(),
+            second_token: first_token.clone(),
+        };
+        self.trusted_swap_pair()
+            .get(&token_pair_reversed)
+            .unwrap_or_else(Address::zero)
+    }
+
+    fn require_whitelisted(&self, caller: &Address) -> SCResult<()> {
+        require!(self.whitelist().contains(caller), "Not whitelisted");
+        Ok(())
+    }
+
+    #[view(getFeeDestinations)]
+    fn get_fee_destinations(&self) -> MultiResultVec<(Address, TokenIdentifier)> {
+        MultiResultVec::from_iter(
+            self.destination_map()
+                .iter()
+                .map(|x| (x.0, x.1))
+                .collect::<Vec<(Address, TokenIdentifier)>>(),
+        )
+    }
+
+    #[view(getTrustedSwapPairs)]
+    fn get_trusted_swap_pairs(&self) -> MultiResultVec<(TokenPair, Address)> {
+        MultiResultVec::from_iter(
+            self.trusted_swap_pair()
+                .iter()
+                .map(|x| (x.0, x.1))
+                .collect::<Vec<(TokenPair, Address)>>(),
+        )
+    }
+}
@@ -0,0 +1,637 @@
+#![no_std]
+
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+const DEFAULT_TRANSFER_EXEC_GAS_LIMIT: u64 = 35000000;
+const DEFAULT_EXTERN_SWAP_GAS_LIMIT: u64 = 50000000;
+
+pub mod config;
+mod events;
+pub mod fee;
+mod liquidity_pool;
+pub mod math;
+
+use common_structs::FftTokenAmountPair;
+use config::State;
+
+type AddLiquidityResultType<BigUint> = MultiResultVec<FftTokenAmountPair<BigUint>>;
+
+type RemoveLiquidityResultType<BigUint> = MultiResultVec<FftTokenAmountPair<BigUint>>;
+
+type SwapTokensFixedInputResultType<BigUint> = FftTokenAmountPair<BigUint>;
+
+type SwapTokensFixedOutputResultType<BigUint> =
+    MultiResult2<FftTokenAmountPair<BigUint>, FftTokenAmountPair<BigUint>>;
+
+#[elrond_wasm::contract]
+pub trait WeightedPool:
+    fee::FeeModule
+    + liquidity_pool::LiquidityPoolModule
+    + config::ConfigModule
+    + math::WeightedMathModule
+    + token_supply::TokenSupplyModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+{
+    #[init]
+    fn init(
+        &self,
+        router_address: Address,
+        router_owner_address: Address,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+        #[var_args] tokens_and_weights: MultiArgVec<MultiArg2<TokenIdentifier, u64>>,
+    ) -> SCResult<()> {
+        self.try_set_fee_percents(total_fee_percent, special_fee_percent)?;
+        self.try_set_tokens_and_weights(
+            tokens_and_weights
+                .into_vec()
+                .into_iter()
+                .map(|entry| entry.into_tuple())
+                .collect(),
+        )?;
+
+        self.state().set_if_empty(&State::ActiveNoSwaps);
+        self.transfer_exec_gas_limit()
+            .set_if_empty(&DEFAULT_TRANSFER_EXEC_GAS_LIMIT);
+        self.extern_swap_gas_limit()
+            .set_if_empty(&DEFAULT_EXTERN_SWAP_GAS_LIMIT);
+
+        self.router_address().set(&router_address);
+        self.router_owner_address().set(&router_owner_address);
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(acceptEsdtPayment)]
+    fn accept_esdt_payment(
+        &self,
+        #[payment_token] token: TokenIdentifier,
+        #[payment_amount] payment: Self::BigUint,
+    ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
+        require!(
+            self.call_value().esdt_token_nonce() == 0,
+            "Only fungible tokens are accepted in liquidity pools"
+        );
+        require!(payment > 0, "Payment amount cannot be zero");
+        self.require_pool_token(&token)?;
+
+        let caller = self.blockchain().get_caller();
+        let mut temporary_funds = self.temporary_funds(&caller, &token).get();
+        temporary_funds += payment;
+        self.temporary_funds(&caller, &token).set(&temporary_funds);
+
+        Ok(())
+    }
+
+    #[endpoint(addLiquidity)]
+    fn add_liquidity(
+        &self,
+        lp_amount_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<AddLiquidityResultType<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        require!(
+            !self.lp_token_identifier().is_empty(),
+            "LP token not issued"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let tokens = self.tokens().get();
+        let mut amounts_desired = Vec::with_capacity(tokens.len());
+        for token_id in tokens.iter() {
+            let amount = self.temporary_funds(&caller, token_id).get();
+            require!(amount > 0, "No available funds for one of the pool tokens");
+            amounts_desired.push(FftTokenAmountPair {
+                token_id: token_id.clone(),
+                amount,
+            });
+        }
+
+        let (liquidity, amounts_used) = self.pool_add_liquidity(&amounts_desired)?;
+        require!(liquidity >= lp_amount_min, "Insufficient liquidity minted");
+
+        let lp_token_id = self.lp_token_identifier().get();
+        self.mint_tokens(&lp_token_id, &liquidity);
+        self.send_tokens(&lp_token_id, &liquidity, &caller, &opt_accept_funds_func)?;
+
+        for (desired, used) in amounts_desired.iter().zip(amounts_used.iter()) {
+            self.temporary_funds(&caller, &desired.token_id).clear();
+            let unused = &desired.amount - &used.amount;
+            self.send_tokens(&desired.token_id, &unused, &caller, &opt_accept_funds_func)?;
+        }
+
+        let lp_token_amount = FftTokenAmountPair {
+            token_id: lp_token_id,
+            amount: liquidity,
+        };
+        self.emit_add_liquidity_event(
+            caller,
+            amounts_used.clone(),
+            lp_token_amount.clone(),
+            self.get_total_lp_token_supply(),
+            self.get_all_reserves(),
+        );
+
+        let mut result = Vec::with_capacity(amounts_used.len() + 1);
+        result.push(lp_token_amount);
+        result.extend(amounts_used);
+        Ok(result.into())
+    }
+
+    #[payable("*")]
+    #[endpoint(addSingleTokenLiquidity)]
+    fn add_single_token_liquidity(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in: Self::BigUint,
+        lp_amount_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<FftTokenAmountPair<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        require!(amount_in > 0, "Payment amount cannot be zero");
+        require!(
+            !self.lp_token_identifier().is_empty(),
+            "LP token not issued"
+        );
+        self.require_pool_token(&token_in)?;
+
+        let liquidity = self.pool_add_single_token_liquidity(&token_in, &amount_in)?;
+        require!(liquidity >= lp_amount_min, "Insufficient liquidity minted");
+
+        let mut fee_amount = 0u64.into();
+        let mut amount_in_after_fee = amount_in.clone();
+        if self.is_fee_enabled() {
+            fee_amount = self.get_special_fee_from_single_token_amount(&token_in, &amount_in);
+            amount_in_after_fee -= &fee_amount;
+        }
+        self.increase_token_reserve(&token_in, &amount_in_after_fee);
+
+        //Same as for swaps: the special fee of the implicit swap goes to the fee destinations.
+        if self.is_fee_enabled() {
+            self.send_fee(&token_in, &fee_amount);
+        }
+
+        let caller = self.blockchain().get_caller();
+        let lp_token_id = self.lp_token_identifier().get();
+        self.mint_tokens(&lp_token_id, &liquidity);
+        self.send_tokens(&lp_token_id, &liquidity, &caller, &opt_accept_funds_func)?;
+
+        let lp_token_amount = FftTokenAmountPair {
+            token_id: lp_token_id,
+            amount: liquidity,
+        };
+        let token_amount_in = FftTokenAmountPair {
+            token_id: token_in,
+            amount: amount_in,
+        };
+        self.emit_add_liquidity_event(
+            caller,
+            [token_amount_in].to_vec(),
+            lp_token_amount.clone(),
+            self.get_total_lp_token_supply(),
+            self.get_all_reserves(),
+        );
+        Ok(lp_token_amount)
+    }
+
+    fn reclaim_temporary_token(
+        &self,
+        caller: &Address,
+        token: &TokenIdentifier,
+        opt_accept_funds_func: &OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        let amount = self.temporary_funds(caller, token).get();
+        self.temporary_funds(caller, token).clear();
+        self.send_tokens(token, &amount, caller, opt_accept_funds_func)?;
+        Ok(())
+    }
+
+    #[endpoint(reclaimTemporaryFunds)]
+    fn reclaim_temporary_funds(
+        &self,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        let caller = self.blockchain().get_caller();
+        for token_id in self.tokens().get().iter() {
+            self.reclaim_temporary_token(&caller, token_id, &opt_accept_funds_func)?;
+        }
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(removeLiquidity)]
+    fn remove_liquidity(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] liquidity: Self::BigUint,
+        #[var_args] amounts_min: VarArgs<Self::BigUint>,
+    ) -> SCResult<RemoveLiquidityResultType<Self::BigUint>> {
+        require!(
+            !self.lp_token_identifier().is_empty(),
+            "LP token not issued"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let lp_token_id = self.lp_token_identifier().get();
+        require!(token_id == lp_token_id, "Wrong liquidity token");
+
+        let amounts_out = self.pool_remove_liquidity(&liquidity, amounts_min.as_slice())?;
+        for amount_out in amounts_out.iter() {
+            self.send_tokens(
+                &amount_out.token_id,
+                &amount_out.amount,
+                &caller,
+                &OptionalArg::None,
+            )?;
+        }
+        self.burn_tokens(&token_id, &liquidity);
+
+        let lp_token_amount = FftTokenAmountPair {
+            token_id: lp_token_id,
+            amount: liquidity,
+        };
+        self.emit_remove_liquidity_event(
+            caller,
+            amounts_out.clone(),
+            lp_token_amount,
+            self.get_total_lp_token_supply(),
+            self.get_all_reserves(),
+        );
+        Ok(amounts_out.into())
+    }
+
+    #[payable("*")]
+    #[endpoint(removeLiquiditySingleToken)]
+    fn remove_liquidity_single_token(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] liquidity: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<FftTokenAmountPair<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        require!(
+            !self.lp_token_identifier().is_empty(),
+            "LP token not issued"
+        );
+        let lp_token_id = self.lp_token_identifier().get();
+        require!(token_id == lp_token_id, "Wrong liquidity token");
+        self.require_pool_token(&token_out)?;
+
+        let (amount_out, amount_out_before_fee) =
+            self.pool_remove_single_token_liquidity(&token_out, &liquidity)?;
+        require!(
+            amount_out >= amount_out_min,
+            "Computed amount out lesser than minimum amount out"
+        );
+
+        let mut fee_amount = 0u64.into();
+        if self.is_fee_enabled() {
+            fee_amount =
+                self.get_special_fee_from_single_token_amount(&token_out, &amount_out_before_fee);
+        }
+        self.try_decrease_token_reserve(&token_out, &(&amount_out + &fee_amount))?;
+
+        let caller = self.blockchain().get_caller();
+        self.burn_tokens(&token_id, &liquidity);
+        if self.is_fee_enabled() {
+            self.send_fee(&token_out, &fee_amount);
+        }
+        self.send_tokens(&token_out, &amount_out, &caller, &opt_accept_funds_func)?;
+
+        let lp_token_amount = FftTokenAmountPair {
+            token_id: lp_token_id,
+            amount: liquidity,
+        };
+        let token_amount_out = FftTokenAmountPair {
+            token_id: token_out,
+            amount: amount_out,
+        };
+        self.emit_remove_liquidity_event(
+            caller,
+            [token_amount_out.clone()].to_vec(),
+            lp_token_amount,
+            self.get_total_lp_token_supply(),
+            self.get_all_reserves(),
+        );
+        Ok(token_amount_out)
+    }
+
+    #[payable("*")]
+    #[endpoint(swapNoFeeAndForward)]
+    fn swap_no_fee(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in: Self::BigUint,
+        token_out: TokenIdentifier,
+        destination_address: Address,
+    ) -> SCResult<()> {
+        let caller = self.blockchain().get_caller();
+        self.require_whitelisted(&caller)?;
+
+        require!(self.can_swap(), "Swap is not enabled");
+        require!(amount_in > 0, "Zero input");
+        require!(token_in != token_out, "Cannot swap same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+
+        let amount_out = self.swap_safe_no_fee(&token_in, &token_out, &amount_in);
+        require!(amount_out > 0, "Zero output");
+
+        self.send_fee_or_burn_on_zero_address(&token_out, &amount_out, &destination_address);
+
+        let swap_out_token_amount = FftTokenAmountPair {
+            token_id: token_out,
+            amount: amount_out,
+        };
+        self.emit_swap_no_fee_and_forward_event(caller, swap_out_token_amount, destination_address);
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInput)]
+    fn swap_tokens_fixed_input(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedInputResultType<Self::BigUint>> {
+        require!(self.can_swap(), "Swap is not enabled");
+        require!(amount_in > 0, "Invalid amount_in");
+        require!(token_in != token_out, "Swap with same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+
+        let spot_price_before = self.compute_spot_price(&token_in, &token_out);
+        let amount_out = self.compute_amount_out(
+            &token_in,
+            &token_out,
+            &amount_in,
+            self.total_fee_percent().get(),
+        )?;
+        require!(
+            amount_out >= amount_out_min,
+            "Computed amount out lesser than minimum amount out"
+        );
+        require!(amount_out != 0, "Optimal value is zero");
+
+        let caller = self.blockchain().get_caller();
+
+        let mut fee_amount = 0u64.into();
+        let mut amount_in_after_fee = amount_in.clone();
+        if self.is_fee_enabled() {
+            fee_amount = self.get_special_fee_from_input(&amount_in);
+            amount_in_after_fee -= &fee_amount;
+        }
+
+        self.try_decrease_token_reserve(&token_out, &amount_out)?;
+        self.increase_token_reserve(&token_in, &amount_in_after_fee);
+        self.validate_spot_price(&token_in, &token_out, &spot_price_before)?;
+
+        //The transaction was made. We are left with $(fee) of $(token_in) as fee.
+        if self.is_fee_enabled() {
+            self.send_fee(&token_in, &fee_amount);
+        }
+        self.send_tokens(&token_out, &amount_out, &caller, &opt_accept_funds_func)?;
+
+        let token_amount_in = FftTokenAmountPair {
+            token_id: token_in,
+            amount: amount_in,
+        };
+        let token_amount_out = FftTokenAmountPair {
+            token_id: token_out,
+            amount: amount_out,
+        };
+        self.emit_swap_event(
+            caller,
+            token_amount_in,
+            token_amount_out.clone(),
+            fee_amount,
+            self.get_all_reserves(),
+        );
+        Ok(token_amount_out)
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedOutput)]
+    fn swap_tokens_fixed_output(
+        &self,
+        #[payment_token] token_in: TokenIdentifier,
+        #[payment_amount] amount_in_max: Self::BigUint,
+        token_out: TokenIdentifier,
+        amount_out: Self::BigUint,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<SwapTokensFixedOutputResultType<Self::BigUint>> {
+        require!(self.can_swap(), "Swap is not enabled");
+        require!(amount_in_max > 0, "Invalid amount_in");
+        require!(token_in != token_out, "Invalid swap with same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+        require!(amount_out != 0, "Desired amount out cannot be zero");
+
+        let spot_price_before = self.compute_spot_price(&token_in, &token_out);
+        let amount_in_optimal = self.compute_amount_in(
+            &token_in,
+            &token_out,
+            &amount_out,
+            self.total_fee_percent().get(),
+        )?;
+        require!(
+            amount_in_optimal <= amount_in_max,
+            "Computed amount in greater than maximum amount in"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let residuum = &amount_in_max - &amount_in_optimal;
+
+        let mut fee_amount = 0u64.into();
+        let mut amount_in_optimal_after_fee = amount_in_optimal.clone();
+        if self.is_fee_enabled() {
+            fee_amount = self.get_special_fee_from_input(&amount_in_optimal);
+            amount_in_optimal_after_fee -= &fee_amount;
+        }
+
+        self.try_decrease_token_reserve(&token_out, &amount_out)?;
+        self.increase_token_reserve(&token_in, &amount_in_optimal_after_fee);
+        self.validate_spot_price(&token_in, &token_out, &spot_price_before)?;
+
+        //The transaction was made. We are left with $(fee) of $(token_in) as fee.
+        if self.is_fee_enabled() {
+            self.send_fee(&token_in, &fee_amount);
+        }
+
+        self.send_tokens(&token_out, &amount_out, &caller, &opt_accept_funds_func)?;
+        self.send_tokens(&token_in, &residuum, &caller, &opt_accept_funds_func)?;
+
+        let token_amount_in = FftTokenAmountPair {
+            token_id: token_in.clone(),
+            amount: amount_in_optimal,
+        };
+        let token_amount_out = FftTokenAmountPair {
+            token_id: token_out,
+            amount: amount_out,
+        };
+        let residuum_token_amount = FftTokenAmountPair {
+            token_id: token_in,
+            amount: residuum,
+        };
+        self.emit_swap_event(
+            caller,
+            token_amount_in,
+            token_amount_out.clone(),
+            fee_amount,
+            self.get_all_reserves(),
+        );
+        Ok((token_amount_out, residuum_token_amount).into())
+    }
+
+    fn get_special_fee_from_input(&self, amount_in: &Self::BigUint) -> Self::BigUint {
+        amount_in * &self.special_fee_percent().get().into() / 100000u64.into()
+    }
+
+    // Single token joins and exits only pay fees on the part that is implicitly swapped.
+    fn get_special_fee_from_single_token_amount(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: &Self::BigUint,
+    ) -> Self::BigUint {
+        let swapped_share = &self.bone() - &self.get_normalized_weight(token_id);
+        let special_fee = self.fee_percent_to_bone(self.special_fee_percent().get());
+        self.bmul(amount, &self.bmul(&swapped_share, &special_fee))
+    }
+
+    // A swap should never make the token in cheaper relative to the token out.
+    fn validate_spot_price(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        spot_price_before: &Self::BigUint,
+    ) -> SCResult<()> {
+        let spot_price_after = self.compute_spot_price(token_in, token_out);
+        require!(
+            &spot_price_after >= spot_price_before,
+            "Spot price invariant failed"
+        );
+        Ok(())
+    }
+
+    fn send_tokens(
+        &self,
+        token: &TokenIdentifier,
+        amount: &Self::BigUint,
+        destination: &Address,
+        opt_accept_funds_func: &OptionalArg<BoxedBytes>,
+    ) -> SCResult<()> {
+        if amount > &0 {
+            self.send_fft_tokens(token, amount, destination, opt_accept_funds_func)?;
+        }
+        Ok(())
+    }
+
+    #[endpoint(setLpTokenIdentifier)]
+    fn set_lp_token_identifier(&self, token_identifier: TokenIdentifier) -> SCResult<()> {
+        self.require_permissions()?;
+        require!(self.lp_token_identifier().is_empty(), "LP token not empty");
+        require!(
+            !self.is_pool_token(&token_identifier),
+            "LP token should differ from the pool tokens"
+        );
+        require!(
+            token_identifier.is_valid_esdt_identifier(),
+            "Provided identifier is not a valid ESDT identifier"
+        );
+
+        self.lp_token_identifier().set(&token_identifier);
+
+        Ok(())
+    }
+
+    #[view(getTokensForGivenPosition)]
+    fn get_tokens_for_given_position(
+        &self,
+        liquidity: Self::BigUint,
+    ) -> MultiResultVec<FftTokenAmountPair<Self::BigUint>> {
+        self.get_tokens_for_position(&liquidity).into()
+    }
+
+    #[view(getReserves)]
+    fn get_reserves(&self) -> MultiResultVec<FftTokenAmountPair<Self::BigUint>> {
+        self.get_all_reserves().into()
+    }
+
+    #[view(getAmountOut)]
+    fn get_amount_out_view(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+        amount_in: Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        require!(amount_in > 0, "Zero input");
+        require!(token_in != token_out, "Swap with same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+
+        self.compute_amount_out(
+            &token_in,
+            &token_out,
+            &amount_in,
+            self.total_fee_percent().get(),
+        )
+    }
+
+    #[view(getAmountIn)]
+    fn get_amount_in_view(
+        &self,
+        token_in: TokenIdentifier,
+        token_wanted: TokenIdentifier,
+        amount_wanted: Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        require!(amount_wanted > 0, "Zero input");
+        require!(token_in != token_wanted, "Swap with same token");
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_wanted)?;
+
+        self.compute_amount_in(
+            &token_in,
+            &token_wanted,
+            &amount_wanted,
+            self.total_fee_percent().get(),
+        )
+    }
+
+    #[view(getSpotPrice)]
+    fn get_spot_price(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+    ) -> SCResult<Self::BigUint> {
+        self.require_pool_token(&token_in)?;
+        self.require_pool_token(&token_out)?;
+        Ok(self.compute_spot_price(&token_in, &token_out))
+    }
+
+    #[inline]
+    fn is_active(&self) -> bool {
+        let state = self.state().get();
+        state == State::Active || state == State::ActiveNoSwaps
+    }
+
+    #[inline]
+    fn can_swap(&self) -> bool {
+        self.state().get() == State::Active
+    }
+
+    #[view(getTemporaryFunds)]
+    #[storage_mapper("funds")]
+    fn temporary_funds(
+        &self,
+        caller: &Address,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+}
@@ -0,0 +1,352 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use super::config;
+use super::math;
+use common_structs::FftTokenAmountPair;
+
+use config::TOTAL_WEIGHT;
+use math::{MAX_IN_RATIO, MAX_OUT_RATIO};
+
+const MINIMUM_LIQUIDITY: u64 = 1_000;
+const INITIAL_POOL_SUPPLY_UNITS: u64 = 100;
+
+type PoolAddLiquidityResult<BigUint> = (BigUint, Vec<FftTokenAmountPair<BigUint>>);
+
+#[elrond_wasm::module]
+pub trait LiquidityPoolModule:
+    config::ConfigModule
+    + math::WeightedMathModule
+    + token_supply::TokenSupplyModule
+    + token_send::TokenSendModule
+{
+    fn pool_add_liquidity(
+        &self,
+        amounts_desired: &[FftTokenAmountPair<Self::BigUint>],
+    ) -> SCResult<PoolAddLiquidityResult<Self::BigUint>> {
+        let total_supply = self.get_total_lp_token_supply();
+        let mut liquidity: Self::BigUint;
+        let mut amounts_used = Vec::with_capacity(amounts_desired.len());
+
+        if total_supply == 0 {
+            // The initial supply does not depend on the deposited value, since
+            // the weights alone define the initial prices.
+            for amount_desired in amounts_desired.iter() {
+                require!(amount_desired.amount > 0, "Initial amounts cannot be zero");
+                amounts_used.push(amount_desired.clone());
+            }
+
+            let minimum_liquidity = Self::BigUint::from(MINIMUM_LIQUIDITY);
+            liquidity = Self::BigUint::from(INITIAL_POOL_SUPPLY_UNITS) * self.bone();
+            liquidity -= &minimum_liquidity;
+            self.mint_tokens(&self.lp_token_identifier().get(), &minimum_liquidity);
+        } else {
+            liquidity = Self::BigUint::zero();
+            for (index, amount_desired) in amounts_desired.iter().enumerate() {
+                let reserve = self.pool_reserve(&amount_desired.token_id).get();
+                require!(reserve > 0, "Zero reserve");
+                let token_liquidity = &(&amount_desired.amount * &total_supply) / &reserve;
+                if index == 0 || token_liquidity < liquidity {
+                    liquidity = token_liquidity;
+                }
+            }
+            require!(liquidity > 0, "Insufficient liquidity minted");
+
+            // Round up the amounts taken from the user, never the ones given to him.
+            let one = Self::BigUint::from(1u64);
+            for amount_desired in amounts_desired.iter() {
+                let reserve = self.pool_reserve(&amount_desired.token_id).get();
+                let numerator = &(&liquidity * &reserve) + &total_supply;
+                let amount = (&numerator - &one) / total_supply.clone();
+                require!(
+                    amount <= amount_desired.amount,
+                    "Not enough tokens provided"
+                );
+                amounts_used.push(FftTokenAmountPair {
+                    token_id: amount_desired.token_id.clone(),
+                    amount,
+                });
+            }
+        }
+        require!(liquidity > 0, "Insufficient liquidity minted");
+
+        for amount_used in amounts_used.iter() {
+            self.increase_token_reserve(&amount_used.token_id, &amount_used.amount);
+        }
+
+        Ok((liquidity, amounts_used))
+    }
+
+    fn pool_remove_liquidity(
+        &self,
+        liquidity: &Self::BigUint,
+        amounts_min: &[Self::BigUint],
+    ) -> SCResult<Vec<FftTokenAmountPair<Self::BigUint>>> {
+        let total_supply = self.get_total_lp_token_supply();
+        require!(
+            total_supply >= liquidity + &MINIMUM_LIQUIDITY.into(),
+            "Not enough LP token supply"
+        );
+
+        let tokens = self.tokens().get();
+        require!(
+            amounts_min.len() == tokens.len(),
+            "Bad minimum amounts length"
+        );
+
+        let mut amounts_out = Vec::with_capacity(tokens.len());
+        for (token_id, amount_min) in tokens.into_iter().zip(amounts_min.iter()) {
+            let mut reserve = self.pool_reserve(&token_id).get();
+            let amount = &(liquidity * &reserve) / &total_supply;
+            require!(amount > 0, "Insufficient liquidity burned");
+            require!(&amount >= amount_min, "Insufficient liquidity burned");
+            require!(reserve > amount, "Not enough reserve");
+
+            reserve -= &amount;
+            self.pool_reserve(&token_id).set(&reserve);
+            amounts_out.push(FftTokenAmountPair { token_id, amount });
+        }
+
+        Ok(amounts_out)
+    }
+
+    fn pool_add_single_token_liquidity(
+        &self,
+        token_in: &TokenIdentifier,
+        amount_in: &Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        let total_supply = self.get_total_lp_token_supply();
+        require!(total_supply > 0, "Pool has no liquidity");
+
+        let reserve_in = self.pool_reserve(token_in).get();
+        require!(
+            amount_in <= &self.bmul(&reserve_in, &MAX_IN_RATIO.into()),
+            "Amount in exceeds maximum ratio"
+        );
+
+        let liquidity = self.calc_pool_out_given_single_in(
+            &reserve_in,
+            &self.get_normalized_weight(token_in),
+            &total_supply,
+            amount_in,
+            &self.fee_percent_to_bone(self.total_fee_percent().get()),
+        )?;
+        require!(liquidity > 0, "Insufficient liquidity minted");
+
+        Ok(liquidity)
+    }
+
+    fn pool_remove_single_token_liquidity(
+        &self,
+        token_out: &TokenIdentifier,
+        liquidity: &Self::BigUint,
+    ) -> SCResult<(Self::BigUint, Self::BigUint)> {
+        let total_supply = self.get_total_lp_token_supply();
+        require!(
+            total_supply >= liquidity + &MINIMUM_LIQUIDITY.into(),
+            "Not enough LP token supply"
+        );
+
+        let reserve_out = self.pool_reserve(token_out).get();
+        let (amount_out, amount_out_before_fee) = self.calc_single_out_given_pool_in(
+            &reserve_out,
+            &self.get_normalized_weight(token_out),
+            &total_supply,
+            liquidity,
+            &self.fee_percent_to_bone(self.total_fee_percent().get()),
+        )?;
+        require!(amount_out > 0, "Insufficient liquidity burned");
+        require!(
+            amount_out_before_fee <= self.bmul(&reserve_out, &MAX_OUT_RATIO.into()),
+            "Amount out exceeds maximum ratio"
+        );
+
+        Ok((amount_out, amount_out_before_fee))
+    }
+
+    fn compute_amount_out(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_in: &Self::BigUint,
+        fee_percent: u64,
+    ) -> SCResult<Self::BigUint> {
+        let reserve_in = self.pool_reserve(token_in).get();
+        let reserve_out = self.pool_reserve(token_out).get();
+        require!(
+            reserve_in > 0 && reserve_out > 0,
+            "Zero reserves for swap tokens"
+        );
+        require!(
+            amount_in <= &self.bmul(&reserve_in, &MAX_IN_RATIO.into()),
+            "Amount in exceeds maximum ratio"
+        );
+
+        self.calc_out_given_in(
+            &reserve_in,
+            &self.get_normalized_weight(token_in),
+            &reserve_out,
+            &self.get_normalized_weight(token_out),
+            amount_in,
+            &self.fee_percent_to_bone(fee_percent),
+        )
+    }
+
+    fn compute_amount_in(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_out: &Self::BigUint,
+        fee_percent: u64,
+    ) -> SCResult<Self::BigUint> {
+        let reserve_in = self.pool_reserve(token_in).get();
+        let reserve_out = self.pool_reserve(token_out).get();
+        require!(
+            reserve_in > 0 && reserve_out > 0,
+            "Zero reserves for swap tokens"
+        );
+        require!(
+            amount_out <= &self.bmul(&reserve_out, &MAX_OUT_RATIO.into()),
+            "Amount out exceeds maximum ratio"
+        );
+
+        let amount_in = self.calc_in_given_out(
+            &reserve_in,
+            &self.get_normalized_weight(token_in),
+            &reserve_out,
+            &self.get_normalized_weight(token_out),
+            amount_out,
+            &self.fee_percent_to_bone(fee_percent),
+        )?;
+        Ok(amount_in + 1u64.into())
+    }
+
+    fn compute_spot_price(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+    ) -> Self::BigUint {
+        let reserve_in = self.pool_reserve(token_in).get();
+        let reserve_out = self.pool_reserve(token_out).get();
+        if reserve_in == 0 || reserve_out == 0 {
+            return Self::BigUint::zero();
+        }
+
+        self.calc_spot_price(
+            &reserve_in,
+            &self.get_normalized_weight(token_in),
+            &reserve_out,
+            &self.get_normalized_weight(token_out),
+            &Self::BigUint::zero(),
+        )
+    }
+
+    fn swap_safe_no_fee(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_in: &Self::BigUint,
+    ) -> Self::BigUint {
+        let big_zero = Self::BigUint::zero();
+        let reserve_in = self.pool_reserve(token_in).get();
+        let reserve_out = self.pool_reserve(token_out).get();
+        if reserve_in == 0 || reserve_out == 0 {
+            return big_zero;
+        }
+        if amount_in > &self.bmul(&reserve_in, &MAX_IN_RATIO.into()) {
+            return big_zero;
+        }
+
+        let amount_out = match self.calc_out_given_in(
+            &reserve_in,
+            &self.get_normalized_weight(token_in),
+            &reserve_out,
+            &self.get_normalized_weight(token_out),
+            amount_in,
+            &big_zero,
+        ) {
+            SCResult::Ok(amount_out) => amount_out,
+            SCResult::Err(_) => return big_zero,
+        };
+        if reserve_out <= amount_out || amount_out == 0 {
+            return big_zero;
+        }
+
+        self.increase_token_reserve(token_in, amount_in);
+        self.decrease_token_reserve(token_out, &amount_out);
+
+        amount_out
+    }
+
+    fn get_normalized_weight(&self, token_id: &TokenIdentifier) -> Self::BigUint {
+        self.normalized_weight(self.token_weight(token_id).get(), TOTAL_WEIGHT)
+    }
+
+    fn increase_token_reserve(&self, token_id: &TokenIdentifier, amount: &Self::BigUint) {
+        self.pool_reserve(token_id)
+            .update(|reserve| *reserve += amount);
+    }
+
+    fn try_decrease_token_reserve(
+        &self,
+        token_id: &TokenIdentifier,
+        amount: &Self::BigUint,
+    ) -> SCResult<()> {
+        self.pool_reserve(token_id).update(|reserve| {
+            require!(&*reserve > amount, "Not enough reserves");
+            *reserve -= amount;
+            Ok(())
+        })
+    }
+
+    fn decrease_token_reserve(&self, token_id: &TokenIdentifier, amount: &Self::BigUint) {
+        self.pool_reserve(token_id)
+            .update(|reserve| *reserve -= amount);
+    }
+
+    fn get_tokens_for_position(
+        &self,
+        liquidity: &Self::BigUint,
+    ) -> Vec<FftTokenAmountPair<Self::BigUint>> {
+        let total_supply = self.get_total_lp_token_supply();
+        self.tokens()
+            .get()
+            .into_iter()
+            .map(|token_id| {
+                let amount = if total_supply != 0 {
+                    &(liquidity * &self.pool_reserve(&token_id).get()) / &total_supply
+                } else {
+                    Self::BigUint::zero()
+                };
+                FftTokenAmountPair { token_id, amount }
+            })
+            .collect()
+    }
+
+    fn get_all_reserves(&self) -> Vec<FftTokenAmountPair<Self::BigUint>> {
+        self.tokens()
+            .get()
+            .into_iter()
+            .map(|token_id| {
+                let amount = self.pool_reserve(&token_id).get();
+                FftTokenAmountPair { token_id, amount }
+            })
+            .collect()
+    }
+
+    #[view(getTotalSupply)]
+    fn get_total_lp_token_supply(&self) -> Self::BigUint {
+        let result = self.get_total_supply(&self.lp_token_identifier().get());
+        match result {
+            SCResult::Ok(amount) => amount,
+            SCResult::Err(message) => self.send().signal_error(message.as_bytes()),
+        }
+    }
+
+    #[view(getReserve)]
+    #[storage_mapper("reserve")]
+    fn pool_reserve(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<Self::Storage, Self::BigUint>;
+}
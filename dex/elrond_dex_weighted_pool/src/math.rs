@@ -0,0 +1,212 @@
+elrond_wasm::imports!();
+
+// Fixed point math over 18 decimals, following the Balancer V1 weighted pool formulas.
+pub const BONE: u64 = 1_000_000_000_000_000_000;
+const MIN_BPOW_BASE: u64 = 1;
+const MAX_BPOW_BASE: u64 = 2 * BONE - 1;
+const BPOW_PRECISION: u64 = BONE / 10_000_000_000;
+pub const MAX_IN_RATIO: u64 = BONE / 2;
+pub const MAX_OUT_RATIO: u64 = BONE / 3 + 1;
+
+#[elrond_wasm::module]
+pub trait WeightedMathModule {
+    fn bone(&self) -> Self::BigUint {
+        Self::BigUint::from(BONE)
+    }
+
+    fn bmul(&self, a: &Self::BigUint, b: &Self::BigUint) -> Self::BigUint {
+        let half = Self::BigUint::from(BONE / 2);
+        (&(a * b) + &half) / self.bone()
+    }
+
+    fn bdiv(&self, a: &Self::BigUint, b: &Self::BigUint) -> Self::BigUint {
+        let half_b = b / &Self::BigUint::from(2u64);
+        (&(a * &self.bone()) + &half_b) / b.clone()
+    }
+
+    fn bsub_sign(&self, a: &Self::BigUint, b: &Self::BigUint) -> (Self::BigUint, bool) {
+        if a >= b {
+            (a - b, false)
+        } else {
+            (b - a, true)
+        }
+    }
+
+    fn bpowi(&self, base: &Self::BigUint, exponent: u64) -> Self::BigUint {
+        let mut a = base.clone();
+        let mut n = exponent;
+        let mut z = if n % 2 == 1 { a.clone() } else { self.bone() };
+
+        n /= 2;
+        while n != 0 {
+            a = self.bmul(&a, &a);
+            if n % 2 == 1 {
+                z = self.bmul(&z, &a);
+            }
+            n /= 2;
+        }
+        z
+    }
+
+    fn bpow(&self, base: &Self::BigUint, exponent: &Self::BigUint) -> SCResult<Self::BigUint> {
+        require!(
+            base >= &Self::BigUint::from(MIN_BPOW_BASE),
+            "Power base too low"
+        );
+        require!(
+            base <= &Self::BigUint::from(MAX_BPOW_BASE),
+            "Power base too high"
+        );
+
+        // The whole part of the exponent is bounded by the weight limits,
+        // hence counting it one unit at a time stays cheap.
+        let bone = self.bone();
+        let mut whole_exponent = 0u64;
+        let mut remain = exponent.clone();
+        while remain >= bone {
+            remain -= &bone;
+            whole_exponent += 1;
+        }
+
+        let whole_pow = self.bpowi(base, whole_exponent);
+        if remain == 0 {
+            return Ok(whole_pow);
+        }
+
+        let partial_result = self.bpow_approx(base, &remain);
+        Ok(self.bmul(&whole_pow, &partial_result))
+    }
+
+    fn bpow_approx(&self, base: &Self::BigUint, exponent: &Self::BigUint) -> Self::BigUint {
+        let bone = self.bone();
+        let precision = Self::BigUint::from(BPOW_PRECISION);
+        let (x, x_negative) = self.bsub_sign(base, &bone);
+
+        let mut term = bone.clone();
+        let mut sum = term.clone();
+        let mut negative = false;
+        let mut i = 1u64;
+
+        while term >= precision {
+            let big_k = Self::BigUint::from(i) * bone.clone();
+            let (c, c_negative) = self.bsub_sign(exponent, &(&big_k - &bone));
+            term = self.bmul(&term, &self.bmul(&c, &x));
+            term = self.bdiv(&term, &big_k);
+            if term == 0 {
+                break;
+            }
+
+            if x_negative {
+                negative = !negative;
+            }
+            if c_negative {
+                negative = !negative;
+            }
+            if negative {
+                sum -= &term;
+            } else {
+                sum += &term;
+            }
+            i += 1;
+        }
+
+        sum
+    }
+
+    fn normalized_weight(&self, weight: u64, total_weight: u64) -> Self::BigUint {
+        Self::BigUint::from(weight) * self.bone() / Self::BigUint::from(total_weight)
+    }
+
+    fn fee_percent_to_bone(&self, fee_percent: u64) -> Self::BigUint {
+        Self::BigUint::from(fee_percent) * self.bone() / Self::BigUint::from(100_000u64)
+    }
+
+    fn calc_spot_price(
+        &self,
+        balance_in: &Self::BigUint,
+        weight_in: &Self::BigUint,
+        balance_out: &Self::BigUint,
+        weight_out: &Self::BigUint,
+        swap_fee: &Self::BigUint,
+    ) -> Self::BigUint {
+        let numerator = self.bdiv(balance_in, weight_in);
+        let denominator = self.bdiv(balance_out, weight_out);
+        let ratio = self.bdiv(&numerator, &denominator);
+        let scale = self.bdiv(&self.bone(), &(&self.bone() - swap_fee));
+        self.bmul(&ratio, &scale)
+    }
+
+    fn calc_out_given_in(
+        &self,
+        balance_in: &Self::BigUint,
+        weight_in: &Self::BigUint,
+        balance_out: &Self::BigUint,
+        weight_out: &Self::BigUint,
+        amount_in: &Self::BigUint,
+        swap_fee: &Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        let weight_ratio = self.bdiv(weight_in, weight_out);
+        let adjusted_in = self.bmul(amount_in, &(&self.bone() - swap_fee));
+        let y = self.bdiv(balance_in, &(balance_in + &adjusted_in));
+        let power = self.bpow(&y, &weight_ratio)?;
+        let complement = &self.bone() - &power;
+        Ok(self.bmul(balance_out, &complement))
+    }
+
+    fn calc_in_given_out(
+        &self,
+        balance_in: &Self::BigUint,
+        weight_in: &Self::BigUint,
+        balance_out: &Self::BigUint,
+        weight_out: &Self::BigUint,
+        amount_out: &Self::BigUint,
+        swap_fee: &Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        let weight_ratio = self.bdiv(weight_out, weight_in);
+        let diff = balance_out - amount_out;
+        let y = self.bdiv(balance_out, &diff);
+        let growth = &self.bpow(&y, &weight_ratio)? - &self.bone();
+        let amount_in = self.bmul(balance_in, &growth);
+        Ok(self.bdiv(&amount_in, &(&self.bone() - swap_fee)))
+    }
+
+    fn calc_pool_out_given_single_in(
+        &self,
+        balance_in: &Self::BigUint,
+        normalized_weight_in: &Self::BigUint,
+        pool_supply: &Self::BigUint,
+        amount_in: &Self::BigUint,
+        swap_fee: &Self::BigUint,
+    ) -> SCResult<Self::BigUint> {
+        // Only the part of the input that is implicitly swapped pays fees.
+        let zaz = self.bmul(&(&self.bone() - normalized_weight_in), swap_fee);
+        let amount_in_after_fee = self.bmul(amount_in, &(&self.bone() - &zaz));
+        let new_balance_in = balance_in + &amount_in_after_fee;
+        let token_in_ratio = self.bdiv(&new_balance_in, balance_in);
+
+        let pool_ratio = self.bpow(&token_in_ratio, normalized_weight_in)?;
+        let new_pool_supply = self.bmul(&pool_ratio, pool_supply);
+        Ok(&new_pool_supply - pool_supply)
+    }
+
+    fn calc_single_out_given_pool_in(
+        &self,
+        balance_out: &Self::BigUint,
+        normalized_weight_out: &Self::BigUint,
+        pool_supply: &Self::BigUint,
+        pool_amount_in: &Self::BigUint,
+        swap_fee: &Self::BigUint,
+    ) -> SCResult<(Self::BigUint, Self::BigUint)> {
+        let new_pool_supply = pool_supply - pool_amount_in;
+        let pool_ratio = self.bdiv(&new_pool_supply, pool_supply);
+        let token_out_ratio =
+            self.bpow(&pool_ratio, &self.bdiv(&self.bone(), normalized_weight_out))?;
+        let new_balance_out = self.bmul(&token_out_ratio, balance_out);
+        let amount_out_before_fee = balance_out - &new_balance_out;
+
+        // Only the part of the output that is implicitly swapped pays fees.
+        let zaz = self.bmul(&(&self.bone() - normalized_weight_out), swap_fee);
+        let amount_out = self.bmul(&amount_out_before_fee, &(&self.bone() - &zaz));
+        Ok((amount_out, amount_out_before_fee))
+    }
+}
@@ -0,0 +1,4 @@
+#![no_std]
+
+pub use elrond_dex_weighted_pool::*;
+pub use elrond_wasm_output::*;
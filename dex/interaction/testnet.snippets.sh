@@ -122,6 +122,8 @@ uploadPairContractCode() {
     PAIR_CODE_HEX1="0x$(split -n1/3 <<<$PAIR_CODE_HEX)"
     PAIR_CODE_HEX2="0x$(split -n2/3 <<<$PAIR_CODE_HEX)"
     PAIR_CODE_HEX3="0x$(split -n3/3 <<<$PAIR_CODE_HEX)7575"
+    # Keccak-256, using the pycryptodome package that erdpy depends on.
+    PAIR_CODE_HASH="0x$(python3 -c "import sys; from Crypto.Hash import keccak; print(keccak.new(digest_bits=256, data=bytes.fromhex(sys.argv[1])).hexdigest())" ${PAIR_CODE_HEX1:2}${PAIR_CODE_HEX2:2}${PAIR_CODE_HEX3:2})"
 
     erdpy --verbose contract call ${ROUTE_ADDRESS} --recall-nonce \
           --pem=${WALLET_PEM} \
@@ -172,6 +174,7 @@ uploadPairContractCode() {
           --gas-price=1400000000 \
           --gas-limit=1400000000 \
           --function=endPairCodeConstruction \
+          --arguments $PAIR_CODE_HASH \
           --send
     sleep 6
 }
@@ -269,6 +272,34 @@ setFeeOff() {
         --send || return
 }
 
+#params:
+#   $1 = timelocked action id, returned when the action was queued
+executeTimelockedAction() {
+    erdpy --verbose contract call $ROUTE_ADDRESS --recall-nonce \
+        --pem=${WALLET_PEM} \
+        --proxy=${PROXY} --chain=${CHAIN_ID} \
+        --gas-limit=200000000 \
+        --function=executeTimelockedAction \
+        --arguments $1 \
+        --send || return
+}
+
+#params:
+#   $1 = contract address
+#   $2 = role, 0 = Admin, 1 = Pauser, 2 = FeeManager, 3 = EmissionManager, 4 = Upgrader
+#   $3 = address receiving the role
+grantRole() {
+    address="0x$(erdpy wallet bech32 --decode $3)"
+
+    erdpy --verbose contract call $1 --recall-nonce \
+        --pem=${WALLET_PEM} \
+        --proxy=${PROXY} --chain=${CHAIN_ID} \
+        --gas-limit=20000000 \
+        --function=grantRole \
+        --arguments $2 $address \
+        --send || return
+}
+
 # params
 #   $1 = Token Identifier
 #   $2 = Address
@@ -496,17 +527,19 @@ multiPairSwap() {
       --send || return
 }
 
+# Queued in the router timelock, run executeTimelockedAction once it expires.
 # params
 #   $1 = destination pair contract,
 #   $2 = pair contract to be whitelisted.
 whitelist() {
+    destination_address="0x$(erdpy wallet bech32 --decode $1)"
     pair_address="0x$(erdpy wallet bech32 --decode $2)"
-    erdpy --verbose contract call $1 --recall-nonce \
+    erdpy --verbose contract call $ROUTE_ADDRESS --recall-nonce \
         --pem=${WALLET_PEM} \
         --proxy=${PROXY} --chain=${CHAIN_ID} \
         --gas-limit=${DEFAULT_GAS_LIMIT} \
-        --function=whitelist \
-        --arguments $pair_address \
+        --function=whitelistPairAddress \
+        --arguments $destination_address $pair_address \
         --send || return
 }
 
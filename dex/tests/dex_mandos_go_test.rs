@@ -50,6 +50,26 @@ fn compound_rewards_setup_go() {
     elrond_wasm_debug::mandos_go("mandos/compound_rewards.scen.json");
 }
 
+#[test]
+fn concentrated_pool_add_liquidity_go() {
+    elrond_wasm_debug::mandos_go("mandos/concentrated_pool_add_liquidity.scen.json");
+}
+
+#[test]
+fn concentrated_pool_collect_fees_go() {
+    elrond_wasm_debug::mandos_go("mandos/concentrated_pool_collect_fees.scen.json");
+}
+
+#[test]
+fn concentrated_pool_remove_liquidity_go() {
+    elrond_wasm_debug::mandos_go("mandos/concentrated_pool_remove_liquidity.scen.json");
+}
+
+#[test]
+fn concentrated_pool_swap_go() {
+    elrond_wasm_debug::mandos_go("mandos/concentrated_pool_swap.scen.json");
+}
+
 #[test]
 fn create_pair_twice_go() {
     elrond_wasm_debug::mandos_go("mandos/create_pair_twice.scen.json");
@@ -115,6 +135,16 @@ fn farm_wrong_lp_token_go() {
     elrond_wasm_debug::mandos_go("mandos/farm_wrong_lp_token.scen.json");
 }
 
+#[test]
+fn fee_policy_override_go() {
+    elrond_wasm_debug::mandos_go("mandos/fee_policy_override.scen.json");
+}
+
+#[test]
+fn fee_routing_push_go() {
+    elrond_wasm_debug::mandos_go("mandos/fee_routing_push.scen.json");
+}
+
 #[test]
 fn get_amounts_go() {
     elrond_wasm_debug::mandos_go("mandos/get_amounts.scen.json");
@@ -135,11 +165,41 @@ fn get_pair_views_go() {
     elrond_wasm_debug::mandos_go("mandos/get_pair_views.scen.json");
 }
 
+#[test]
+fn guardian_pause_resume_go() {
+    elrond_wasm_debug::mandos_go("mandos/guardian_pause_resume.scen.json");
+}
+
+#[test]
+fn lbp_migrate_to_pair_go() {
+    elrond_wasm_debug::mandos_go("mandos/lbp_migrate_to_pair.scen.json");
+}
+
+#[test]
+fn lbp_swap_go() {
+    elrond_wasm_debug::mandos_go("mandos/lbp_swap.scen.json");
+}
+
 #[test]
 fn multi_pair_swap_go() {
     elrond_wasm_debug::mandos_go("mandos/multi_pair_swap.scen.json");
 }
 
+#[test]
+fn multi_pair_swap_egld_go() {
+    elrond_wasm_debug::mandos_go("mandos/multi_pair_swap_egld.scen.json");
+}
+
+#[test]
+fn multi_pair_swap_egld_no_wrapper_go() {
+    elrond_wasm_debug::mandos_go("mandos/multi_pair_swap_egld_no_wrapper.scen.json");
+}
+
+#[test]
+fn multi_pair_swap_to_egld_go() {
+    elrond_wasm_debug::mandos_go("mandos/multi_pair_swap_to_egld.scen.json");
+}
+
 #[test]
 fn merge_tokens_go() {
     elrond_wasm_debug::mandos_go("mandos/merge_tokens.scen.json");
@@ -155,11 +215,81 @@ fn owner_resume_farm_go() {
     elrond_wasm_debug::mandos_go("mandos/owner_resume_farm.scen.json");
 }
 
+#[test]
+fn pair_code_chunks_go() {
+    elrond_wasm_debug::mandos_go("mandos/pair_code_chunks.scen.json");
+}
+
+#[test]
+fn pair_delist_archive_go() {
+    elrond_wasm_debug::mandos_go("mandos/pair_delist_archive.scen.json");
+}
+
+#[test]
+fn pair_migration_go() {
+    elrond_wasm_debug::mandos_go("mandos/pair_migration.scen.json");
+}
+
+#[test]
+fn pair_setup_go() {
+    elrond_wasm_debug::mandos_go("mandos/pair_setup.scen.json");
+}
+
+#[test]
+fn pair_sunset_go() {
+    elrond_wasm_debug::mandos_go("mandos/pair_sunset.scen.json");
+}
+
+#[test]
+fn pair_temporary_owner_go() {
+    elrond_wasm_debug::mandos_go("mandos/pair_temporary_owner.scen.json");
+}
+
+#[test]
+fn pair_twap_price_go() {
+    elrond_wasm_debug::mandos_go("mandos/pair_twap_price.scen.json");
+}
+
+#[test]
+fn path_quoter_go() {
+    elrond_wasm_debug::mandos_go("mandos/path_quoter.scen.json");
+}
+
+#[test]
+fn permissions_go() {
+    elrond_wasm_debug::mandos_go("mandos/permissions.scen.json");
+}
+
+#[test]
+fn pool_token_policy_go() {
+    elrond_wasm_debug::mandos_go("mandos/pool_token_policy.scen.json");
+}
+
+#[test]
+fn proxy_swap_locked_asset_go() {
+    elrond_wasm_debug::mandos_go("mandos/proxy_swap_locked_asset.scen.json");
+}
+
+#[test]
+fn proxy_swap_wrapped_token_gain_go() {
+    elrond_wasm_debug::mandos_go("mandos/proxy_swap_wrapped_token_gain.scen.json");
+}
+
+#[test]
+fn proxy_swap_wrapped_token_loss_go() {
+    elrond_wasm_debug::mandos_go("mandos/proxy_swap_wrapped_token_loss.scen.json");
+}
+
 #[test]
 fn reclaim_temporary_funds_go() {
     elrond_wasm_debug::mandos_go("mandos/reclaim_temporary_funds.scen.json");
 }
 
+#[test]
+fn referral_swap_go() {
+    elrond_wasm_debug::mandos_go("mandos/referral_swap.scen.json");
+}
+
 #[test]
 fn remove_liquidity_go() {
     elrond_wasm_debug::mandos_go("mandos/remove_liquidity.scen.json");
@@ -170,6 +300,11 @@ fn remove_liquidity_twice_go() {
     elrond_wasm_debug::mandos_go("mandos/remove_liquidity_twice.scen.json");
 }
 
+#[test]
+fn route_finder_go() {
+    elrond_wasm_debug::mandos_go("mandos/route_finder.scen.json");
+}
+
 #[test]
 fn router_pause_self_go() {
     elrond_wasm_debug::mandos_go("mandos/router_pause_self.scen.json");
@@ -185,6 +320,11 @@ fn send_with_no_funds_go() {
     elrond_wasm_debug::mandos_go("mandos/send_with_no_funds.scen.json");
 }
 
+#[test]
+fn split_swap_go() {
+    elrond_wasm_debug::mandos_go("mandos/split_swap.scen.json");
+}
+
 #[test]
 fn swap_fixed_input_go() {
     elrond_wasm_debug::mandos_go("mandos/swap_fixed_input.scen.json");
@@ -210,7 +350,37 @@ fn swap_wrong_token_go() {
     elrond_wasm_debug::mandos_go("mandos/swap_wrong_token.scen.json");
 }
 
+#[test]
+fn timelock_defaults_go() {
+    elrond_wasm_debug::mandos_go("mandos/timelock_defaults.scen.json");
+}
+
 #[test]
 fn upgrade_contract_go() {
     elrond_wasm_debug::mandos_go("mandos/upgrade_contract.scen.json");
 }
+
+#[test]
+fn upgrade_pairs_go() {
+    elrond_wasm_debug::mandos_go("mandos/upgrade_pairs.scen.json");
+}
+
+#[test]
+fn weighted_pool_add_liquidity_go() {
+    elrond_wasm_debug::mandos_go("mandos/weighted_pool_add_liquidity.scen.json");
+}
+
+#[test]
+fn weighted_pool_single_token_exit_go() {
+    elrond_wasm_debug::mandos_go("mandos/weighted_pool_single_token_exit.scen.json");
+}
+
+#[test]
+fn weighted_pool_single_token_join_go() {
+    elrond_wasm_debug::mandos_go("mandos/weighted_pool_single_token_join.scen.json");
+}
+
+#[test]
+fn weighted_pool_swap_go() {
+    elrond_wasm_debug::mandos_go("mandos/weighted_pool_swap.scen.json");
+}
@@ -4,6 +4,7 @@ elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
 use common_structs::Epoch;
+use permissions::Role;
 
 mod global_op;
 
@@ -24,7 +25,7 @@ pub struct CommunityDistribution<BigUint: BigUintApi> {
 }
 
 #[elrond_wasm::contract]
-pub trait Distribution: global_op::GlobalOperationModule {
+pub trait Distribution: global_op::GlobalOperationModule + permissions::PermissionsModule {
     #[proxy]
     fn locked_asset_factory_proxy(
         &self,
@@ -45,19 +46,20 @@ pub trait Distribution: global_op::GlobalOperationModule {
         self.asset_token_id().set(&asset_token_id);
         self.locked_asset_factory_address()
             .set(&locked_asset_factory_address);
+        self.init_permissions(&self.blockchain().get_caller());
         Ok(())
     }
 
     #[endpoint(startGlobalOperation)]
     fn start_planning(&self) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::EmissionManager)?;
         self.global_op_start()?;
         Ok(())
     }
 
     #[endpoint(endGlobalOperation)]
     fn end_planning(&self) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::EmissionManager)?;
         self.global_op_stop()?;
         Ok(())
     }
@@ -68,7 +70,7 @@ pub trait Distribution: global_op::GlobalOperationModule {
         total_amount: Self::BigUint,
         spread_epoch: u64,
     ) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::EmissionManager)?;
         self.require_global_op_ongoing()?;
         require!(total_amount > 0, "Zero amount");
         require!(
@@ -99,7 +101,7 @@ pub trait Distribution: global_op::GlobalOperationModule {
         spread_epoch: u64,
         #[var_args] user_locked_assets: VarArgs<MultiArg2<Address, Self::BigUint>>,
     ) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::EmissionManager)?;
         self.require_global_op_ongoing()?;
         self.require_community_distribution_list_not_empty()?;
 
@@ -148,7 +150,7 @@ pub trait Distribution: global_op::GlobalOperationModule {
 
     #[endpoint(undoLastCommunityDistribution)]
     fn undo_last_community_distrib(&self) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::EmissionManager)?;
         self.require_global_op_ongoing()?;
         self.require_community_distribution_list_not_empty()?;
         self.community_distribution_list().pop_front();
@@ -157,7 +159,7 @@ pub trait Distribution: global_op::GlobalOperationModule {
 
     #[endpoint(undoUserDistributedAssetsBetweenEpochs)]
     fn undo_user_assets_between_epochs(&self, lower: u64, higher: u64) -> SCResult<usize> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::EmissionManager)?;
         self.require_global_op_ongoing()?;
         self.require_community_distribution_list_not_empty()?;
         require!(lower <= higher, "Bad input values");
@@ -3,6 +3,7 @@ elrond_wasm::derive_imports!();
 
 use common_structs::{
     GenericTokenAmountPair, WrappedFarmTokenAttributes, WrappedLpTokenAttributes,
+    WrappedSwapTokenAttributes,
 };
 
 #[derive(TopEncode)]
@@ -87,6 +88,31 @@ pub struct CompoundRewardsProxyEvent<BigUint: BigUintApi> {
     timestamp: u64,
 }
 
+#[derive(TopEncode)]
+pub struct SwapLockedAssetProxyEvent<BigUint: BigUintApi> {
+    caller: Address,
+    router_address: Address,
+    locked_asset_amount: GenericTokenAmountPair<BigUint>,
+    wrapped_swap_token_amount: GenericTokenAmountPair<BigUint>,
+    wrapped_swap_attributes: WrappedSwapTokenAttributes<BigUint>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TopEncode)]
+pub struct SwapWrappedTokenProxyEvent<BigUint: BigUintApi> {
+    caller: Address,
+    router_address: Address,
+    wrapped_swap_token_amount: GenericTokenAmountPair<BigUint>,
+    wrapped_swap_attributes: WrappedSwapTokenAttributes<BigUint>,
+    locked_asset_amount: GenericTokenAmountPair<BigUint>,
+    locked_asset_gain_amount: GenericTokenAmountPair<BigUint>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[elrond_wasm::module]
 pub trait EventsModule {
     fn emit_add_liquidity_proxy_event(
@@ -273,6 +299,62 @@ pub trait EventsModule {
         )
     }
 
+    fn emit_swap_locked_asset_proxy_event(
+        self,
+        caller: Address,
+        router_address: Address,
+        locked_asset_amount: GenericTokenAmountPair<Self::BigUint>,
+        wrapped_swap_token_amount: GenericTokenAmountPair<Self::BigUint>,
+        wrapped_swap_attributes: WrappedSwapTokenAttributes<Self::BigUint>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.swap_locked_asset_proxy_event(
+            wrapped_swap_attributes.token_id.clone(),
+            caller.clone(),
+            router_address.clone(),
+            epoch,
+            SwapLockedAssetProxyEvent {
+                caller,
+                router_address,
+                locked_asset_amount,
+                wrapped_swap_token_amount,
+                wrapped_swap_attributes,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_swap_wrapped_token_proxy_event(
+        self,
+        caller: Address,
+        router_address: Address,
+        wrapped_swap_token_amount: GenericTokenAmountPair<Self::BigUint>,
+        wrapped_swap_attributes: WrappedSwapTokenAttributes<Self::BigUint>,
+        locked_asset_amount: GenericTokenAmountPair<Self::BigUint>,
+        locked_asset_gain_amount: GenericTokenAmountPair<Self::BigUint>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.swap_wrapped_token_proxy_event(
+            wrapped_swap_attributes.token_id.clone(),
+            caller.clone(),
+            router_address.clone(),
+            epoch,
+            SwapWrappedTokenProxyEvent {
+                caller,
+                router_address,
+                wrapped_swap_token_amount,
+                wrapped_swap_attributes,
+                locked_asset_amount,
+                locked_asset_gain_amount,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
     #[event("add_liquidity_proxy")]
     fn add_liquidity_proxy_event(
         self,
@@ -334,4 +416,24 @@ pub trait EventsModule {
         #[indexed] epoch: u64,
         compound_rewards_farm_proxy_event: CompoundRewardsProxyEvent<Self::BigUint>,
     );
+
+    #[event("swap_locked_asset_proxy")]
+    fn swap_locked_asset_proxy_event(
+        self,
+        #[indexed] token_out: TokenIdentifier,
+        #[indexed] caller: Address,
+        #[indexed] router_address: Address,
+        #[indexed] epoch: u64,
+        swap_locked_asset_proxy_event: SwapLockedAssetProxyEvent<Self::BigUint>,
+    );
+
+    #[event("swap_wrapped_token_proxy")]
+    fn swap_wrapped_token_proxy_event(
+        self,
+        #[indexed] token_in: TokenIdentifier,
+        #[indexed] caller: Address,
+        #[indexed] router_address: Address,
+        #[indexed] epoch: u64,
+        swap_wrapped_token_proxy_event: SwapWrappedTokenProxyEvent<Self::BigUint>,
+    );
 }
@@ -8,6 +8,7 @@ mod events;
 mod proxy_common;
 mod proxy_farm;
 mod proxy_pair;
+mod proxy_swap;
 mod wrapped_farm_token_merge;
 mod wrapped_lp_token_merge;
 
@@ -17,6 +18,7 @@ const DEFAULT_NFT_DEPOSIT_MAX_LEN: usize = 10;
 pub enum IssueRequestType {
     ProxyFarm,
     ProxyPair,
+    ProxySwap,
 }
 
 #[elrond_wasm::contract]
@@ -24,6 +26,7 @@ pub trait ProxyDexImpl:
     proxy_common::ProxyCommonModule
     + proxy_pair::ProxyPairModule
     + proxy_farm::ProxyFarmModule
+    + proxy_swap::ProxySwapModule
     + token_supply::TokenSupplyModule
     + nft_deposit::NftDepositModule
     + token_merge::TokenMergeModule
@@ -31,6 +34,7 @@ pub trait ProxyDexImpl:
     + wrapped_farm_token_merge::WrappedFarmTokenMerge
     + wrapped_lp_token_merge::WrappedLpTokenMerge
     + events::EventsModule
+    + permissions::PermissionsModule
 {
     #[init]
     fn init(
@@ -58,6 +62,7 @@ pub trait ProxyDexImpl:
         self.locked_asset_token_id().set(&locked_asset_token_id);
         self.locked_asset_factory_address()
             .set(&locked_asset_factory_address);
+        self.init_permissions(&self.blockchain().get_caller());
         Ok(())
     }
 
@@ -69,7 +74,7 @@ pub trait ProxyDexImpl:
         token_ticker: BoxedBytes,
         #[payment_amount] issue_cost: Self::BigUint,
     ) -> SCResult<AsyncCall<Self::SendApi>> {
-        only_owner!(self, "Permission denied");
+        self.require_permissions()?;
         require!(self.wrapped_lp_token_id().is_empty(), "SFT already issued");
         self.issue_nft(
             token_display_name,
@@ -87,7 +92,7 @@ pub trait ProxyDexImpl:
         token_ticker: BoxedBytes,
         #[payment_amount] issue_cost: Self::BigUint,
     ) -> SCResult<AsyncCall<Self::SendApi>> {
-        only_owner!(self, "Permission denied");
+        self.require_permissions()?;
         require!(
             self.wrapped_farm_token_id().is_empty(),
             "SFT already issued"
@@ -100,6 +105,27 @@ pub trait ProxyDexImpl:
         )
     }
 
+    #[payable("EGLD")]
+    #[endpoint(issueSftProxySwap)]
+    fn issue_sft_proxy_swap(
+        &self,
+        token_display_name: BoxedBytes,
+        token_ticker: BoxedBytes,
+        #[payment_amount] issue_cost: Self::BigUint,
+    ) -> SCResult<AsyncCall<Self::SendApi>> {
+        self.require_permissions()?;
+        require!(
+            self.wrapped_swap_token_id().is_empty(),
+            "SFT already issued"
+        );
+        self.issue_nft(
+            token_display_name,
+            token_ticker,
+            issue_cost,
+            IssueRequestType::ProxySwap,
+        )
+    }
+
     fn issue_nft(
         &self,
         token_display_name: BoxedBytes,
@@ -148,6 +174,11 @@ pub trait ProxyDexImpl:
                             self.nft_deposit_accepted_token_ids().insert(token_id);
                         }
                     }
+                    IssueRequestType::ProxySwap => {
+                        if self.wrapped_swap_token_id().is_empty() {
+                            self.wrapped_swap_token_id().set(&token_id);
+                        }
+                    }
                 }
             }
             AsyncCallResult::Err(message) => {
@@ -172,7 +203,7 @@ pub trait ProxyDexImpl:
         address: Address,
         #[var_args] roles: VarArgs<EsdtLocalRole>,
     ) -> SCResult<AsyncCall<Self::SendApi>> {
-        only_owner!(self, "Permission denied");
+        self.require_permissions()?;
         require!(!roles.is_empty(), "Empty roles");
         Ok(ESDTSystemSmartContractProxy::new_proxy_obj(self.send())
             .set_special_roles(&address, &token, roles.as_slice())
@@ -194,7 +225,7 @@ pub trait ProxyDexImpl:
 
     #[endpoint(setNftDepositMaxLen)]
     fn set_nft_deposit_max_len(&self, max_len: usize) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_permissions()?;
         self.nft_deposit_max_len().set(&max_len);
         Ok(())
     }
@@ -2,14 +2,35 @@ elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
 use common_structs::Nonce;
-use common_structs::{WrappedFarmTokenAttributes, WrappedLpTokenAttributes};
+use common_structs::{
+    WrappedFarmTokenAttributes, WrappedLpTokenAttributes, WrappedSwapTokenAttributes,
+};
+use permissions::Role;
 
 pub const ACCEPT_PAY_FUNC_NAME: &[u8] = b"acceptPay";
 
 #[elrond_wasm::module]
-pub trait ProxyCommonModule {
+pub trait ProxyCommonModule: permissions::PermissionsModule {
     fn require_permissions(&self) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::Admin)
+    }
+
+    #[inline]
+    fn is_active(&self) -> bool {
+        !self.paused().get()
+    }
+
+    #[endpoint]
+    fn pause(&self) -> SCResult<()> {
+        self.require_role(Role::Pauser)?;
+        self.paused().set(&true);
+        Ok(())
+    }
+
+    #[endpoint]
+    fn resume(&self) -> SCResult<()> {
+        self.require_role(Role::Pauser)?;
+        self.paused().clear();
         Ok(())
     }
 
@@ -41,6 +62,12 @@ pub trait ProxyCommonModule {
         new_nonce
     }
 
+    fn increase_wrapped_swap_token_nonce(&self) -> Nonce {
+        let new_nonce = self.wrapped_swap_token_nonce().get() + 1;
+        self.wrapped_swap_token_nonce().set(&new_nonce);
+        new_nonce
+    }
+
     fn get_wrapped_lp_token_attributes(
         &self,
         token_id: &TokenIdentifier,
@@ -82,6 +109,27 @@ pub trait ProxyCommonModule {
         }
     }
 
+    fn get_wrapped_swap_token_attributes(
+        &self,
+        token_id: &TokenIdentifier,
+        token_nonce: Nonce,
+    ) -> SCResult<WrappedSwapTokenAttributes<Self::BigUint>> {
+        let token_info = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            token_id,
+            token_nonce,
+        );
+
+        let attributes =
+            token_info.decode_attributes::<WrappedSwapTokenAttributes<Self::BigUint>>();
+        match attributes {
+            Result::Ok(decoded_obj) => Ok(decoded_obj),
+            Result::Err(_) => {
+                return sc_error!("Decoding error");
+            }
+        }
+    }
+
     #[storage_mapper("current_tx_accepted_funds")]
     fn current_tx_accepted_funds(
         &self,
@@ -109,6 +157,13 @@ pub trait ProxyCommonModule {
     #[storage_mapper("wrapped_farm_token_nonce")]
     fn wrapped_farm_token_nonce(&self) -> SingleValueMapper<Self::Storage, Nonce>;
 
+    #[view(getWrappedSwapTokenId)]
+    #[storage_mapper("wrapped_swap_token_id")]
+    fn wrapped_swap_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[storage_mapper("wrapped_swap_token_nonce")]
+    fn wrapped_swap_token_nonce(&self) -> SingleValueMapper<Self::Storage, Nonce>;
+
     #[storage_mapper("locked_asset_factory_address")]
     fn locked_asset_factory_address(&self) -> SingleValueMapper<Self::Storage, Address>;
 
@@ -119,4 +174,8 @@ pub trait ProxyCommonModule {
     #[view(getIntermediatedPairs)]
     #[storage_mapper("intermediated_pairs")]
     fn intermediated_pairs(&self) -> SafeSetMapper<Self::Storage, Address>;
+
+    #[view(isPaused)]
+    #[storage_mapper("paused")]
+    fn paused(&self) -> SingleValueMapper<Self::Storage, bool>;
 }
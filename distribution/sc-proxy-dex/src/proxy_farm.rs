@@ -39,6 +39,7 @@ pub trait ProxyFarmModule:
     + wrapped_farm_token_merge::WrappedFarmTokenMerge
     + wrapped_lp_token_merge::WrappedLpTokenMerge
     + events::EventsModule
+    + permissions::PermissionsModule
 {
     #[proxy]
     fn farm_contract_proxy(&self, to: Address) -> elrond_dex_farm::Proxy<Self::SendApi>;
@@ -90,6 +91,7 @@ pub trait ProxyFarmModule:
         farm_address: Address,
         with_lock_rewards: bool,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         self.require_is_intermediated_farm(&farm_address)?;
         self.require_wrapped_farm_token_id_not_empty()?;
         self.require_wrapped_lp_token_id_not_empty()?;
@@ -160,6 +162,7 @@ pub trait ProxyFarmModule:
         #[payment_nonce] token_nonce: Nonce,
         farm_address: &Address,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         self.require_is_intermediated_farm(farm_address)?;
         self.require_wrapped_farm_token_id_not_empty()?;
         self.require_wrapped_lp_token_id_not_empty()?;
@@ -234,6 +237,7 @@ pub trait ProxyFarmModule:
         #[payment_nonce] token_nonce: Nonce,
         farm_address: Address,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         self.require_is_intermediated_farm(&farm_address)?;
         self.require_wrapped_farm_token_id_not_empty()?;
         self.require_wrapped_lp_token_id_not_empty()?;
@@ -321,6 +325,7 @@ pub trait ProxyFarmModule:
         #[payment_amount] payment_amount: Self::BigUint,
         farm_address: Address,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         self.require_is_intermediated_farm(&farm_address)?;
         self.require_wrapped_farm_token_id_not_empty()?;
         self.require_wrapped_lp_token_id_not_empty()?;
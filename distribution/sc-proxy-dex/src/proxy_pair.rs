@@ -40,6 +40,7 @@ pub trait ProxyPairModule:
     + token_send::TokenSendModule
     + nft_deposit::NftDepositModule
     + events::EventsModule
+    + permissions::PermissionsModule
 {
     #[proxy]
     fn pair_contract_proxy(&self, to: Address) -> elrond_dex_pair::Proxy<Self::SendApi>;
@@ -68,6 +69,7 @@ pub trait ProxyPairModule:
         #[payment_nonce] token_nonce: Nonce,
         pair_address: Address,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         self.require_is_intermediated_pair(&pair_address)?;
         require!(amount != 0, "Payment amount cannot be zero");
         require!(
@@ -93,6 +95,7 @@ pub trait ProxyPairModule:
         second_token_id: TokenIdentifier,
         second_token_nonce: Nonce,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         require!(
             first_token_id != second_token_id || first_token_nonce != second_token_nonce,
             "Identical tokens"
@@ -138,6 +141,7 @@ pub trait ProxyPairModule:
         second_token_amount_desired: Self::BigUint,
         second_token_amount_min: Self::BigUint,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         self.require_is_intermediated_pair(&pair_address)?;
         self.require_wrapped_lp_token_id_not_empty()?;
         self.require_deposit_empty_or_tokens_are_wrapped_lp_tokens()?;
@@ -312,6 +316,7 @@ pub trait ProxyPairModule:
         first_token_amount_min: Self::BigUint,
         second_token_amount_min: Self::BigUint,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         self.require_is_intermediated_pair(&pair_address)?;
         self.require_wrapped_lp_token_id_not_empty()?;
         require!(token_nonce != 0, "Can only be called with an SFT");
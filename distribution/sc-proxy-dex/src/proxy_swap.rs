@@ -0,0 +1,297 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use common_structs::{
+    FftTokenAmountPair, GenericTokenAmountPair, Nonce, WrappedSwapTokenAttributes,
+};
+
+use super::events;
+use super::proxy_common;
+use proxy_common::ACCEPT_PAY_FUNC_NAME;
+use sc_locked_asset_factory::locked_asset::LockedAssetTokenAttributes;
+
+mod router_proxy {
+    elrond_wasm::imports!();
+
+    use common_structs::FftTokenAmountPair;
+
+    #[elrond_wasm::proxy]
+    pub trait Router {
+        #[payable("*")]
+        #[endpoint(swapExactInBestRoute)]
+        fn swap_exact_in_best_route(
+            &self,
+            #[payment_token] token_in: TokenIdentifier,
+            #[payment_amount] amount_in: Self::BigUint,
+            token_out: TokenIdentifier,
+            amount_out_min: Self::BigUint,
+            max_hops: usize,
+            #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+        ) -> FftTokenAmountPair<Self::BigUint>;
+    }
+}
+
+#[elrond_wasm::module]
+pub trait ProxySwapModule:
+    proxy_common::ProxyCommonModule
+    + token_supply::TokenSupplyModule
+    + token_merge::TokenMergeModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + permissions::PermissionsModule
+{
+    #[proxy]
+    fn router_proxy(&self, to: Address) -> router_proxy::Proxy<Self::SendApi>;
+
+    #[proxy]
+    fn locked_asset_factory_swap_proxy(
+        &self,
+        to: Address,
+    ) -> sc_locked_asset_factory::Proxy<Self::SendApi>;
+
+    #[endpoint(setRouterAddress)]
+    fn set_router_address(&self, router_address: Address) -> SCResult<()> {
+        self.require_permissions()?;
+        self.router_address().set(&router_address);
+        Ok(())
+    }
+
+    // The locked assets are swapped as the asset, through the router's best route. The output
+    // stays in the proxy and the caller gets Wrapped Swap Tokens for it, one for one.
+    #[payable("*")]
+    #[endpoint(swapLockedAssetProxy)]
+    fn swap_locked_asset_proxy(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: Self::BigUint,
+        #[payment_nonce] token_nonce: Nonce,
+        token_out: TokenIdentifier,
+        amount_out_min: Self::BigUint,
+        max_hops: usize,
+    ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
+        self.require_router_address_not_empty()?;
+        self.require_wrapped_swap_token_id_not_empty()?;
+        require!(token_nonce != 0, "Can only be called with an SFT");
+        require!(amount != 0, "Payment amount cannot be zero");
+        require!(
+            token_id == self.locked_asset_token_id().get(),
+            "Wrong input token"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let asset_token_id = self.asset_token_id().get();
+        self.mint_tokens(&asset_token_id, &amount);
+        let token_amount_out = self.actual_swap_via_router(
+            &asset_token_id,
+            &amount,
+            &token_out,
+            &amount_out_min,
+            max_hops,
+        );
+        require!(
+            token_amount_out.token_id == token_out,
+            "Bad token received from router"
+        );
+
+        let wrapped_swap_token_id = self.wrapped_swap_token_id().get();
+        let attributes = WrappedSwapTokenAttributes {
+            token_id: token_amount_out.token_id,
+            token_total_amount: token_amount_out.amount.clone(),
+            locked_assets_invested: amount.clone(),
+            locked_assets_nonce: token_nonce,
+        };
+        self.nft_create_tokens(
+            &wrapped_swap_token_id,
+            &token_amount_out.amount,
+            &attributes,
+        );
+        let new_nonce = self.increase_wrapped_swap_token_nonce();
+        self.send().direct(
+            &caller,
+            &wrapped_swap_token_id,
+            new_nonce,
+            &token_amount_out.amount,
+            &[],
+        );
+
+        let locked_asset_amount = GenericTokenAmountPair {
+            token_id,
+            token_nonce,
+            amount,
+        };
+        let wrapped_swap_token_amount = GenericTokenAmountPair {
+            token_id: wrapped_swap_token_id,
+            token_nonce: new_nonce,
+            amount: token_amount_out.amount,
+        };
+        self.emit_swap_locked_asset_proxy_event(
+            caller,
+            self.router_address().get(),
+            locked_asset_amount,
+            wrapped_swap_token_amount,
+            attributes,
+        );
+        Ok(())
+    }
+
+    // Swaps the tokens behind the Wrapped Swap Tokens back to the asset. The locked assets they
+    // were created from are returned with their original nonce, and any gain is created by the
+    // locked asset factory with the same attributes, so the unlock schedule is kept for both.
+    #[payable("*")]
+    #[endpoint(swapWrappedTokenProxy)]
+    fn swap_wrapped_token_proxy(
+        &self,
+        #[payment_token] token_id: TokenIdentifier,
+        #[payment_amount] amount: Self::BigUint,
+        #[payment_nonce] token_nonce: Nonce,
+        amount_out_min: Self::BigUint,
+        max_hops: usize,
+    ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
+        self.require_router_address_not_empty()?;
+        self.require_wrapped_swap_token_id_not_empty()?;
+        require!(token_nonce != 0, "Can only be called with an SFT");
+        require!(amount != 0, "Payment amount cannot be zero");
+
+        let wrapped_swap_token_id = self.wrapped_swap_token_id().get();
+        require!(token_id == wrapped_swap_token_id, "Wrong input token");
+
+        let caller = self.blockchain().get_caller();
+        let attributes = self.get_wrapped_swap_token_attributes(&token_id, token_nonce)?;
+        let locked_assets_invested = self.rule_of_three_non_zero_result(
+            &amount,
+            &attributes.token_total_amount,
+            &attributes.locked_assets_invested,
+        )?;
+
+        let locked_asset_token_id = self.locked_asset_token_id().get();
+        let asset_token_id = self.asset_token_id().get();
+        let token_amount_out = self.actual_swap_via_router(
+            &attributes.token_id,
+            &amount,
+            &asset_token_id,
+            &amount_out_min,
+            max_hops,
+        );
+        require!(
+            token_amount_out.token_id == asset_token_id,
+            "Bad token received from router"
+        );
+        let assets_received = token_amount_out.amount;
+
+        let locked_asset_attributes = self
+            .get_locked_asset_attributes(&locked_asset_token_id, attributes.locked_assets_nonce)?;
+        let locked_assets_to_send =
+            core::cmp::min(assets_received.clone(), locked_assets_invested.clone());
+        self.send().direct(
+            &caller,
+            &locked_asset_token_id,
+            attributes.locked_assets_nonce,
+            &locked_assets_to_send,
+            &[],
+        );
+
+        let mut locked_assets_gained = GenericTokenAmountPair {
+            token_id: locked_asset_token_id.clone(),
+            token_nonce: 0,
+            amount: Self::BigUint::zero(),
+        };
+        if assets_received > locked_assets_invested {
+            let difference = &assets_received - &locked_assets_invested;
+            locked_assets_gained = self
+                .locked_asset_factory_swap_proxy(self.locked_asset_factory_address().get())
+                .create_and_forward_with_attributes(
+                    difference,
+                    caller.clone(),
+                    locked_asset_attributes,
+                    OptionalArg::None,
+                )
+                .execute_on_dest_context_custom_range(|_, after| (after - 1, after));
+        } else if assets_received < locked_assets_invested {
+            let difference = &locked_assets_invested - &assets_received;
+            self.nft_burn_tokens(
+                &locked_asset_token_id,
+                attributes.locked_assets_nonce,
+                &difference,
+            );
+        }
+
+        self.burn_tokens(&asset_token_id, &assets_received);
+        self.nft_burn_tokens(&wrapped_swap_token_id, token_nonce, &amount);
+
+        let wrapped_swap_token_amount = GenericTokenAmountPair {
+            token_id,
+            token_nonce,
+            amount,
+        };
+        let locked_asset_amount = GenericTokenAmountPair {
+            token_id: locked_asset_token_id,
+            token_nonce: attributes.locked_assets_nonce,
+            amount: locked_assets_to_send,
+        };
+        self.emit_swap_wrapped_token_proxy_event(
+            caller,
+            self.router_address().get(),
+            wrapped_swap_token_amount,
+            attributes,
+            locked_asset_amount,
+            locked_assets_gained,
+        );
+        Ok(())
+    }
+
+    fn actual_swap_via_router(
+        &self,
+        token_in: &TokenIdentifier,
+        amount_in: &Self::BigUint,
+        token_out: &TokenIdentifier,
+        amount_out_min: &Self::BigUint,
+        max_hops: usize,
+    ) -> FftTokenAmountPair<Self::BigUint> {
+        self.router_proxy(self.router_address().get())
+            .swap_exact_in_best_route(
+                token_in.clone(),
+                amount_in.clone(),
+                token_out.clone(),
+                amount_out_min.clone(),
+                max_hops,
+                OptionalArg::Some(BoxedBytes::from(ACCEPT_PAY_FUNC_NAME)),
+            )
+            .execute_on_dest_context()
+    }
+
+    fn get_locked_asset_attributes(
+        &self,
+        token_id: &TokenIdentifier,
+        token_nonce: Nonce,
+    ) -> SCResult<LockedAssetTokenAttributes> {
+        let token_info = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            token_id,
+            token_nonce,
+        );
+
+        let attributes = token_info.decode_attributes::<LockedAssetTokenAttributes>();
+        match attributes {
+            Result::Ok(decoded_obj) => Ok(decoded_obj),
+            Result::Err(_) => {
+                return sc_error!("Decoding error");
+            }
+        }
+    }
+
+    fn require_router_address_not_empty(&self) -> SCResult<()> {
+        require!(!self.router_address().is_empty(), "Router address not set");
+        Ok(())
+    }
+
+    fn require_wrapped_swap_token_id_not_empty(&self) -> SCResult<()> {
+        require!(!self.wrapped_swap_token_id().is_empty(), "Empty token id");
+        Ok(())
+    }
+
+    #[view(getRouterAddress)]
+    #[storage_mapper("router_address")]
+    fn router_address(&self) -> SingleValueMapper<Self::Storage, Address>;
+}
@@ -26,6 +26,7 @@ pub trait WrappedFarmTokenMerge:
     + proxy_common::ProxyCommonModule
     + nft_deposit::NftDepositModule
     + wrapped_lp_token_merge::WrappedLpTokenMerge
+    + permissions::PermissionsModule
 {
     #[proxy]
     fn locked_asset_factory_proxy(
@@ -42,6 +43,7 @@ pub trait WrappedFarmTokenMerge:
         farm_contract: Address,
         #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         let caller = self.blockchain().get_caller();
         require!(
             self.intermediated_farms().contains(&farm_contract),
@@ -18,6 +18,7 @@ pub trait WrappedLpTokenMerge:
     + token_supply::TokenSupplyModule
     + proxy_common::ProxyCommonModule
     + nft_deposit::NftDepositModule
+    + permissions::PermissionsModule
 {
     #[proxy]
     fn locked_asset_factory(&self, to: Address) -> sc_locked_asset_factory::Proxy<Self::SendApi>;
@@ -27,6 +28,7 @@ pub trait WrappedLpTokenMerge:
         &self,
         #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         let caller = self.blockchain().get_caller();
         self.merge_wrapped_lp_tokens_and_send(&caller, Option::None, opt_accept_funds_func)?;
         Ok(())
@@ -0,0 +1,60 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+#[derive(TopEncode)]
+pub struct GuardianEvent {
+    caller: Address,
+    reason: BoxedBytes,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[elrond_wasm::module]
+pub trait EventsModule {
+    fn emit_pause_all_event(self, caller: Address, reason: BoxedBytes) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.pause_all_event(
+            caller.clone(),
+            epoch,
+            GuardianEvent {
+                caller,
+                reason,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_resume_all_event(self, caller: Address, reason: BoxedBytes) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.resume_all_event(
+            caller.clone(),
+            epoch,
+            GuardianEvent {
+                caller,
+                reason,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    #[event("pause_all")]
+    fn pause_all_event(
+        self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        guardian_event: GuardianEvent,
+    );
+
+    #[event("resume_all")]
+    fn resume_all_event(
+        self,
+        #[indexed] caller: Address,
+        #[indexed] epoch: u64,
+        guardian_event: GuardianEvent,
+    );
+}
@@ -0,0 +1,367 @@
+#![no_std]
+
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+mod events;
+
+use core::iter::FromIterator;
+use guarded_contract_proxy::FarmState;
+use permissions::Role;
+
+const GAS_THRESHOLD: u64 = 30_000_000;
+
+mod guarded_contract_proxy {
+    elrond_wasm::imports!();
+    elrond_wasm::derive_imports!();
+
+    use permissions::Role;
+
+    #[derive(TopEncode, TopDecode, PartialEq, TypeAbi)]
+    pub enum FarmState {
+        Inactive,
+        Active,
+    }
+
+    #[elrond_wasm::proxy]
+    pub trait GuardedContract {
+        #[endpoint]
+        fn pause(&self);
+
+        #[endpoint]
+        fn resume(&self);
+
+        #[view(hasRole)]
+        fn has_role(&self, role: Role, address: Address) -> bool;
+
+        #[view(getState)]
+        fn get_state(&self) -> FarmState;
+
+        #[view(isPaused)]
+        fn is_paused(&self) -> bool;
+    }
+}
+
+mod router_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait Router {
+        #[endpoint(emergencyPause)]
+        fn emergency_pause(&self, from_pair_id: u64) -> MultiResult2<u64, usize>;
+
+        #[endpoint(emergencyResume)]
+        fn emergency_resume(&self, from_pair_id: u64) -> MultiResult2<u64, usize>;
+
+        #[view(isEmergencyActive)]
+        fn is_emergency_active(&self) -> bool;
+    }
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi, Clone, Copy)]
+pub enum GuardedContractType {
+    Router,
+    Pair,
+    Farm,
+    ProxyDex,
+    LockedAssetFactory,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct GuardedContract {
+    pub address: Address,
+    pub contract_type: GuardedContractType,
+}
+
+#[derive(TopEncode, TopDecode, PartialEq, TypeAbi, Clone, Copy)]
+pub enum GuardianState {
+    Idle,
+    Pausing,
+    Paused,
+    Resuming,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PauseRecord {
+    pub reason: BoxedBytes,
+    pub paused_by: Address,
+    pub pause_epoch: u64,
+    pub resume_epoch: u64,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi)]
+pub struct GuardianCursor {
+    pub contract_index: usize,
+    pub pair_id: u64,
+}
+
+#[elrond_wasm::contract]
+pub trait Guardian: permissions::PermissionsModule + events::EventsModule {
+    #[proxy]
+    fn guarded_contract_proxy(&self, to: Address) -> guarded_contract_proxy::Proxy<Self::SendApi>;
+
+    #[proxy]
+    fn router_proxy(&self, to: Address) -> router_proxy::Proxy<Self::SendApi>;
+
+    #[init]
+    fn init(&self) {
+        self.init_permissions(&self.blockchain().get_caller());
+    }
+
+    // The guardian must already be a pauser of the contract. Pairs only accept calls from
+    // their router, so they are paused through the router instead of being registered.
+    #[endpoint(registerContract)]
+    fn register_contract(
+        &self,
+        address: Address,
+        contract_type: GuardedContractType,
+    ) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        self.require_idle()?;
+        require!(
+            contract_type != GuardedContractType::Pair,
+            "Pairs are paused through their router"
+        );
+        require!(
+            self.get_guarded_contract_index(&address).is_none(),
+            "Contract already registered"
+        );
+
+        let guardian = self.blockchain().get_sc_address();
+        let is_pauser = self
+            .guarded_contract_proxy(address.clone())
+            .has_role(Role::Pauser, guardian)
+            .execute_on_dest_context();
+        require!(is_pauser, "Guardian is not a pauser of the contract");
+
+        self.guarded_contracts().push(&GuardedContract {
+            address,
+            contract_type,
+        });
+        Ok(())
+    }
+
+    #[endpoint(unregisterContract)]
+    fn unregister_contract(&self, address: Address) -> SCResult<()> {
+        self.require_role(Role::Admin)?;
+        self.require_idle()?;
+        let index = self.get_guarded_contract_index(&address);
+        require!(index.is_some(), "Contract not registered");
+
+        let index = index.unwrap();
+        let mut contracts = self.guarded_contracts();
+        let remaining: Vec<GuardedContract> = (1..=contracts.len())
+            .filter(|i| *i != index)
+            .map(|i| contracts.get(i))
+            .collect();
+        contracts.clear();
+        for contract in remaining.iter() {
+            contracts.push(contract);
+        }
+        Ok(())
+    }
+
+    // Starts a pause run, or continues one that ran out of gas. The reason is only recorded
+    // when a new run starts. Returns Pausing while more calls are needed and Paused once done.
+    #[endpoint(pauseAll)]
+    fn pause_all(&self, reason: BoxedBytes) -> SCResult<GuardianState> {
+        self.require_role(Role::Pauser)?;
+        let state = self.guardian_state().get();
+        require!(state != GuardianState::Paused, "Already paused");
+
+        if state != GuardianState::Pausing {
+            require!(!reason.is_empty(), "Empty reason");
+            let caller = self.blockchain().get_caller();
+            self.pause_records().push(&PauseRecord {
+                reason: reason.clone(),
+                paused_by: caller.clone(),
+                pause_epoch: self.blockchain().get_block_epoch(),
+                resume_epoch: 0,
+            });
+            self.guardian_state().set(&GuardianState::Pausing);
+            self.start_cursor();
+            self.emit_pause_all_event(caller, reason);
+        }
+
+        if self.run_guarded_operation(true) {
+            self.guardian_state().set(&GuardianState::Paused);
+        }
+        Ok(self.guardian_state().get())
+    }
+
+    // Only the contracts paused by pauseAll are resumed. Returns Resuming while more calls
+    // are needed and Idle once every contract is resumed.
+    #[endpoint(resumeAll)]
+    fn resume_all(&self) -> SCResult<GuardianState> {
+        self.require_role(Role::Pauser)?;
+        let state = self.guardian_state().get();
+        require!(
+            state == GuardianState::Paused || state == GuardianState::Resuming,
+            "Not paused"
+        );
+
+        if state == GuardianState::Paused {
+            let records = self.pause_records();
+            let last_index = records.len();
+            let mut record = records.get(last_index);
+            record.resume_epoch = self.blockchain().get_block_epoch();
+            records.set(last_index, &record);
+
+            self.guardian_state().set(&GuardianState::Resuming);
+            self.start_cursor();
+            self.emit_resume_all_event(self.blockchain().get_caller(), record.reason);
+        }
+
+        if self.run_guarded_operation(false) {
+            self.guardian_state().set(&GuardianState::Idle);
+        }
+        Ok(self.guardian_state().get())
+    }
+
+    // Returns true once every registered contract has been handled. Contracts that were
+    // already paused are skipped, so that resuming leaves them paused.
+    fn run_guarded_operation(&self, pause: bool) -> bool {
+        let contracts = self.guarded_contracts();
+        let mut cursor = self.guardian_cursor().get();
+
+        while cursor.contract_index <= contracts.len() {
+            if self.blockchain().get_gas_left() < GAS_THRESHOLD {
+                self.guardian_cursor().set(&cursor);
+                return false;
+            }
+
+            let contract = contracts.get(cursor.contract_index);
+            let starting = cursor.pair_id == 1;
+            if pause && starting && self.is_contract_active(&contract) {
+                self.paused_by_guardian().insert(contract.address.clone());
+            }
+
+            if self.paused_by_guardian().contains(&contract.address) {
+                if contract.contract_type == GuardedContractType::Router {
+                    let (next_pair_id, _) = if pause {
+                        self.router_proxy(contract.address.clone())
+                            .emergency_pause(cursor.pair_id)
+                            .execute_on_dest_context_custom_range(|_, after| (after - 2, after))
+                            .into_tuple()
+                    } else {
+                        self.router_proxy(contract.address.clone())
+                            .emergency_resume(cursor.pair_id)
+                            .execute_on_dest_context_custom_range(|_, after| (after - 2, after))
+                            .into_tuple()
+                    };
+                    if next_pair_id != 0 {
+                        cursor.pair_id = next_pair_id;
+                        self.guardian_cursor().set(&cursor);
+                        return false;
+                    }
+                } else if pause {
+                    self.guarded_contract_proxy(contract.address.clone())
+                        .pause()
+                        .execute_on_dest_context();
+                } else {
+                    self.guarded_contract_proxy(contract.address.clone())
+                        .resume()
+                        .execute_on_dest_context();
+                }
+
+                if !pause {
+                    self.paused_by_guardian().remove(&contract.address);
+                }
+            }
+
+            cursor.contract_index += 1;
+            cursor.pair_id = 1;
+        }
+
+        self.guardian_cursor().clear();
+        true
+    }
+
+    // A router counts as active when no emergency pause is running on it.
+    fn is_contract_active(&self, contract: &GuardedContract) -> bool {
+        let address = contract.address.clone();
+        match contract.contract_type {
+            GuardedContractType::Router => !self
+                .router_proxy(address)
+                .is_emergency_active()
+                .execute_on_dest_context(),
+            GuardedContractType::Farm => {
+                self.guarded_contract_proxy(address)
+                    .get_state()
+                    .execute_on_dest_context()
+                    == FarmState::Active
+            }
+            _ => !self
+                .guarded_contract_proxy(address)
+                .is_paused()
+                .execute_on_dest_context(),
+        }
+    }
+
+    fn start_cursor(&self) {
+        self.guardian_cursor().set(&GuardianCursor {
+            contract_index: 1,
+            pair_id: 1,
+        });
+    }
+
+    fn require_idle(&self) -> SCResult<()> {
+        require!(
+            self.guardian_state().get() == GuardianState::Idle,
+            "Pause or resume in progress"
+        );
+        Ok(())
+    }
+
+    fn get_guarded_contract_index(&self, address: &Address) -> Option<usize> {
+        let contracts = self.guarded_contracts();
+        (1..=contracts.len()).find(|i| &contracts.get(*i).address == address)
+    }
+
+    #[view(getGuardedContracts)]
+    fn get_guarded_contracts(&self) -> MultiResultVec<GuardedContract> {
+        let contracts = self.guarded_contracts();
+        let result: Vec<GuardedContract> =
+            (1..=contracts.len()).map(|i| contracts.get(i)).collect();
+        MultiResultVec::from_iter(result)
+    }
+
+    #[view(getPausedByGuardian)]
+    fn get_paused_by_guardian(&self) -> MultiResultVec<Address> {
+        self.paused_by_guardian().iter().collect()
+    }
+
+    #[view(getPauseRecords)]
+    fn get_pause_records(&self) -> MultiResultVec<PauseRecord> {
+        let records = self.pause_records();
+        let result: Vec<PauseRecord> = (1..=records.len()).map(|i| records.get(i)).collect();
+        MultiResultVec::from_iter(result)
+    }
+
+    #[view(getLastPauseRecord)]
+    fn get_last_pause_record(&self) -> Option<PauseRecord> {
+        let records = self.pause_records();
+        if records.is_empty() {
+            None
+        } else {
+            Some(records.get(records.len()))
+        }
+    }
+
+    #[storage_mapper("guarded_contracts")]
+    fn guarded_contracts(&self) -> VecMapper<Self::Storage, GuardedContract>;
+
+    #[storage_mapper("paused_by_guardian")]
+    fn paused_by_guardian(&self) -> SafeSetMapper<Self::Storage, Address>;
+
+    #[storage_mapper("pause_records")]
+    fn pause_records(&self) -> VecMapper<Self::Storage, PauseRecord>;
+
+    #[view(getGuardianState)]
+    #[storage_mapper("guardian_state")]
+    fn guardian_state(&self) -> SingleValueMapper<Self::Storage, GuardianState>;
+
+    #[view(getGuardianCursor)]
+    #[storage_mapper("guardian_cursor")]
+    fn guardian_cursor(&self) -> SingleValueMapper<Self::Storage, GuardianCursor>;
+}
@@ -0,0 +1,4 @@
+#![no_std]
+
+pub use sc_guardian::*;
+pub use elrond_wasm_output::*;
@@ -8,7 +8,10 @@ use super::locked_asset::UnlockSchedule;
 
 #[elrond_wasm::module]
 pub trait CacheModule:
-    locked_asset::LockedAssetModule + token_supply::TokenSupplyModule + token_send::TokenSendModule
+    locked_asset::LockedAssetModule
+    + token_supply::TokenSupplyModule
+    + token_send::TokenSendModule
+    + permissions::PermissionsModule
 {
     #[inline(always)]
     fn get_sft_nonce_for_unlock_schedule(&self, unlock_schedule: &UnlockSchedule) -> Option<Nonce> {
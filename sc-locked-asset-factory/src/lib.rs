@@ -2,7 +2,7 @@
 
 mod cache;
 mod events;
-mod locked_asset;
+pub mod locked_asset;
 pub mod locked_asset_token_merge;
 
 elrond_wasm::imports!();
@@ -15,6 +15,7 @@ const EPOCHS_IN_MONTH: u64 = 30;
 
 use common_structs::{Epoch, FftTokenAmountPair, GenericTokenAmountPair, Nonce, UnlockMilestone};
 use locked_asset::UnlockSchedule;
+use permissions::Role;
 
 use crate::locked_asset::LockedAssetTokenAttributes;
 
@@ -28,6 +29,7 @@ pub trait LockedAssetFactory:
     + token_merge::TokenMergeModule
     + locked_asset_token_merge::LockedAssetTokenMergeModule
     + events::EventsModule
+    + permissions::PermissionsModule
 {
     #[init]
     fn init(
@@ -54,12 +56,13 @@ pub trait LockedAssetFactory:
 
         self.asset_token_id().set(&asset_token_id);
         self.default_unlock_period().set(&default_unlock_period.0);
+        self.init_permissions(&self.blockchain().get_caller());
         Ok(())
     }
 
     #[endpoint]
     fn whitelist(&self, address: Address) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::Admin)?;
 
         let is_new = self.whitelisted_contracts().insert(address);
         require!(is_new, "Address already whitelisted");
@@ -68,7 +71,7 @@ pub trait LockedAssetFactory:
 
     #[endpoint(removeWhitelist)]
     fn remove_whitelist(&self, address: Address) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::Admin)?;
 
         let is_removed = self.whitelisted_contracts().remove(&address);
         require!(is_removed, "Addresss not whitelisted");
@@ -83,6 +86,7 @@ pub trait LockedAssetFactory:
         start_epoch: Epoch,
         #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
     ) -> SCResult<GenericTokenAmountPair<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
         let caller = self.blockchain().get_caller();
         require!(
             self.whitelisted_contracts().contains(&caller),
@@ -108,6 +112,37 @@ pub trait LockedAssetFactory:
         Ok(new_token)
     }
 
+    // Used by contracts that hand back more Locked MEX than they received, so the extra amount
+    // keeps the unlock schedule of the tokens it was derived from.
+    #[endpoint(createAndForwardWithAttributes)]
+    fn create_and_forward_with_attributes(
+        &self,
+        amount: Self::BigUint,
+        address: Address,
+        attributes: LockedAssetTokenAttributes,
+        #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
+    ) -> SCResult<GenericTokenAmountPair<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.whitelisted_contracts().contains(&caller),
+            "Permission denied"
+        );
+        require!(!self.locked_asset_token_id().is_empty(), "No SFT issued");
+        require!(amount > 0, "Zero input amount");
+        require!(
+            !attributes.unlock_schedule.unlock_milestones.is_empty(),
+            "Empty unlock schedule"
+        );
+
+        let new_token =
+            self.produce_tokens_and_send(&amount, &attributes, &address, &opt_accept_funds_func)?;
+
+        let epoch = self.blockchain().get_block_epoch();
+        self.emit_create_and_forward_event(caller, address, new_token.clone(), attributes, epoch);
+        Ok(new_token)
+    }
+
     #[payable("*")]
     #[endpoint(unlockAssets)]
     fn unlock_assets(
@@ -116,6 +151,7 @@ pub trait LockedAssetFactory:
         #[payment_amount] amount: Self::BigUint,
         #[payment_nonce] token_nonce: Nonce,
     ) -> SCResult<()> {
+        require!(self.is_active(), "Not active");
         let locked_token_id = self.locked_asset_token_id().get();
         require!(token_id == locked_token_id, "Bad payment token");
 
@@ -248,7 +284,7 @@ pub trait LockedAssetFactory:
         token_ticker: BoxedBytes,
         #[payment_amount] issue_cost: Self::BigUint,
     ) -> SCResult<AsyncCall<Self::SendApi>> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::Admin)?;
         require!(
             self.locked_asset_token_id().is_empty(),
             "NFT already issued"
@@ -304,7 +340,7 @@ pub trait LockedAssetFactory:
         address: Address,
         #[var_args] roles: VarArgs<EsdtLocalRole>,
     ) -> SCResult<AsyncCall<Self::SendApi>> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::Admin)?;
         require!(
             !self.locked_asset_token_id().is_empty(),
             "Locked asset SFT not issued"
@@ -332,7 +368,7 @@ pub trait LockedAssetFactory:
 
     #[endpoint(setNftDepositMaxLen)]
     fn set_nft_deposit_max_len(&self, max_len: usize) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::Admin)?;
         self.nft_deposit_max_len().set(&max_len);
         Ok(())
     }
@@ -2,6 +2,7 @@ elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
 use common_structs::{Epoch, Nonce, UnlockMilestone};
+use permissions::Role;
 
 pub const PERCENTAGE_TOTAL: u64 = 100;
 
@@ -17,7 +18,9 @@ pub struct LockedAssetTokenAttributes {
 }
 
 #[elrond_wasm::module]
-pub trait LockedAssetModule: token_supply::TokenSupplyModule + token_send::TokenSendModule {
+pub trait LockedAssetModule:
+    token_supply::TokenSupplyModule + token_send::TokenSendModule + permissions::PermissionsModule
+{
     fn create_and_send_locked_assets(
         &self,
         amount: &Self::BigUint,
@@ -177,11 +180,30 @@ pub trait LockedAssetModule: token_supply::TokenSupplyModule + token_send::Token
 
     #[endpoint]
     fn set_transfer_exec_gas_limit(&self, gas_limit: u64) -> SCResult<()> {
-        only_owner!(self, "Permission denied");
+        self.require_role(Role::Admin)?;
         self.transfer_exec_gas_limit().set(&gas_limit);
         Ok(())
     }
 
+    #[inline]
+    fn is_active(&self) -> bool {
+        !self.paused().get()
+    }
+
+    #[endpoint]
+    fn pause(&self) -> SCResult<()> {
+        self.require_role(Role::Pauser)?;
+        self.paused().set(&true);
+        Ok(())
+    }
+
+    #[endpoint]
+    fn resume(&self) -> SCResult<()> {
+        self.require_role(Role::Pauser)?;
+        self.paused().clear();
+        Ok(())
+    }
+
     fn mint_and_send_assets(&self, dest: &Address, amount: &Self::BigUint) {
         if amount > &0 {
             let asset_token_id = self.asset_token_id().get();
@@ -200,4 +222,8 @@ pub trait LockedAssetModule: token_supply::TokenSupplyModule + token_send::Token
     #[view(getAssetTokenId)]
     #[storage_mapper("asset_token_id")]
     fn asset_token_id(&self) -> SingleValueMapper<Self::Storage, TokenIdentifier>;
+
+    #[view(isPaused)]
+    #[storage_mapper("paused")]
+    fn paused(&self) -> SingleValueMapper<Self::Storage, bool>;
 }
@@ -20,12 +20,14 @@ pub trait LockedAssetTokenMergeModule:
     + token_send::TokenSendModule
     + nft_deposit::NftDepositModule
     + token_merge::TokenMergeModule
+    + permissions::PermissionsModule
 {
     #[endpoint(mergeLockedAssetTokens)]
     fn merge_locked_asset_tokens(
         &self,
         #[var_args] opt_accept_funds_func: OptionalArg<BoxedBytes>,
     ) -> SCResult<GenericTokenAmountPair<Self::BigUint>> {
+        require!(self.is_active(), "Not active");
         let caller = self.blockchain().get_caller();
         let deposit = self.nft_deposit(&caller).get();
         require!(!deposit.is_empty(), "Empty deposit");